@@ -0,0 +1,286 @@
+//! Copying between grids.
+//!
+//! The lowest corner of the source grid is placed at an offset in
+//! the destination grid, and the copy is clipped to the destination
+//! bounds. `ArrayGrid2::blit_from` and `ArrayGrid3::blit_from` are
+//! faster equivalents of `blit` and `blit3` between heap grids.
+
+use crate::{
+    scalar::Scalar,
+    range::{BoundRange, clip},
+    grid2::{Grid2, Grid2Get, Grid2Set},
+    grid3::{Grid3, Grid3Get, Grid3Set},
+};
+use mint::{Vector2, Vector3};
+use std::ops::Range;
+
+fn bound_range<B: BoundRange<S>, S: Scalar>(bound: B) -> Range<S> {
+    bound.lower_inclusive()..bound.upper_exclusive()
+}
+
+/// Destination ranges covered by a source grid, and the source
+/// coordinate corresponding to their lowest corner.
+fn overlap2<G, D, S>(src: &G, dst: &D, dst_offset: Vector2<S>) -> Option<(Range<S>, Range<S>, Vector2<S>)>
+where
+    G: Grid2<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    D: Grid2<S>,
+    D::XBound: BoundRange<S>,
+    D::YBound: BoundRange<S>,
+    S: Scalar,
+{
+    let (x_range, src_x) = clip(bound_range(src.x_bound()), dst_offset.x, bound_range(dst.x_bound()))?;
+    let (y_range, src_y) = clip(bound_range(src.y_bound()), dst_offset.y, bound_range(dst.y_bound()))?;
+    Some((x_range, y_range, Vector2 { x: src_x, y: src_y }))
+}
+
+/// Visit each pair of source and destination coordinates in an
+/// overlap.
+fn each2<S: Scalar>(overlap: Option<(Range<S>, Range<S>, Vector2<S>)>, mut f: impl FnMut(Vector2<S>, Vector2<S>)) {
+    if let Some((x_range, y_range, src_start)) = overlap {
+        let mut src_coord = src_start;
+        let mut y = y_range.start;
+        while y < y_range.end {
+            src_coord.x = src_start.x;
+            let mut x = x_range.start;
+            while x < x_range.end {
+                f(src_coord, Vector2 { x, y });
+                src_coord.x = src_coord.x + S::ONE;
+                x = x + S::ONE;
+            }
+            src_coord.y = src_coord.y + S::ONE;
+            y = y + S::ONE;
+        }
+    }
+}
+
+/// Destination ranges covered by a source grid, and the source
+/// coordinate corresponding to their lowest corner.
+fn overlap3<G, D, S>(src: &G, dst: &D, dst_offset: Vector3<S>) -> Option<(Range<S>, Range<S>, Range<S>, Vector3<S>)>
+where
+    G: Grid3<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    D: Grid3<S>,
+    D::XBound: BoundRange<S>,
+    D::YBound: BoundRange<S>,
+    D::ZBound: BoundRange<S>,
+    S: Scalar,
+{
+    let (x_range, src_x) = clip(bound_range(src.x_bound()), dst_offset.x, bound_range(dst.x_bound()))?;
+    let (y_range, src_y) = clip(bound_range(src.y_bound()), dst_offset.y, bound_range(dst.y_bound()))?;
+    let (z_range, src_z) = clip(bound_range(src.z_bound()), dst_offset.z, bound_range(dst.z_bound()))?;
+    Some((x_range, y_range, z_range, Vector3 { x: src_x, y: src_y, z: src_z }))
+}
+
+/// Visit each pair of source and destination coordinates in an
+/// overlap.
+fn each3<S: Scalar>(overlap: Option<(Range<S>, Range<S>, Range<S>, Vector3<S>)>, mut f: impl FnMut(Vector3<S>, Vector3<S>)) {
+    if let Some((x_range, y_range, z_range, src_start)) = overlap {
+        let mut src_coord = src_start;
+        let mut z = z_range.start;
+        while z < z_range.end {
+            src_coord.y = src_start.y;
+            let mut y = y_range.start;
+            while y < y_range.end {
+                src_coord.x = src_start.x;
+                let mut x = x_range.start;
+                while x < x_range.end {
+                    f(src_coord, Vector3 { x, y, z });
+                    src_coord.x = src_coord.x + S::ONE;
+                    x = x + S::ONE;
+                }
+                src_coord.y = src_coord.y + S::ONE;
+                y = y + S::ONE;
+            }
+            src_coord.z = src_coord.z + S::ONE;
+            z = z + S::ONE;
+        }
+    }
+}
+
+/// Copy a grid into another, with its lowest corner at dst_offset.
+pub fn blit<G, D, I, S>(src: &G, dst: &mut D, dst_offset: I)
+where
+    G: Grid2Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    D: Grid2Set<S, Item=G::Item>,
+    D::XBound: BoundRange<S>,
+    D::YBound: BoundRange<S>,
+    I: Into<Vector2<S>>,
+    S: Scalar,
+{
+    let overlap = overlap2(src, dst, dst_offset.into());
+    each2(overlap, |from, to| dst.set(to, src.get(from)));
+}
+
+/// Copy the elements of a grid which satisfy a predicate into
+/// another, with its lowest corner at dst_offset.
+pub fn blit_masked<G, D, I, F, S>(src: &G, dst: &mut D, dst_offset: I, mut mask: F)
+where
+    G: Grid2Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    D: Grid2Set<S, Item=G::Item>,
+    D::XBound: BoundRange<S>,
+    D::YBound: BoundRange<S>,
+    I: Into<Vector2<S>>,
+    F: FnMut(&G::Item) -> bool,
+    S: Scalar,
+{
+    let overlap = overlap2(src, dst, dst_offset.into());
+    each2(overlap, |from, to| {
+        let elem = src.get(from);
+        if mask(&elem) {
+            dst.set(to, elem);
+        }
+    });
+}
+
+/// Blend a grid into another, with its lowest corner at dst_offset.
+///
+/// Each destination element is replaced with the result of passing
+/// it and the source element to the blend function.
+pub fn blit_with<G, D, I, F, S>(src: &G, dst: &mut D, dst_offset: I, mut blend: F)
+where
+    G: Grid2Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    D: Grid2Get<S> + Grid2Set<S>,
+    D::XBound: BoundRange<S>,
+    D::YBound: BoundRange<S>,
+    I: Into<Vector2<S>>,
+    F: FnMut(D::Item, G::Item) -> D::Item,
+    S: Scalar,
+{
+    let overlap = overlap2(src, dst, dst_offset.into());
+    each2(overlap, |from, to| {
+        let elem = blend(dst.get(to), src.get(from));
+        dst.set(to, elem);
+    });
+}
+
+/// Set every element in a rectangle, clipped to the grid's bounds.
+pub fn fill_rect<D, S>(dst: &mut D, x: Range<S>, y: Range<S>, value: D::Item)
+where
+    D: Grid2Set<S>,
+    D::Item: Clone,
+    D::XBound: BoundRange<S>,
+    D::YBound: BoundRange<S>,
+    S: Scalar,
+{
+    let x = clip(x.clone(), x.start, bound_range(dst.x_bound()));
+    let y = clip(y.clone(), y.start, bound_range(dst.y_bound()));
+    if let (Some((x_range, _)), Some((y_range, _))) = (x, y) {
+        let mut y = y_range.start;
+        while y < y_range.end {
+            let mut x = x_range.start;
+            while x < x_range.end {
+                dst.set(Vector2 { x, y }, value.clone());
+                x = x + S::ONE;
+            }
+            y = y + S::ONE;
+        }
+    }
+}
+
+/// Copy a grid into another, with its lowest corner at dst_offset.
+pub fn blit3<G, D, I, S>(src: &G, dst: &mut D, dst_offset: I)
+where
+    G: Grid3Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    D: Grid3Set<S, Item=G::Item>,
+    D::XBound: BoundRange<S>,
+    D::YBound: BoundRange<S>,
+    D::ZBound: BoundRange<S>,
+    I: Into<Vector3<S>>,
+    S: Scalar,
+{
+    let overlap = overlap3(src, dst, dst_offset.into());
+    each3(overlap, |from, to| dst.set(to, src.get(from)));
+}
+
+/// Copy the elements of a grid which satisfy a predicate into
+/// another, with its lowest corner at dst_offset.
+pub fn blit3_masked<G, D, I, F, S>(src: &G, dst: &mut D, dst_offset: I, mut mask: F)
+where
+    G: Grid3Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    D: Grid3Set<S, Item=G::Item>,
+    D::XBound: BoundRange<S>,
+    D::YBound: BoundRange<S>,
+    D::ZBound: BoundRange<S>,
+    I: Into<Vector3<S>>,
+    F: FnMut(&G::Item) -> bool,
+    S: Scalar,
+{
+    let overlap = overlap3(src, dst, dst_offset.into());
+    each3(overlap, |from, to| {
+        let elem = src.get(from);
+        if mask(&elem) {
+            dst.set(to, elem);
+        }
+    });
+}
+
+/// Blend a grid into another, with its lowest corner at dst_offset.
+///
+/// Each destination element is replaced with the result of passing
+/// it and the source element to the blend function.
+pub fn blit3_with<G, D, I, F, S>(src: &G, dst: &mut D, dst_offset: I, mut blend: F)
+where
+    G: Grid3Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    D: Grid3Get<S> + Grid3Set<S>,
+    D::XBound: BoundRange<S>,
+    D::YBound: BoundRange<S>,
+    D::ZBound: BoundRange<S>,
+    I: Into<Vector3<S>>,
+    F: FnMut(D::Item, G::Item) -> D::Item,
+    S: Scalar,
+{
+    let overlap = overlap3(src, dst, dst_offset.into());
+    each3(overlap, |from, to| {
+        let elem = blend(dst.get(to), src.get(from));
+        dst.set(to, elem);
+    });
+}
+
+/// Set every element in a box, clipped to the grid's bounds.
+pub fn fill_box<D, S>(dst: &mut D, x: Range<S>, y: Range<S>, z: Range<S>, value: D::Item)
+where
+    D: Grid3Set<S>,
+    D::Item: Clone,
+    D::XBound: BoundRange<S>,
+    D::YBound: BoundRange<S>,
+    D::ZBound: BoundRange<S>,
+    S: Scalar,
+{
+    let x = clip(x.clone(), x.start, bound_range(dst.x_bound()));
+    let y = clip(y.clone(), y.start, bound_range(dst.y_bound()));
+    let z = clip(z.clone(), z.start, bound_range(dst.z_bound()));
+    if let (Some((x_range, _)), Some((y_range, _)), Some((z_range, _))) = (x, y, z) {
+        let mut z = z_range.start;
+        while z < z_range.end {
+            let mut y = y_range.start;
+            while y < y_range.end {
+                let mut x = x_range.start;
+                while x < x_range.end {
+                    dst.set(Vector3 { x, y, z }, value.clone());
+                    x = x + S::ONE;
+                }
+                y = y + S::ONE;
+            }
+            z = z + S::ONE;
+        }
+    }
+}
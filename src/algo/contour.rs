@@ -0,0 +1,162 @@
+//! Iso-line extraction from 2D scalar fields.
+
+use crate::{
+    scalar::Scalar,
+    grid2::Grid2Get,
+    range::BoundRange,
+};
+use mint::{Point2, Vector2};
+use std::collections::{HashMap, HashSet};
+
+/// Polyline in grid coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polyline {
+    /// For closed polylines, the last point connects back to
+    /// the first, and is not repeated.
+    pub points: Vec<Point2<f32>>,
+    pub closed: bool,
+}
+
+/// Edge between two adjacent elements, identified by its lower
+/// element and whether it runs along x or along y.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+struct Edge {
+    x: i64,
+    y: i64,
+    along_x: bool,
+}
+
+/// Extract the iso-lines where the field crosses a threshold.
+///
+/// Elements are at integer coordinates, and crossing points are
+/// linearly interpolated along the edges between them. Elements
+/// at or above the threshold are inside, and polylines run with
+/// the inside on their left (in a y-up coordinate system).
+/// Polylines which reach the edge of the grid are open, all
+/// others are closed.
+///
+/// Saddle cells are disambiguated by the average of their four
+/// corners.
+pub fn marching_squares<G, S>(grid: &G, threshold: f32) -> Vec<Polyline>
+where
+    G: Grid2Get<S>,
+    G::Item: Into<f32>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    S: Scalar,
+{
+    let x_bound = grid.x_bound();
+    let y_bound = grid.y_bound();
+    let (x_start, x_end) = (x_bound.lower_inclusive().to_i64(), x_bound.upper_exclusive().to_i64());
+    let (y_start, y_end) = (y_bound.lower_inclusive().to_i64(), y_bound.upper_exclusive().to_i64());
+    
+    let value = |x: i64, y: i64| -> f32 {
+        grid.get(Vector2 {
+            x: S::from_i64(x),
+            y: S::from_i64(y),
+        }).into()
+    };
+    
+    // directed segments, start edge → end edge
+    let mut segments: Vec<(Edge, Edge)> = Vec::new();
+    for y in y_start..y_end - 1 {
+        for x in x_start..x_end - 1 {
+            // corners and edges in counter-clockwise order
+            let values = [
+                value(x, y),
+                value(x + 1, y),
+                value(x + 1, y + 1),
+                value(x, y + 1),
+            ];
+            let edges = [
+                Edge { x, y, along_x: true },
+                Edge { x: x + 1, y, along_x: false },
+                Edge { x, y: y + 1, along_x: true },
+                Edge { x, y, along_x: false },
+            ];
+            let inside = values.map(|v| v >= threshold);
+            
+            // edge k runs from corner k to corner k + 1, and is
+            // an exit if it leaves the inside, or an entry if it
+            // enters the inside
+            let exits: Vec<usize> = (0..4)
+                .filter(|&k| inside[k] && !inside[(k + 1) % 4])
+                .collect();
+            let entries: Vec<usize> = (0..4)
+                .filter(|&k| !inside[k] && inside[(k + 1) % 4])
+                .collect();
+            
+            match exits.len() {
+                0 => (),
+                1 => segments.push((edges[exits[0]], edges[entries[0]])),
+                _ => {
+                    // saddle: if the center is inside, the two inside
+                    // corners are connected, so each segment cuts off
+                    // an outside corner
+                    let center = values.iter().sum::<f32>() / 4.0;
+                    let connected = center >= threshold;
+                    for &k in &exits {
+                        let entry = if connected { (k + 1) % 4 } else { (k + 3) % 4 };
+                        segments.push((edges[k], edges[entry]));
+                    }
+                }
+            }
+        }
+    }
+    
+    let point = |edge: Edge| -> Point2<f32> {
+        let (x2, y2) = if edge.along_x { (edge.x + 1, edge.y) } else { (edge.x, edge.y + 1) };
+        let a = value(edge.x, edge.y);
+        let b = value(x2, y2);
+        let t = (threshold - a) / (b - a);
+        Point2 {
+            x: edge.x as f32 + (x2 - edge.x) as f32 * t,
+            y: edge.y as f32 + (y2 - edge.y) as f32 * t,
+        }
+    };
+    
+    let mut next: HashMap<Edge, Edge> = HashMap::new();
+    let mut has_prev: HashSet<Edge> = HashSet::new();
+    for &(from, to) in &segments {
+        next.insert(from, to);
+        has_prev.insert(to);
+    }
+    
+    let mut polylines = Vec::new();
+    
+    // open polylines begin at an edge with no predecessor
+    for &(start, _) in &segments {
+        if has_prev.contains(&start) {
+            continue;
+        }
+        let mut points = vec![point(start)];
+        let mut curr = start;
+        while let Some(to) = next.remove(&curr) {
+            points.push(point(to));
+            curr = to;
+        }
+        polylines.push(Polyline {
+            points,
+            closed: false,
+        });
+    }
+    
+    // all remaining segments form loops
+    for &(start, _) in &segments {
+        if !next.contains_key(&start) {
+            continue;
+        }
+        let mut points = Vec::new();
+        let mut curr = start;
+        while let Some(to) = next.remove(&curr) {
+            points.push(point(curr));
+            curr = to;
+        }
+        polylines.push(Polyline {
+            points,
+            closed: true,
+        });
+    }
+    
+    polylines
+}
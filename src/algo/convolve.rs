@@ -0,0 +1,243 @@
+//! Convolution with kernel grids.
+//!
+//! Kernels are centered on their middle element (rounding up for
+//! even lengths), and the result at p is the sum over kernel offsets
+//! o of `kernel(o) * grid(p - o)`.
+//!
+//! The output has the given bounds, but the input is read up to
+//! the kernel's reach beyond them. Edge handling is therefore
+//! chosen by the input grid: pass a `wrapping` view to wrap around,
+//! or an `oob_handler` view to supply values beyond the edges.
+
+use crate::{
+    scalar::Scalar,
+    range::BoundRange,
+    grid2::{
+        Grid2,
+        Grid2Get,
+        backends::heap::ArrayGrid2,
+        combinate::neworigin::Grid2NewOrigin,
+    },
+    grid3::{
+        Grid3,
+        Grid3Get,
+        backends::heap::ArrayGrid3,
+        combinate::neworigin::Grid3NewOrigin,
+    },
+};
+use super::dense::Dense;
+use mint::{Vector2, Vector3};
+
+/// Reach of a kernel of some length below and above an output element.
+fn reach(len: usize) -> (i64, i64) {
+    let center = (len / 2) as i64;
+    (len as i64 - 1 - center, center)
+}
+
+/// Input region read to produce the output region.
+fn input_region(lo: &[i64], lens: &[usize], kernel_lens: &[usize]) -> (Vec<i64>, Vec<usize>) {
+    lo.iter()
+        .zip(lens)
+        .zip(kernel_lens)
+        .map(|((&lo, &len), &k)| {
+            let (below, _) = reach(k);
+            (lo - below, if len == 0 { 0 } else { len + k.max(1) - 1 })
+        })
+        .unzip()
+}
+
+fn index(coord: &[i64], lens: &[usize]) -> usize {
+    let mut index = 0;
+    let mut stride = 1;
+    for (&c, &len) in coord.iter().zip(lens) {
+        index += c as usize * stride;
+        stride *= len;
+    }
+    index
+}
+
+/// Convolve a dense input region with a dense kernel.
+fn full(input: &Dense<f32>, kernel: &Dense<f32>, lens: &[usize]) -> Dense<f32> {
+    let taps: Vec<(Vec<i64>, f32)> = (0..kernel.data.len())
+        .filter(|&i| kernel.data[i] != 0.0)
+        .map(|i| (kernel.coord(i), kernel.data[i]))
+        .collect();
+    let mut at = vec![0; lens.len()];
+    Dense::with_lens(lens, |coord| {
+        let mut sum = 0.0;
+        for (k, weight) in &taps {
+            // p - o, relative to the input region's lowest corner,
+            // is p + (len - 1 - k)
+            for axis in 0..coord.len() {
+                at[axis] = coord[axis] + kernel.lens[axis] as i64 - 1 - k[axis];
+            }
+            sum += weight * input.data[index(&at, &input.lens)];
+        }
+        sum
+    })
+}
+
+/// Convolve a dense input region with one 1D kernel per axis.
+fn separable(input: Dense<f32>, kernels: &[&[f32]]) -> Dense<f32> {
+    let mut buf = input;
+    for (axis, kernel) in kernels.iter().enumerate() {
+        let mut lens = buf.lens.clone();
+        lens[axis] = if lens[axis] == 0 { 0 } else { lens[axis] + 1 - kernel.len().max(1) };
+        let mut at = vec![0; lens.len()];
+        buf = Dense::with_lens(&lens, |coord| {
+            at.copy_from_slice(coord);
+            let mut sum = 0.0;
+            for (k, &weight) in kernel.iter().enumerate() {
+                at[axis] = coord[axis] + kernel.len() as i64 - 1 - k as i64;
+                sum += weight * buf.data[index(&at, &buf.lens)];
+            }
+            sum
+        });
+    }
+    buf
+}
+
+fn bounds<S: Scalar>(bounds: &[(S, S)]) -> (Vec<i64>, Vec<usize>) {
+    bounds.iter()
+        .map(|&(lo, hi)| (lo.to_i64(), (hi.to_i64() - lo.to_i64()).max(0) as usize))
+        .unzip()
+}
+
+fn read2<G, S>(grid: &G, lo: &[i64], lens: &[usize]) -> Dense<f32>
+where
+    G: Grid2Get<S>,
+    G::Item: Into<f32>,
+    S: Scalar,
+{
+    Dense::with_lens(lens, |c| grid.get(Vector2 {
+        x: S::from_i64(lo[0] + c[0]),
+        y: S::from_i64(lo[1] + c[1]),
+    }).into())
+}
+
+fn read3<G, S>(grid: &G, lo: &[i64], lens: &[usize]) -> Dense<f32>
+where
+    G: Grid3Get<S>,
+    G::Item: Into<f32>,
+    S: Scalar,
+{
+    Dense::with_lens(lens, |c| grid.get(Vector3 {
+        x: S::from_i64(lo[0] + c[0]),
+        y: S::from_i64(lo[1] + c[1]),
+        z: S::from_i64(lo[2] + c[2]),
+    }).into())
+}
+
+/// Convolve a Grid2 with a kernel grid, over the given bounds.
+pub fn convolve<G, K, X, Y, S>(grid: &G, kernel: &K, x_bound: X, y_bound: Y) -> Grid2NewOrigin<ArrayGrid2<f32, S>, S>
+where
+    G: Grid2Get<S>,
+    G::Item: Into<f32>,
+    K: Grid2Get<S>,
+    K::Item: Into<f32>,
+    K::XBound: BoundRange<S>,
+    K::YBound: BoundRange<S>,
+    X: BoundRange<S>,
+    Y: BoundRange<S>,
+    S: Scalar,
+{
+    let kernel = Dense::from_grid2(kernel, Into::into);
+    let (lo, lens) = bounds(&[
+        (x_bound.lower_inclusive(), x_bound.upper_exclusive()),
+        (y_bound.lower_inclusive(), y_bound.upper_exclusive()),
+    ]);
+    let (in_lo, in_lens) = input_region(&lo, &lens, &kernel.lens);
+    let input = read2(grid, &in_lo, &in_lens);
+    full(&input, &kernel, &lens).into_grid2()
+        .new_origin(Vector2 { x: x_bound.lower_inclusive(), y: y_bound.lower_inclusive() })
+}
+
+/// Convolve a Grid2 with a separable kernel, over the given bounds.
+///
+/// This is equivalent to convolving with the kernel whose element
+/// at <x, y> is `kernel_x[x] * kernel_y[y]`, but takes time
+/// proportional to the sum rather than the product of their lengths.
+pub fn convolve_separable<G, X, Y, S>(grid: &G, kernel_x: &[f32], kernel_y: &[f32], x_bound: X, y_bound: Y) -> Grid2NewOrigin<ArrayGrid2<f32, S>, S>
+where
+    G: Grid2Get<S>,
+    G::Item: Into<f32>,
+    X: BoundRange<S>,
+    Y: BoundRange<S>,
+    S: Scalar,
+{
+    let (lo, lens) = bounds(&[
+        (x_bound.lower_inclusive(), x_bound.upper_exclusive()),
+        (y_bound.lower_inclusive(), y_bound.upper_exclusive()),
+    ]);
+    let (in_lo, in_lens) = input_region(&lo, &lens, &[kernel_x.len(), kernel_y.len()]);
+    let input = read2(grid, &in_lo, &in_lens);
+    separable(input, &[kernel_x, kernel_y]).into_grid2()
+        .new_origin(Vector2 { x: x_bound.lower_inclusive(), y: y_bound.lower_inclusive() })
+}
+
+/// Convolve a Grid3 with a kernel grid, over the given bounds.
+pub fn convolve3<G, K, X, Y, Z, S>(grid: &G, kernel: &K, x_bound: X, y_bound: Y, z_bound: Z) -> Grid3NewOrigin<ArrayGrid3<f32, S>, S>
+where
+    G: Grid3Get<S>,
+    G::Item: Into<f32>,
+    K: Grid3Get<S>,
+    K::Item: Into<f32>,
+    K::XBound: BoundRange<S>,
+    K::YBound: BoundRange<S>,
+    K::ZBound: BoundRange<S>,
+    X: BoundRange<S>,
+    Y: BoundRange<S>,
+    Z: BoundRange<S>,
+    S: Scalar,
+{
+    let kernel = Dense::from_grid3(kernel, Into::into);
+    let (lo, lens) = bounds(&[
+        (x_bound.lower_inclusive(), x_bound.upper_exclusive()),
+        (y_bound.lower_inclusive(), y_bound.upper_exclusive()),
+        (z_bound.lower_inclusive(), z_bound.upper_exclusive()),
+    ]);
+    let (in_lo, in_lens) = input_region(&lo, &lens, &kernel.lens);
+    let input = read3(grid, &in_lo, &in_lens);
+    full(&input, &kernel, &lens).into_grid3()
+        .new_origin(Vector3 {
+            x: x_bound.lower_inclusive(),
+            y: y_bound.lower_inclusive(),
+            z: z_bound.lower_inclusive(),
+        })
+}
+
+/// Convolve a Grid3 with a separable kernel, over the given bounds.
+///
+/// This is equivalent to convolving with the kernel whose element
+/// at <x, y, z> is `kernel_x[x] * kernel_y[y] * kernel_z[z]`.
+pub fn convolve3_separable<G, X, Y, Z, S>(
+    grid: &G,
+    kernel_x: &[f32],
+    kernel_y: &[f32],
+    kernel_z: &[f32],
+    x_bound: X,
+    y_bound: Y,
+    z_bound: Z,
+) -> Grid3NewOrigin<ArrayGrid3<f32, S>, S>
+where
+    G: Grid3Get<S>,
+    G::Item: Into<f32>,
+    X: BoundRange<S>,
+    Y: BoundRange<S>,
+    Z: BoundRange<S>,
+    S: Scalar,
+{
+    let (lo, lens) = bounds(&[
+        (x_bound.lower_inclusive(), x_bound.upper_exclusive()),
+        (y_bound.lower_inclusive(), y_bound.upper_exclusive()),
+        (z_bound.lower_inclusive(), z_bound.upper_exclusive()),
+    ]);
+    let (in_lo, in_lens) = input_region(&lo, &lens, &[kernel_x.len(), kernel_y.len(), kernel_z.len()]);
+    let input = read3(grid, &in_lo, &in_lens);
+    separable(input, &[kernel_x, kernel_y, kernel_z]).into_grid3()
+        .new_origin(Vector3 {
+            x: x_bound.lower_inclusive(),
+            y: y_bound.lower_inclusive(),
+            z: z_bound.lower_inclusive(),
+        })
+}
@@ -0,0 +1,187 @@
+//! Dense scratch buffers for algorithms.
+
+use crate::{
+    scalar::Scalar,
+    range::BoundRange,
+    grid2::{
+        Grid2,
+        Grid2Get,
+        backends::heap::ArrayGrid2,
+    },
+    grid3::{
+        Grid3,
+        Grid3Get,
+        backends::heap::ArrayGrid3,
+    },
+};
+use super::Edge;
+use mint::{Vector2, Vector3};
+
+/// Elements of a bounded grid in an x-fastest buffer, indexed from
+/// the grid's lowest corner.
+pub(crate) struct Dense<T> {
+    pub data: Vec<T>,
+    pub lens: Vec<usize>,
+}
+
+impl<T> Dense<T> {
+    pub fn from_grid2<G, F, S>(grid: &G, mut func: F) -> Self
+    where
+        G: Grid2Get<S>,
+        G::XBound: BoundRange<S>,
+        G::YBound: BoundRange<S>,
+        F: FnMut(G::Item) -> T,
+        S: Scalar,
+    {
+        let (x, y) = (grid.x_bound(), grid.y_bound());
+        let (x0, y0) = (x.lower_inclusive(), y.lower_inclusive());
+        let lens = vec![
+            (x.upper_exclusive() - x0).to_usize(),
+            (y.upper_exclusive() - y0).to_usize(),
+        ];
+        let mut data = Vec::with_capacity(lens[0] * lens[1]);
+        for j in 0..lens[1] {
+            for i in 0..lens[0] {
+                data.push(func(grid.get(Vector2 {
+                    x: x0 + S::from_usize(i),
+                    y: y0 + S::from_usize(j),
+                })));
+            }
+        }
+        Dense { data, lens }
+    }
+    
+    pub fn from_grid3<G, F, S>(grid: &G, mut func: F) -> Self
+    where
+        G: Grid3Get<S>,
+        G::XBound: BoundRange<S>,
+        G::YBound: BoundRange<S>,
+        G::ZBound: BoundRange<S>,
+        F: FnMut(G::Item) -> T,
+        S: Scalar,
+    {
+        let (x, y, z) = (grid.x_bound(), grid.y_bound(), grid.z_bound());
+        let (x0, y0, z0) = (x.lower_inclusive(), y.lower_inclusive(), z.lower_inclusive());
+        let lens = vec![
+            (x.upper_exclusive() - x0).to_usize(),
+            (y.upper_exclusive() - y0).to_usize(),
+            (z.upper_exclusive() - z0).to_usize(),
+        ];
+        let mut data = Vec::with_capacity(lens[0] * lens[1] * lens[2]);
+        for k in 0..lens[2] {
+            for j in 0..lens[1] {
+                for i in 0..lens[0] {
+                    data.push(func(grid.get(Vector3 {
+                        x: x0 + S::from_usize(i),
+                        y: y0 + S::from_usize(j),
+                        z: z0 + S::from_usize(k),
+                    })));
+                }
+            }
+        }
+        Dense { data, lens }
+    }
+    
+    /// Buffer of the same lengths, populated from coordinates.
+    pub fn with_lens<F>(lens: &[usize], mut func: F) -> Self
+    where
+        F: FnMut(&[i64]) -> T,
+    {
+        let total: usize = lens.iter().product();
+        let data = (0..total)
+            .map(|index| func(&coord(lens, index)))
+            .collect();
+        Dense { data, lens: lens.to_vec() }
+    }
+    
+    /// Coordinate of an index into the buffer.
+    pub fn coord(&self, index: usize) -> Vec<i64> {
+        coord(&self.lens, index)
+    }
+    
+    pub fn into_grid2<S: Scalar>(self) -> ArrayGrid2<T, S> {
+        let mut data = self.data.into_iter();
+        ArrayGrid2::new(
+            S::from_usize(self.lens[0]),
+            S::from_usize(self.lens[1]),
+            |_: Vector2<S>| data.next().unwrap())
+    }
+    
+    pub fn into_grid3<S: Scalar>(self) -> ArrayGrid3<T, S> {
+        let mut data = self.data.into_iter();
+        ArrayGrid3::new(
+            S::from_usize(self.lens[0]),
+            S::from_usize(self.lens[1]),
+            S::from_usize(self.lens[2]),
+            |_: Vector3<S>| data.next().unwrap())
+    }
+    
+    /// Element at a possibly out-of-bounds coordinate.
+    pub fn get(&self, coord: &[i64], edge: &Edge<T>) -> T
+    where
+        T: Clone,
+    {
+        let mut index = 0;
+        let mut stride = 1;
+        for (&c, &len) in coord.iter().zip(&self.lens) {
+            let len_i = len as i64;
+            let c = if c >= 0 && c < len_i {
+                c
+            } else {
+                match *edge {
+                    Edge::Constant(ref value) => return value.clone(),
+                    Edge::Clamp => c.max(0).min(len_i - 1),
+                    Edge::Wrap => c.rem_euclid(len_i),
+                    Edge::Mirror => {
+                        let c = c.rem_euclid(2 * len_i);
+                        if c < len_i { c } else { 2 * len_i - 1 - c }
+                    }
+                }
+            };
+            index += c as usize * stride;
+            stride *= len;
+        }
+        self.data[index].clone()
+    }
+}
+
+fn coord(lens: &[usize], index: usize) -> Vec<i64> {
+    let mut rem = index;
+    lens.iter()
+        .map(|&len| {
+            let c = rem % len;
+            rem /= len;
+            c as i64
+        })
+        .collect()
+}
+
+/// Lowest corner of a bounded Grid2.
+pub(crate) fn origin2<G, S>(grid: &G) -> Vector2<S>
+where
+    G: Grid2<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    S: Scalar,
+{
+    Vector2 {
+        x: grid.x_bound().lower_inclusive(),
+        y: grid.y_bound().lower_inclusive(),
+    }
+}
+
+/// Lowest corner of a bounded Grid3.
+pub(crate) fn origin3<G, S>(grid: &G) -> Vector3<S>
+where
+    G: Grid3<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    S: Scalar,
+{
+    Vector3 {
+        x: grid.x_bound().lower_inclusive(),
+        y: grid.y_bound().lower_inclusive(),
+        z: grid.z_bound().lower_inclusive(),
+    }
+}
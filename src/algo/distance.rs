@@ -0,0 +1,346 @@
+//! Distance transforms.
+//!
+//! Each transform produces, for every element of a bounded grid,
+//! the distance in elements to the nearest feature element, or
+//! infinity if there are no features. The output grid has the
+//! same bounds as the input.
+
+use crate::{
+    scalar::Scalar,
+    range::BoundRange,
+    grid2::{
+        Grid2,
+        Grid2Get,
+        backends::heap::ArrayGrid2,
+        combinate::neworigin::Grid2NewOrigin,
+    },
+    grid3::{
+        Grid3,
+        Grid3Get,
+        backends::heap::ArrayGrid3,
+        combinate::neworigin::Grid3NewOrigin,
+    },
+};
+use super::dense::{
+    Dense,
+    origin2,
+    origin3,
+};
+
+/// Metric for chamfer distance transforms.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+enum Chamfer {
+    /// Sum of axis distances.
+    Manhattan,
+    /// Max of axis distances.
+    Chebyshev,
+}
+
+/// Feature mask, as 0 at features and infinity elsewhere.
+fn mask<T, F>(is_feature: F) -> impl FnMut(T) -> f32
+where
+    F: Fn(T) -> bool,
+{
+    move |item| if is_feature(item) { 0.0 } else { f32::INFINITY }
+}
+
+/// Exact squared Euclidean distance transform of a sampled function
+/// along one line, by the lower envelope of parabolas.
+///
+/// From Felzenszwalb and Huttenlocher, "Distance Transforms of
+/// Sampled Functions".
+///
+/// Intersections are computed in f64, as the squared coordinates
+/// lose precision in f32 for lines longer than a few thousand.
+fn edt_1d(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f64]) {
+    let n = f.len();
+    
+    // parabolas rooted at infinity never contribute
+    let mut k: usize = 0;
+    let mut any = false;
+    for q in 0..n {
+        if f[q] == f32::INFINITY {
+            continue;
+        }
+        if !any {
+            v[0] = q;
+            z[0] = f64::NEG_INFINITY;
+            z[1] = f64::INFINITY;
+            any = true;
+            continue;
+        }
+        loop {
+            let p = v[k];
+            let s = ((f[q] as f64 + (q * q) as f64) - (f[p] as f64 + (p * p) as f64))
+                / (2 * q - 2 * p) as f64;
+            // z[0] is negative infinity, so k never underflows
+            if s <= z[k] {
+                k -= 1;
+            } else {
+                k += 1;
+                v[k] = q;
+                z[k] = s;
+                z[k + 1] = f64::INFINITY;
+                break;
+            }
+        }
+    }
+    
+    if !any {
+        d.iter_mut().for_each(|x| *x = f32::INFINITY);
+        return;
+    }
+    
+    k = 0;
+    for (q, dq) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let p = v[k];
+        let diff = q as f64 - p as f64;
+        *dq = (diff * diff + f[p] as f64) as f32;
+    }
+}
+
+/// Exact Euclidean distance transform, in place, of an x-fastest
+/// buffer with the given lengths.
+fn edt_nd(buf: &mut [f32], lens: &[usize]) {
+    let total: usize = lens.iter().product();
+    if total == 0 {
+        return;
+    }
+    let max_len = lens.iter().copied().max().unwrap_or(0);
+    let mut f = vec![0.0; max_len];
+    let mut d = vec![0.0; max_len];
+    let mut v = vec![0; max_len];
+    let mut z = vec![0.0f64; max_len + 1];
+    
+    let mut stride = 1;
+    for &len in lens {
+        // every line along this axis starts at an index whose
+        // coordinate along this axis is 0
+        for start in 0..total {
+            if (start / stride) % len != 0 {
+                continue;
+            }
+            for i in 0..len {
+                f[i] = buf[start + i * stride];
+            }
+            edt_1d(&f[..len], &mut d[..len], &mut v[..len], &mut z[..len + 1]);
+            for i in 0..len {
+                buf[start + i * stride] = d[i];
+            }
+        }
+        stride *= len;
+    }
+    
+    for x in buf.iter_mut() {
+        *x = x.sqrt();
+    }
+}
+
+/// Two-pass chamfer distance transform, in place, of an x-fastest
+/// buffer with the given lengths.
+///
+/// With unit costs, this is exact for both metrics.
+fn chamfer_nd(buf: &mut [f32], lens: &[usize], metric: Chamfer) {
+    let dims = lens.len();
+    
+    // neighbour offsets per axis, in {-1, 0, 1}
+    let mut neighbours: Vec<Vec<i64>> = Vec::new();
+    for n in 0..3usize.pow(dims as u32) {
+        let offset: Vec<i64> = (0..dims)
+            .map(|axis| (n / 3usize.pow(axis as u32)) as i64 % 3 - 1)
+            .collect();
+        let nonzero = offset.iter().filter(|&&o| o != 0).count();
+        let include = match metric {
+            Chamfer::Manhattan => nonzero == 1,
+            Chamfer::Chebyshev => nonzero >= 1,
+        };
+        if include {
+            neighbours.push(offset);
+        }
+    }
+    
+    let total: usize = lens.iter().product();
+    let mut coord = vec![0i64; dims];
+    let mut relax = |index: usize, forward: bool, buf: &mut [f32]| {
+        let mut rem = index;
+        for axis in 0..dims {
+            coord[axis] = (rem % lens[axis]) as i64;
+            rem /= lens[axis];
+        }
+        let mut best = buf[index];
+        'neighbours: for offset in &neighbours {
+            // only neighbours already visited in this pass, which
+            // are those whose most significant nonzero offset is
+            // negative for the forward pass
+            let sign = offset.iter().rev().find(|&&o| o != 0).copied().unwrap();
+            if (sign < 0) != forward {
+                continue;
+            }
+            let mut neighbour = 0;
+            let mut stride = 1;
+            for axis in 0..dims {
+                let c = coord[axis] + offset[axis];
+                if c < 0 || c >= lens[axis] as i64 {
+                    continue 'neighbours;
+                }
+                neighbour += c as usize * stride;
+                stride *= lens[axis];
+            }
+            best = best.min(buf[neighbour] + 1.0);
+        }
+        buf[index] = best;
+    };
+    
+    for index in 0..total {
+        relax(index, true, buf);
+    }
+    for index in (0..total).rev() {
+        relax(index, false, buf);
+    }
+}
+
+/// Signed distance from two unsigned transforms: positive outside
+/// features, and negative within them.
+fn signed(outside: Vec<f32>, inside: Vec<f32>) -> Vec<f32> {
+    outside.into_iter()
+        .zip(inside)
+        .map(|(o, i)| o - i)
+        .collect()
+}
+
+/// Exact Euclidean distance transform of a Grid2.
+pub fn edt<G, F, S>(grid: &G, is_feature: F) -> Grid2NewOrigin<ArrayGrid2<f32, S>, S>
+where
+    G: Grid2Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    F: Fn(G::Item) -> bool,
+    S: Scalar,
+{
+    let mut dense = Dense::from_grid2(grid, mask(is_feature));
+    edt_nd(&mut dense.data, &dense.lens);
+    dense.into_grid2().new_origin(origin2(grid))
+}
+
+/// Manhattan (4-neighbour) distance transform of a Grid2.
+pub fn manhattan<G, F, S>(grid: &G, is_feature: F) -> Grid2NewOrigin<ArrayGrid2<f32, S>, S>
+where
+    G: Grid2Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    F: Fn(G::Item) -> bool,
+    S: Scalar,
+{
+    let mut dense = Dense::from_grid2(grid, mask(is_feature));
+    chamfer_nd(&mut dense.data, &dense.lens, Chamfer::Manhattan);
+    dense.into_grid2().new_origin(origin2(grid))
+}
+
+/// Chebyshev (8-neighbour) distance transform of a Grid2.
+pub fn chebyshev<G, F, S>(grid: &G, is_feature: F) -> Grid2NewOrigin<ArrayGrid2<f32, S>, S>
+where
+    G: Grid2Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    F: Fn(G::Item) -> bool,
+    S: Scalar,
+{
+    let mut dense = Dense::from_grid2(grid, mask(is_feature));
+    chamfer_nd(&mut dense.data, &dense.lens, Chamfer::Chebyshev);
+    dense.into_grid2().new_origin(origin2(grid))
+}
+
+/// Signed Euclidean distance transform of a Grid2.
+///
+/// Elements outside features have their distance to the nearest
+/// feature, and elements within features have the negated distance
+/// to the nearest non-feature.
+pub fn signed_edt<G, F, S>(grid: &G, is_feature: F) -> Grid2NewOrigin<ArrayGrid2<f32, S>, S>
+where
+    G: Grid2Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    F: Fn(G::Item) -> bool,
+    S: Scalar,
+{
+    let mut outside = Dense::from_grid2(grid, mask(is_feature));
+    let mut inside: Vec<f32> = outside.data.iter()
+        .map(|&x| if x == 0.0 { f32::INFINITY } else { 0.0 })
+        .collect();
+    edt_nd(&mut outside.data, &outside.lens);
+    edt_nd(&mut inside, &outside.lens);
+    outside.data = signed(outside.data, inside);
+    outside.into_grid2().new_origin(origin2(grid))
+}
+
+/// Exact Euclidean distance transform of a Grid3.
+pub fn edt3<G, F, S>(grid: &G, is_feature: F) -> Grid3NewOrigin<ArrayGrid3<f32, S>, S>
+where
+    G: Grid3Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    F: Fn(G::Item) -> bool,
+    S: Scalar,
+{
+    let mut dense = Dense::from_grid3(grid, mask(is_feature));
+    edt_nd(&mut dense.data, &dense.lens);
+    dense.into_grid3().new_origin(origin3(grid))
+}
+
+/// Manhattan (6-neighbour) distance transform of a Grid3.
+pub fn manhattan3<G, F, S>(grid: &G, is_feature: F) -> Grid3NewOrigin<ArrayGrid3<f32, S>, S>
+where
+    G: Grid3Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    F: Fn(G::Item) -> bool,
+    S: Scalar,
+{
+    let mut dense = Dense::from_grid3(grid, mask(is_feature));
+    chamfer_nd(&mut dense.data, &dense.lens, Chamfer::Manhattan);
+    dense.into_grid3().new_origin(origin3(grid))
+}
+
+/// Chebyshev (26-neighbour) distance transform of a Grid3.
+pub fn chebyshev3<G, F, S>(grid: &G, is_feature: F) -> Grid3NewOrigin<ArrayGrid3<f32, S>, S>
+where
+    G: Grid3Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    F: Fn(G::Item) -> bool,
+    S: Scalar,
+{
+    let mut dense = Dense::from_grid3(grid, mask(is_feature));
+    chamfer_nd(&mut dense.data, &dense.lens, Chamfer::Chebyshev);
+    dense.into_grid3().new_origin(origin3(grid))
+}
+
+/// Signed Euclidean distance transform of a Grid3.
+///
+/// Elements outside features have their distance to the nearest
+/// feature, and elements within features have the negated distance
+/// to the nearest non-feature.
+pub fn signed_edt3<G, F, S>(grid: &G, is_feature: F) -> Grid3NewOrigin<ArrayGrid3<f32, S>, S>
+where
+    G: Grid3Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    F: Fn(G::Item) -> bool,
+    S: Scalar,
+{
+    let mut outside = Dense::from_grid3(grid, mask(is_feature));
+    let mut inside: Vec<f32> = outside.data.iter()
+        .map(|&x| if x == 0.0 { f32::INFINITY } else { 0.0 })
+        .collect();
+    edt_nd(&mut outside.data, &outside.lens);
+    edt_nd(&mut inside, &outside.lens);
+    outside.data = signed(outside.data, inside);
+    outside.into_grid3().new_origin(origin3(grid))
+}
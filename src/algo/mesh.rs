@@ -0,0 +1,558 @@
+//! Mesh extraction from 3D grids.
+//!
+//! Positions are in grid coordinates, so meshing subviews of a
+//! larger grid produces meshes which fit together in the larger
+//! grid's coordinate space.
+//!
+//! All functions treat the outermost layer of elements on every
+//! side as a border: it is read, but the mesh only covers the
+//! surface of the interior. To mesh a chunk spanning `a..b` on
+//! each axis, pass a subview spanning `a - 1..b + 1`, and the meshes
+//! of neighbouring chunks will meet without seams or overlaps.
+//!
+//! `marching_cubes` estimates normals from the elements around each
+//! cube corner, so its border is two layers deep on the high side:
+//! pass a subview spanning `a - 1..b + 2`, and normals along the
+//! seams between chunks will match as well.
+
+use crate::{
+    scalar::Scalar,
+    grid3::Grid3Get,
+    range::BoundRange,
+};
+use mint::{Point3, Vector3};
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+};
+
+/// Indexed triangle mesh.
+///
+/// Triangles are wound counter-clockwise when viewed from outside,
+/// which is the side below the threshold, and normals point outside.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mesh {
+    pub positions: Vec<Point3<f32>>,
+    pub normals: Vec<Vector3<f32>>,
+    pub indices: Vec<u32>,
+}
+
+impl Mesh {
+    pub fn new() -> Self {
+        Mesh::default()
+    }
+    
+    fn push_vertex(&mut self, position: Point3<f32>, normal: Vector3<f32>) -> u32 {
+        self.positions.push(position);
+        self.normals.push(normal);
+        (self.positions.len() - 1) as u32
+    }
+}
+
+/// Scalar field read from a bounded grid in i64 coordinates.
+struct Field<'a, G, S> {
+    grid: &'a G,
+    lo: [i64; 3],
+    hi: [i64; 3],
+    threshold: f32,
+    p: PhantomData<fn(S)>,
+}
+
+impl<'a, G, S> Field<'a, G, S>
+where
+    G: Grid3Get<S>,
+    G::Item: Into<f32>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    S: Scalar,
+{
+    fn new(grid: &'a G, threshold: f32) -> Self {
+        let (lo, hi) = bounds(grid);
+        Field {
+            grid,
+            lo,
+            hi,
+            threshold,
+            p: PhantomData,
+        }
+    }
+    
+    /// Field value relative to the threshold, so that the surface
+    /// is at 0 and the inside is non-negative.
+    fn value(&self, p: [i64; 3]) -> f32 {
+        let v: f32 = get(self.grid, p).into();
+        v - self.threshold
+    }
+    
+    /// Central-difference gradient, clamped at the bounds.
+    fn gradient(&self, p: [i64; 3]) -> [f32; 3] {
+        let mut grad = [0.0; 3];
+        for (axis, g) in grad.iter_mut().enumerate() {
+            let mut lo = p;
+            let mut hi = p;
+            lo[axis] = (p[axis] - 1).max(self.lo[axis]);
+            hi[axis] = (p[axis] + 1).min(self.hi[axis] - 1);
+            if hi[axis] > lo[axis] {
+                *g = (self.value(hi) - self.value(lo)) / (hi[axis] - lo[axis]) as f32;
+            }
+        }
+        grad
+    }
+    
+    /// Interior cells, identified by their lowest corner.
+    fn cells(&self) -> impl Iterator<Item=[i64; 3]> {
+        interior(self.lo, self.hi)
+    }
+}
+
+/// Bounds of a grid as i64 arrays, lower inclusive and upper exclusive.
+fn bounds<G, S>(grid: &G) -> ([i64; 3], [i64; 3])
+where
+    G: Grid3Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    S: Scalar,
+{
+    let (x, y, z) = (grid.x_bound(), grid.y_bound(), grid.z_bound());
+    (
+        [
+            x.lower_inclusive().to_i64(),
+            y.lower_inclusive().to_i64(),
+            z.lower_inclusive().to_i64(),
+        ],
+        [
+            x.upper_exclusive().to_i64(),
+            y.upper_exclusive().to_i64(),
+            z.upper_exclusive().to_i64(),
+        ],
+    )
+}
+
+/// Coordinates within bounds, excluding the outermost layer.
+fn interior(lo: [i64; 3], hi: [i64; 3]) -> impl Iterator<Item=[i64; 3]> {
+    (lo[2] + 1..hi[2] - 1)
+        .flat_map(move |z| (lo[1] + 1..hi[1] - 1)
+            .flat_map(move |y| (lo[0] + 1..hi[0] - 1)
+                .map(move |x| [x, y, z])))
+}
+
+fn get<G, S>(grid: &G, p: [i64; 3]) -> G::Item
+where
+    G: Grid3Get<S>,
+    S: Scalar,
+{
+    grid.get(Vector3 {
+        x: S::from_i64(p[0]),
+        y: S::from_i64(p[1]),
+        z: S::from_i64(p[2]),
+    })
+}
+
+fn offset(p: [i64; 3], axis: usize, n: i64) -> [i64; 3] {
+    let mut p = p;
+    p[axis] += n;
+    p
+}
+
+/// Corner of a cell, where bit n is the offset along axis n.
+fn corner(cell: [i64; 3], i: usize) -> [i64; 3] {
+    [
+        cell[0] + (i & 1) as i64,
+        cell[1] + ((i >> 1) & 1) as i64,
+        cell[2] + ((i >> 2) & 1) as i64,
+    ]
+}
+
+/// Outward unit normal from a field gradient.
+fn normal_from_gradient(grad: [f32; 3]) -> Vector3<f32> {
+    let len = (grad[0] * grad[0] + grad[1] * grad[1] + grad[2] * grad[2]).sqrt();
+    if len > 0.0 {
+        Vector3 { x: -grad[0] / len, y: -grad[1] / len, z: -grad[2] / len }
+    } else {
+        Vector3 { x: 0.0, y: 0.0, z: 0.0 }
+    }
+}
+
+/// Point where the surface crosses the edge from a to b.
+fn crossing(a: [i64; 3], b: [i64; 3], va: f32, vb: f32) -> ([f32; 3], f32) {
+    let t = va / (va - vb);
+    let mut p = [0.0; 3];
+    for axis in 0..3 {
+        p[axis] = a[axis] as f32 + (b[axis] - a[axis]) as f32 * t;
+    }
+    (p, t)
+}
+
+/// Extract an isosurface with marching cubes.
+///
+/// Elements at or above the threshold are inside. Rather than a
+/// case table, the surface polygon of each cube is traced around
+/// its faces, with ambiguous faces resolved by the average of
+/// their four corners. Neighbouring cubes resolve shared faces the
+/// same way, so the surface has no holes.
+///
+/// Normals are interpolated from central differences at the cube
+/// corners, which read one element beyond each corner, so on the
+/// high side of each axis the two outermost layers are border.
+pub fn marching_cubes<G, S>(grid: &G, threshold: f32) -> Mesh
+where
+    G: Grid3Get<S>,
+    G::Item: Into<f32>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    S: Scalar,
+{
+    let field = Field::new(grid, threshold);
+    let mut mesh = Mesh::new();
+    // global edge (lower element, axis) → vertex index
+    let mut vertices: HashMap<([i64; 3], usize), u32> = HashMap::new();
+    
+    // cells whose corners all lie within the interior, so that
+    // gradients at the corners are never clamped
+    for cell in interior(field.lo, field.hi.map(|i| i - 1)) {
+        let values: Vec<f32> = (0..8).map(|i| field.value(corner(cell, i))).collect();
+        let inside: Vec<bool> = values.iter().map(|&v| v >= 0.0).collect();
+        if inside.iter().all(|&b| b) || inside.iter().all(|&b| !b) {
+            continue;
+        }
+        
+        // directed segments across the cube's faces, between
+        // cube edges given as (corner, corner) pairs
+        let mut next: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        for axis in 0..3 {
+            let u = 1 << ((axis + 1) % 3);
+            let v = 1 << ((axis + 2) % 3);
+            for side in 0..2 {
+                let base = side << axis;
+                // counter-clockwise when viewed from outside the cube
+                let mut face = [base, base | u, base | u | v, base | v];
+                if side == 0 {
+                    face.reverse();
+                }
+                
+                let exits: Vec<usize> = (0..4)
+                    .filter(|&k| inside[face[k]] && !inside[face[(k + 1) % 4]])
+                    .collect();
+                let entries: Vec<usize> = (0..4)
+                    .filter(|&k| !inside[face[k]] && inside[face[(k + 1) % 4]])
+                    .collect();
+                let edge = |k: usize| {
+                    let (a, b) = (face[k], face[(k + 1) % 4]);
+                    (a.min(b), a.max(b))
+                };
+                
+                match exits.len() {
+                    0 => (),
+                    1 => { next.insert(edge(exits[0]), edge(entries[0])); },
+                    _ => {
+                        let center: f32 = face.iter().map(|&c| values[c]).sum::<f32>() / 4.0;
+                        let connected = center >= 0.0;
+                        for &k in &exits {
+                            let entry = if connected { (k + 1) % 4 } else { (k + 3) % 4 };
+                            next.insert(edge(k), edge(entry));
+                        }
+                    }
+                }
+            }
+        }
+        
+        // trace each loop into a polygon and fan-triangulate it
+        while let Some(&start) = next.keys().min() {
+            let mut polygon = Vec::new();
+            let mut curr = start;
+            while let Some(to) = next.remove(&curr) {
+                let (a, b) = curr;
+                let (ca, cb) = (corner(cell, a), corner(cell, b));
+                let axis = (a ^ b).trailing_zeros() as usize;
+                let index = *vertices.entry((ca, axis)).or_insert_with(|| {
+                    let (p, t) = crossing(ca, cb, values[a], values[b]);
+                    let (ga, gb) = (field.gradient(ca), field.gradient(cb));
+                    let grad = [
+                        ga[0] + (gb[0] - ga[0]) * t,
+                        ga[1] + (gb[1] - ga[1]) * t,
+                        ga[2] + (gb[2] - ga[2]) * t,
+                    ];
+                    mesh.push_vertex(
+                        Point3 { x: p[0], y: p[1], z: p[2] },
+                        normal_from_gradient(grad))
+                });
+                polygon.push(index);
+                curr = to;
+            }
+            // loops run with the inside on their left when viewed
+            // from outside the cube, which is clockwise when viewed
+            // from outside the surface
+            for i in 1..polygon.len() - 1 {
+                mesh.indices.extend_from_slice(&[polygon[0], polygon[i + 1], polygon[i]]);
+            }
+        }
+    }
+    
+    mesh
+}
+
+/// Extract an isosurface with surface nets.
+///
+/// Elements at or above the threshold are inside. Each cell which
+/// the surface passes through gets one vertex, at the average of
+/// its edge crossings, and each element edge which the surface
+/// crosses gets a quad between its four surrounding cells. This
+/// produces fewer, better-shaped triangles than marching cubes.
+pub fn surface_nets<G, S>(grid: &G, threshold: f32) -> Mesh
+where
+    G: Grid3Get<S>,
+    G::Item: Into<f32>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    S: Scalar,
+{
+    let field = Field::new(grid, threshold);
+    let mut mesh = Mesh::new();
+    let mut vertices: HashMap<[i64; 3], u32> = HashMap::new();
+    
+    // one vertex per surface cell, including cells in the
+    // low border, which interior quads may reach
+    let (lo, hi) = (field.lo, field.hi);
+    for z in lo[2]..hi[2] - 1 {
+        for y in lo[1]..hi[1] - 1 {
+            for x in lo[0]..hi[0] - 1 {
+                let cell = [x, y, z];
+                let values: Vec<f32> = (0..8).map(|i| field.value(corner(cell, i))).collect();
+                
+                let mut sum = [0.0; 3];
+                let mut count = 0;
+                for a in 0..8 {
+                    for axis in 0..3 {
+                        let b = a | (1 << axis);
+                        if b == a || (values[a] >= 0.0) == (values[b] >= 0.0) {
+                            continue;
+                        }
+                        let (p, _) = crossing(corner(cell, a), corner(cell, b), values[a], values[b]);
+                        for n in 0..3 {
+                            sum[n] += p[n];
+                        }
+                        count += 1;
+                    }
+                }
+                if count == 0 {
+                    continue;
+                }
+                let p = sum.map(|s| s / count as f32);
+                
+                // gradient of the trilinear interpolation at p
+                let t = [p[0] - x as f32, p[1] - y as f32, p[2] - z as f32];
+                let mut grad = [0.0; 3];
+                for (i, &v) in values.iter().enumerate() {
+                    for (axis, g) in grad.iter_mut().enumerate() {
+                        let mut w = if (i >> axis) & 1 == 1 { 1.0 } else { -1.0 };
+                        for other in (0..3).filter(|&o| o != axis) {
+                            w *= if (i >> other) & 1 == 1 { t[other] } else { 1.0 - t[other] };
+                        }
+                        *g += w * v;
+                    }
+                }
+                
+                let index = mesh.push_vertex(
+                    Point3 { x: p[0], y: p[1], z: p[2] },
+                    normal_from_gradient(grad));
+                vertices.insert(cell, index);
+            }
+        }
+    }
+    
+    // one quad per crossed edge, for edges starting in the interior
+    for p in field.cells() {
+        let inside = field.value(p) >= 0.0;
+        for axis in 0..3 {
+            let q = offset(p, axis, 1);
+            if inside == (field.value(q) >= 0.0) {
+                continue;
+            }
+            let u = (axis + 1) % 3;
+            let v = (axis + 2) % 3;
+            let cells = [
+                offset(offset(p, u, -1), v, -1),
+                offset(p, v, -1),
+                p,
+                offset(p, u, -1),
+            ];
+            let quad = cells.map(|c| vertices[&c]);
+            // counter-clockwise around the axis, which faces outside
+            // when the inside is at the low end of the edge
+            let quad = if inside { quad } else { [quad[0], quad[3], quad[2], quad[1]] };
+            mesh.indices.extend_from_slice(&[quad[0], quad[1], quad[2], quad[0], quad[2], quad[3]]);
+        }
+    }
+    
+    mesh
+}
+
+/// Axis-aligned face of a block, or a rectangle of merged faces.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quad<T> {
+    /// Counter-clockwise when viewed from outside.
+    pub corners: [Point3<f32>; 4],
+    /// Unit normal, pointing out of the block.
+    pub normal: Vector3<f32>,
+    /// Block which this face belongs to.
+    pub block: T,
+}
+
+/// Quad covering the faces of blocks from `min` to `max` inclusive
+/// (which differ only along the u and v axes), on the side of the
+/// blocks facing along `axis` in the direction `dir`.
+fn quad<T>(min: [i64; 3], max: [i64; 3], axis: usize, dir: i64, block: T) -> Quad<T> {
+    let u = (axis + 1) % 3;
+    let v = (axis + 2) % 3;
+    let point = |du: i64, dv: i64| {
+        let mut p = [0.0; 3];
+        p[axis] = (min[axis] + if dir > 0 { 1 } else { 0 }) as f32;
+        p[u] = (if du > 0 { max[u] + 1 } else { min[u] }) as f32;
+        p[v] = (if dv > 0 { max[v] + 1 } else { min[v] }) as f32;
+        Point3 { x: p[0], y: p[1], z: p[2] }
+    };
+    // u, v, axis is right-handed, so this is counter-clockwise
+    // around the positive axis
+    let mut corners = [point(0, 0), point(1, 0), point(1, 1), point(0, 1)];
+    if dir < 0 {
+        corners.reverse();
+    }
+    let mut normal = [0.0; 3];
+    normal[axis] = dir as f32;
+    Quad {
+        corners,
+        normal: Vector3 { x: normal[0], y: normal[1], z: normal[2] },
+        block,
+    }
+}
+
+/// One quad per visible block face.
+///
+/// Blocks are unit cubes, with the block at `<x, y, z>` spanning
+/// from `<x, y, z>` to `<x + 1, y + 1, z + 1>`. A face is visible if
+/// its block is opaque and the neighbouring block is not.
+pub fn culled_faces<G, F, S>(grid: &G, is_opaque: F) -> Vec<Quad<G::Item>>
+where
+    G: Grid3Get<S>,
+    G::Item: Clone,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    F: Fn(&G::Item) -> bool,
+    S: Scalar,
+{
+    let (lo, hi) = bounds(grid);
+    let mut quads = Vec::new();
+    for p in interior(lo, hi) {
+        let block = get(grid, p);
+        if !is_opaque(&block) {
+            continue;
+        }
+        for axis in 0..3 {
+            for &dir in &[-1, 1] {
+                if !is_opaque(&get(grid, offset(p, axis, dir))) {
+                    quads.push(quad(p, p, axis, dir, block.clone()));
+                }
+            }
+        }
+    }
+    quads
+}
+
+/// Visible block faces, with adjacent coplanar faces merged into
+/// larger rectangles.
+///
+/// Visibility is as in `culled_faces`, and faces only merge if the
+/// merge key of their blocks is equal. Each merged quad carries the
+/// block of its lowest face.
+pub fn greedy_quads<G, F, M, K, S>(grid: &G, is_opaque: F, merge_key: M) -> Vec<Quad<G::Item>>
+where
+    G: Grid3Get<S>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    F: Fn(&G::Item) -> bool,
+    M: Fn(&G::Item) -> K,
+    K: PartialEq,
+    S: Scalar,
+{
+    let (lo, hi) = bounds(grid);
+    let mut quads = Vec::new();
+    
+    for axis in 0..3 {
+        let u = (axis + 1) % 3;
+        let v = (axis + 2) % 3;
+        let u_len = (hi[u] - lo[u] - 2).max(0) as usize;
+        let v_len = (hi[v] - lo[v] - 2).max(0) as usize;
+        
+        for &dir in &[-1, 1] {
+            for layer in lo[axis] + 1..hi[axis] - 1 {
+                let coord = |i: usize, j: usize| {
+                    let mut p = [0; 3];
+                    p[axis] = layer;
+                    p[u] = lo[u] + 1 + i as i64;
+                    p[v] = lo[v] + 1 + j as i64;
+                    p
+                };
+                
+                // visible faces in this layer, row-major over v, u
+                let mut mask: Vec<Option<(K, G::Item)>> = Vec::with_capacity(u_len * v_len);
+                for j in 0..v_len {
+                    for i in 0..u_len {
+                        let p = coord(i, j);
+                        let block = get(grid, p);
+                        let visible = is_opaque(&block)
+                            && !is_opaque(&get(grid, offset(p, axis, dir)));
+                        mask.push(if visible { Some((merge_key(&block), block)) } else { None });
+                    }
+                }
+                
+                for j in 0..v_len {
+                    let mut i = 0;
+                    while i < u_len {
+                        let (key, block) = match mask[j * u_len + i].take() {
+                            Some(face) => face,
+                            None => {
+                                i += 1;
+                                continue;
+                            }
+                        };
+                        let same = |face: &Option<(K, G::Item)>| match face {
+                            Some((k, _)) => *k == key,
+                            None => false,
+                        };
+                        
+                        let mut width = 1;
+                        while i + width < u_len && same(&mask[j * u_len + i + width]) {
+                            width += 1;
+                        }
+                        let mut height = 1;
+                        while j + height < v_len
+                            && (i..i + width).all(|i2| same(&mask[(j + height) * u_len + i2])) {
+                            height += 1;
+                        }
+                        for j2 in j..j + height {
+                            for i2 in i..i + width {
+                                mask[j2 * u_len + i2] = None;
+                            }
+                        }
+                        
+                        quads.push(quad(
+                            coord(i, j),
+                            coord(i + width - 1, j + height - 1),
+                            axis,
+                            dir,
+                            block));
+                        i += width;
+                    }
+                }
+            }
+        }
+    }
+    
+    quads
+}
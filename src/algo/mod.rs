@@ -0,0 +1,35 @@
+//! Algorithms over grids.
+
+/// Iso-line extraction.
+pub mod contour;
+
+/// Mesh extraction.
+pub mod mesh;
+
+/// Distance transforms.
+pub mod distance;
+
+/// Morphological operations.
+pub mod morph;
+
+/// Convolution.
+pub mod convolve;
+
+/// Copying between grids.
+pub mod blit;
+
+mod dense;
+
+/// Policy for reading beyond the bounds of a grid.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Edge<T> {
+    /// Out-of-bounds elements have this value.
+    Constant(T),
+    /// Out-of-bounds coordinates are clamped to the nearest element.
+    Clamp,
+    /// Out-of-bounds coordinates wrap around to the other side.
+    Wrap,
+    /// Out-of-bounds coordinates are reflected back, repeating
+    /// the edge element.
+    Mirror,
+}
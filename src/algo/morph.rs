@@ -0,0 +1,201 @@
+//! Morphological operations on boolean grids.
+//!
+//! The structuring element is a small boolean grid, centered on its
+//! middle element (rounding up for even lengths), so a 3x3 element
+//! covers offsets from -1 to 1. The output grid has the same
+//! bounds as the input.
+
+use crate::{
+    scalar::Scalar,
+    range::BoundRange,
+    grid2::{
+        Grid2,
+        Grid2Get,
+        backends::heap::ArrayGrid2,
+        combinate::neworigin::Grid2NewOrigin,
+    },
+    grid3::{
+        Grid3,
+        Grid3Get,
+        backends::heap::ArrayGrid3,
+        combinate::neworigin::Grid3NewOrigin,
+    },
+};
+use super::{
+    Edge,
+    dense::{
+        Dense,
+        origin2,
+        origin3,
+    },
+};
+
+/// Offsets of the set elements of a structuring element.
+fn offsets(element: &Dense<bool>) -> Vec<Vec<i64>> {
+    (0..element.data.len())
+        .filter(|&index| element.data[index])
+        .map(|index| element.coord(index).iter()
+            .zip(&element.lens)
+            .map(|(&c, &len)| c - (len / 2) as i64)
+            .collect())
+        .collect()
+}
+
+/// Dilation if `dilate`, otherwise erosion.
+fn apply(input: &Dense<bool>, offsets: &[Vec<i64>], edge: &Edge<bool>, dilate: bool) -> Dense<bool> {
+    let mut neighbour = vec![0; input.lens.len()];
+    Dense::with_lens(&input.lens, |coord| {
+        let mut hits = offsets.iter().map(|offset| {
+            for (n, (&c, &o)) in neighbour.iter_mut().zip(coord.iter().zip(offset)) {
+                // dilation reflects the element, erosion does not
+                *n = if dilate { c - o } else { c + o };
+            }
+            input.get(&neighbour, edge)
+        });
+        if dilate {
+            hits.any(|b| b)
+        } else {
+            hits.all(|b| b)
+        }
+    })
+}
+
+/// Set every element within reach of a set element.
+pub fn dilate<G, E, S>(grid: &G, element: &E, edge: Edge<bool>) -> Grid2NewOrigin<ArrayGrid2<bool, S>, S>
+where
+    G: Grid2Get<S, Item=bool>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    E: Grid2Get<S, Item=bool>,
+    E::XBound: BoundRange<S>,
+    E::YBound: BoundRange<S>,
+    S: Scalar,
+{
+    let input = Dense::from_grid2(grid, |b| b);
+    let offsets = offsets(&Dense::from_grid2(element, |b| b));
+    apply(&input, &offsets, &edge, true).into_grid2().new_origin(origin2(grid))
+}
+
+/// Keep only elements whose whole reach is set.
+pub fn erode<G, E, S>(grid: &G, element: &E, edge: Edge<bool>) -> Grid2NewOrigin<ArrayGrid2<bool, S>, S>
+where
+    G: Grid2Get<S, Item=bool>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    E: Grid2Get<S, Item=bool>,
+    E::XBound: BoundRange<S>,
+    E::YBound: BoundRange<S>,
+    S: Scalar,
+{
+    let input = Dense::from_grid2(grid, |b| b);
+    let offsets = offsets(&Dense::from_grid2(element, |b| b));
+    apply(&input, &offsets, &edge, false).into_grid2().new_origin(origin2(grid))
+}
+
+/// Erosion followed by dilation, which removes small features.
+pub fn open<G, E, S>(grid: &G, element: &E, edge: Edge<bool>) -> Grid2NewOrigin<ArrayGrid2<bool, S>, S>
+where
+    G: Grid2Get<S, Item=bool>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    E: Grid2Get<S, Item=bool>,
+    E::XBound: BoundRange<S>,
+    E::YBound: BoundRange<S>,
+    S: Scalar,
+{
+    let input = Dense::from_grid2(grid, |b| b);
+    let offsets = offsets(&Dense::from_grid2(element, |b| b));
+    let eroded = apply(&input, &offsets, &edge, false);
+    apply(&eroded, &offsets, &edge, true).into_grid2().new_origin(origin2(grid))
+}
+
+/// Dilation followed by erosion, which fills small holes.
+pub fn close<G, E, S>(grid: &G, element: &E, edge: Edge<bool>) -> Grid2NewOrigin<ArrayGrid2<bool, S>, S>
+where
+    G: Grid2Get<S, Item=bool>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    E: Grid2Get<S, Item=bool>,
+    E::XBound: BoundRange<S>,
+    E::YBound: BoundRange<S>,
+    S: Scalar,
+{
+    let input = Dense::from_grid2(grid, |b| b);
+    let offsets = offsets(&Dense::from_grid2(element, |b| b));
+    let dilated = apply(&input, &offsets, &edge, true);
+    apply(&dilated, &offsets, &edge, false).into_grid2().new_origin(origin2(grid))
+}
+
+/// Set every element within reach of a set element.
+pub fn dilate3<G, E, S>(grid: &G, element: &E, edge: Edge<bool>) -> Grid3NewOrigin<ArrayGrid3<bool, S>, S>
+where
+    G: Grid3Get<S, Item=bool>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    E: Grid3Get<S, Item=bool>,
+    E::XBound: BoundRange<S>,
+    E::YBound: BoundRange<S>,
+    E::ZBound: BoundRange<S>,
+    S: Scalar,
+{
+    let input = Dense::from_grid3(grid, |b| b);
+    let offsets = offsets(&Dense::from_grid3(element, |b| b));
+    apply(&input, &offsets, &edge, true).into_grid3().new_origin(origin3(grid))
+}
+
+/// Keep only elements whose whole reach is set.
+pub fn erode3<G, E, S>(grid: &G, element: &E, edge: Edge<bool>) -> Grid3NewOrigin<ArrayGrid3<bool, S>, S>
+where
+    G: Grid3Get<S, Item=bool>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    E: Grid3Get<S, Item=bool>,
+    E::XBound: BoundRange<S>,
+    E::YBound: BoundRange<S>,
+    E::ZBound: BoundRange<S>,
+    S: Scalar,
+{
+    let input = Dense::from_grid3(grid, |b| b);
+    let offsets = offsets(&Dense::from_grid3(element, |b| b));
+    apply(&input, &offsets, &edge, false).into_grid3().new_origin(origin3(grid))
+}
+
+/// Erosion followed by dilation, which removes small features.
+pub fn open3<G, E, S>(grid: &G, element: &E, edge: Edge<bool>) -> Grid3NewOrigin<ArrayGrid3<bool, S>, S>
+where
+    G: Grid3Get<S, Item=bool>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    E: Grid3Get<S, Item=bool>,
+    E::XBound: BoundRange<S>,
+    E::YBound: BoundRange<S>,
+    E::ZBound: BoundRange<S>,
+    S: Scalar,
+{
+    let input = Dense::from_grid3(grid, |b| b);
+    let offsets = offsets(&Dense::from_grid3(element, |b| b));
+    let eroded = apply(&input, &offsets, &edge, false);
+    apply(&eroded, &offsets, &edge, true).into_grid3().new_origin(origin3(grid))
+}
+
+/// Dilation followed by erosion, which fills small holes.
+pub fn close3<G, E, S>(grid: &G, element: &E, edge: Edge<bool>) -> Grid3NewOrigin<ArrayGrid3<bool, S>, S>
+where
+    G: Grid3Get<S, Item=bool>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    E: Grid3Get<S, Item=bool>,
+    E::XBound: BoundRange<S>,
+    E::YBound: BoundRange<S>,
+    E::ZBound: BoundRange<S>,
+    S: Scalar,
+{
+    let input = Dense::from_grid3(grid, |b| b);
+    let offsets = offsets(&Dense::from_grid3(element, |b| b));
+    let dilated = apply(&input, &offsets, &edge, true);
+    apply(&dilated, &offsets, &edge, false).into_grid3().new_origin(origin3(grid))
+}
@@ -0,0 +1,687 @@
+//! Thread-safe grids.
+//!
+//! A sharded grid is divided into square or cubic chunks, each
+//! behind its own `RwLock`, so threads can access disjoint areas
+//! concurrently through a shared reference.
+//!
+//! Locks are always acquired in ascending order of chunk index, so
+//! acquiring a single region, or several regions at once through
+//! `read_regions` or `write_regions`, cannot deadlock with other
+//! threads doing the same. Acquiring a region while already holding
+//! another one does not have this guarantee.
+
+use crate::{
+    scalar::Scalar,
+    range::Range0To,
+    grid2::{
+        Grid2, Grid2Len, Grid2Get, Grid2Set, Grid2Ref, Grid2Mut,
+        backends::heap::ArrayGrid2,
+    },
+    grid3::{
+        Grid3, Grid3Len, Grid3Get, Grid3Set, Grid3Ref, Grid3Mut,
+        backends::heap::ArrayGrid3,
+    },
+};
+use mint::{Vector2, Vector3};
+use std::{
+    ops::{Range, Deref, DerefMut},
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+/// Number of chunks needed to cover a length.
+fn chunk_count<S: Scalar>(len: S, chunk_len: S) -> usize {
+    ((len + chunk_len - S::ONE) / chunk_len).to_usize()
+}
+
+/// Chunk indices along one axis which overlap a range.
+fn chunk_range<S: Scalar>(range: &Range<S>, chunk_len: S) -> Range<usize> {
+    if range.start >= range.end {
+        0..0
+    } else {
+        (range.start / chunk_len).to_usize()..chunk_count(range.end, chunk_len)
+    }
+}
+
+/// Lock the chunks of several regions in ascending order of chunk
+/// index, returning each region's guards in its own order.
+///
+/// Panics if regions share a chunk.
+fn lock_regions<'a, L, G>(locks: &'a [L], regions: &[Vec<usize>], lock: impl Fn(&'a L) -> G) -> Vec<Vec<G>> {
+    let mut order = regions.iter()
+        .enumerate()
+        .flat_map(|(r, chunks)| chunks.iter().map(move |&chunk| (chunk, r)))
+        .collect::<Vec<(usize, usize)>>();
+    order.sort_unstable();
+    for pair in order.windows(2) {
+        if pair[0].0 == pair[1].0 {
+            panic!("regions {} and {} share chunk {}", pair[0].1, pair[1].1, pair[0].0);
+        }
+    }
+    
+    let mut guards = regions.iter()
+        .map(|chunks| Vec::with_capacity(chunks.len()))
+        .collect::<Vec<Vec<G>>>();
+    for (chunk, r) in order {
+        guards[r].push(lock(&locks[chunk]));
+    }
+    guards
+}
+
+/// Grid2 divided into individually locked chunks.
+pub struct ShardedGrid2<T, S = i32> {
+    chunks: Vec<RwLock<ArrayGrid2<T, S>>>,
+    x_len: S,
+    y_len: S,
+    chunk_len: S,
+    /// Number of chunks along the x axis.
+    x_chunks: usize,
+}
+
+impl<T, S: Scalar> ShardedGrid2<T, S> {
+    /// The generator is called chunk by chunk.
+    pub fn new<I, F>(x_len: S, y_len: S, chunk_len: S, mut startval: F) -> Self
+    where
+        I: From<Vector2<S>>,
+        F: FnMut(I) -> T,
+    {
+        assert!(x_len >= S::ZERO);
+        assert!(y_len >= S::ZERO);
+        assert!(chunk_len > S::ZERO, "chunk length must be positive");
+        
+        let x_chunks = chunk_count(x_len, chunk_len);
+        let y_chunks = chunk_count(y_len, chunk_len);
+        let mut chunks = Vec::with_capacity(x_chunks * y_chunks);
+        for cy in 0..y_chunks {
+            for cx in 0..x_chunks {
+                let origin = Vector2 {
+                    x: S::from_usize(cx) * chunk_len,
+                    y: S::from_usize(cy) * chunk_len,
+                };
+                let chunk = ArrayGrid2::new(
+                    chunk_len.min(x_len - origin.x),
+                    chunk_len.min(y_len - origin.y),
+                    |local: Vector2<S>| startval(I::from(Vector2 {
+                        x: origin.x + local.x,
+                        y: origin.y + local.y,
+                    })));
+                chunks.push(RwLock::new(chunk));
+            }
+        }
+        ShardedGrid2 {
+            chunks,
+            x_len,
+            y_len,
+            chunk_len,
+            x_chunks,
+        }
+    }
+    
+    pub fn broadcast(x_len: S, y_len: S, chunk_len: S, startval: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(x_len, y_len, chunk_len, |_: Vector2<S>| startval.clone())
+    }
+    
+    pub fn chunk_len(&self) -> S {
+        self.chunk_len
+    }
+    
+    /// Chunk index and coordinate within the chunk.
+    fn locate(&self, coord: Vector2<S>) -> (usize, Vector2<S>) {
+        let Vector2 { x, y } = coord;
+        if x < S::ZERO || x >= self.x_len || y < S::ZERO || y >= self.y_len {
+            panic!("invalid index {:?}", coord);
+        }
+        let cx = x / self.chunk_len;
+        let cy = y / self.chunk_len;
+        let local = Vector2 {
+            x: x - cx * self.chunk_len,
+            y: y - cy * self.chunk_len,
+        };
+        (cy.to_usize() * self.x_chunks + cx.to_usize(), local)
+    }
+    
+    /// Read an element, locking its chunk for reading.
+    pub fn get<I>(&self, coord: I) -> T
+    where
+        I: Into<Vector2<S>>,
+        T: Clone,
+    {
+        let (chunk, local) = self.locate(coord.into());
+        self.chunks[chunk].read().unwrap().idx(local).clone()
+    }
+    
+    /// Write an element, locking its chunk for writing.
+    pub fn set<I>(&self, coord: I, elem: T)
+    where
+        I: Into<Vector2<S>>,
+    {
+        let (chunk, local) = self.locate(coord.into());
+        self.chunks[chunk].write().unwrap().set(local, elem);
+    }
+    
+    /// Chunk indices overlapping a region, in ascending order.
+    fn region_chunks(&self, x: &Range<S>, y: &Range<S>) -> (Vec<usize>, Vector2<usize>, usize) {
+        if x.start < S::ZERO || x.end > self.x_len || y.start < S::ZERO || y.end > self.y_len {
+            panic!("region {:?} × {:?} out of bounds", x, y);
+        }
+        let x_range = chunk_range(x, self.chunk_len);
+        let y_range = chunk_range(y, self.chunk_len);
+        let mut chunks = Vec::new();
+        for cy in y_range.clone() {
+            for cx in x_range.clone() {
+                chunks.push(cy * self.x_chunks + cx);
+            }
+        }
+        (chunks, Vector2 { x: x_range.start, y: y_range.start }, x_range.len())
+    }
+    
+    fn regions<'a, G>(&'a self, regions: &[(Range<S>, Range<S>)], lock: impl Fn(&'a RwLock<ArrayGrid2<T, S>>) -> G) -> Vec<Region2<G, S>> {
+        let located = regions.iter()
+            .map(|(x, y)| self.region_chunks(x, y))
+            .collect::<Vec<_>>();
+        let chunks = located.iter()
+            .map(|(chunks, _, _)| chunks.clone())
+            .collect::<Vec<_>>();
+        lock_regions(&self.chunks, &chunks, lock)
+            .into_iter()
+            .zip(located)
+            .zip(regions)
+            .map(|((guards, (_, first_chunk, x_chunks)), (x, y))| Region2 {
+                guards,
+                x: x.clone(),
+                y: y.clone(),
+                chunk_len: self.chunk_len,
+                first_chunk,
+                x_chunks,
+            })
+            .collect()
+    }
+    
+    /// Lock a rectangle for reading.
+    pub fn read_region(&self, x: Range<S>, y: Range<S>) -> ReadRegion2<'_, T, S> {
+        self.read_regions(&[(x, y)]).pop().unwrap()
+    }
+    
+    /// Lock a rectangle for writing.
+    pub fn write_region(&self, x: Range<S>, y: Range<S>) -> WriteRegion2<'_, T, S> {
+        self.write_regions(&[(x, y)]).pop().unwrap()
+    }
+    
+    /// Lock several rectangles for reading at once.
+    ///
+    /// Panics if rectangles share a chunk.
+    pub fn read_regions(&self, regions: &[(Range<S>, Range<S>)]) -> Vec<ReadRegion2<'_, T, S>> {
+        self.regions(regions, |lock| lock.read().unwrap())
+    }
+    
+    /// Lock several rectangles for writing at once.
+    ///
+    /// Panics if rectangles share a chunk.
+    pub fn write_regions(&self, regions: &[(Range<S>, Range<S>)]) -> Vec<WriteRegion2<'_, T, S>> {
+        self.regions(regions, |lock| lock.write().unwrap())
+    }
+}
+
+impl<T, S: Scalar> Grid2<S> for ShardedGrid2<T, S> {
+    type Item = T;
+    type XBound = Range0To<S>;
+    type YBound = Range0To<S>;
+    
+    fn x_bound(&self) -> Range0To<S> {
+        Range0To { end: self.x_len }
+    }
+    
+    fn y_bound(&self) -> Range0To<S> {
+        Range0To { end: self.y_len }
+    }
+}
+
+impl<T, S: Scalar> Grid2Len<S> for ShardedGrid2<T, S> {}
+
+impl<T: Clone, S: Scalar> Grid2Get<S> for ShardedGrid2<T, S> {
+    fn get<I: Into<Vector2<S>>>(&self, coord: I) -> Self::Item 
+    { ShardedGrid2::get(self, coord) }
+}
+
+impl<T, S: Scalar> Grid2Set<S> for ShardedGrid2<T, S> {
+    fn set<I: Into<Vector2<S>>>(&mut self, coord: I, elem: Self::Item) {
+        let (chunk, local) = self.locate(coord.into());
+        self.chunks[chunk].get_mut().unwrap().set(local, elem);
+    }
+}
+
+/// Locked rectangle of a `ShardedGrid2`.
+///
+/// Coordinates are the same as in the sharded grid.
+pub struct Region2<G, S = i32> {
+    /// Guards of overlapping chunks, in row-major order.
+    guards: Vec<G>,
+    x: Range<S>,
+    y: Range<S>,
+    chunk_len: S,
+    /// Chunk coordinate of the first guard.
+    first_chunk: Vector2<usize>,
+    /// Number of overlapping chunks along the x axis.
+    x_chunks: usize,
+}
+
+/// Rectangle of a `ShardedGrid2` locked for reading.
+pub type ReadRegion2<'a, T, S = i32> = Region2<RwLockReadGuard<'a, ArrayGrid2<T, S>>, S>;
+
+/// Rectangle of a `ShardedGrid2` locked for writing.
+pub type WriteRegion2<'a, T, S = i32> = Region2<RwLockWriteGuard<'a, ArrayGrid2<T, S>>, S>;
+
+impl<G, S: Scalar> Region2<G, S> {
+    /// Guard index and coordinate within the chunk.
+    fn locate(&self, coord: Vector2<S>) -> (usize, Vector2<S>) {
+        let Vector2 { x, y } = coord;
+        if !self.x.contains(&x) || !self.y.contains(&y) {
+            panic!("invalid index {:?}", coord);
+        }
+        let cx = x / self.chunk_len;
+        let cy = y / self.chunk_len;
+        let local = Vector2 {
+            x: x - cx * self.chunk_len,
+            y: y - cy * self.chunk_len,
+        };
+        let guard = (cy.to_usize() - self.first_chunk.y) * self.x_chunks + cx.to_usize() - self.first_chunk.x;
+        (guard, local)
+    }
+}
+
+impl<G, T, S> Grid2<S> for Region2<G, S>
+where
+    G: Deref<Target=ArrayGrid2<T, S>>,
+    S: Scalar,
+{
+    type Item = T;
+    type XBound = Range<S>;
+    type YBound = Range<S>;
+    
+    fn x_bound(&self) -> Range<S> {
+        self.x.clone()
+    }
+    
+    fn y_bound(&self) -> Range<S> {
+        self.y.clone()
+    }
+}
+
+impl<G, T, S> Grid2Ref<S> for Region2<G, S>
+where
+    G: Deref<Target=ArrayGrid2<T, S>>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let (guard, local) = self.locate(coord.into());
+        self.guards[guard].idx(local)
+    }
+}
+
+impl<G, T, S> Grid2Mut<S> for Region2<G, S>
+where
+    G: DerefMut<Target=ArrayGrid2<T, S>>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let (guard, local) = self.locate(coord.into());
+        self.guards[guard].midx(local)
+    }
+}
+
+impl<G, T, S> Grid2Get<S> for Region2<G, S>
+where
+    G: Deref<Target=ArrayGrid2<T, S>>,
+    T: Clone,
+    S: Scalar,
+{
+    fn get<I: Into<Vector2<S>>>(&self, coord: I) -> Self::Item 
+    { self.idx(coord).clone() }
+}
+
+impl<G, T, S> Grid2Set<S> for Region2<G, S>
+where
+    G: DerefMut<Target=ArrayGrid2<T, S>>,
+    S: Scalar,
+{
+    fn set<I: Into<Vector2<S>>>(&mut self, coord: I, elem: Self::Item) 
+    { *self.midx(coord) = elem; }
+}
+
+/// Grid3 divided into individually locked chunks.
+pub struct ShardedGrid3<T, S = i32> {
+    chunks: Vec<RwLock<ArrayGrid3<T, S>>>,
+    x_len: S,
+    y_len: S,
+    z_len: S,
+    chunk_len: S,
+    /// Number of chunks along the x and y axes.
+    x_chunks: usize,
+    y_chunks: usize,
+}
+
+impl<T, S: Scalar> ShardedGrid3<T, S> {
+    /// The generator is called chunk by chunk.
+    pub fn new<I, F>(x_len: S, y_len: S, z_len: S, chunk_len: S, mut startval: F) -> Self
+    where
+        I: From<Vector3<S>>,
+        F: FnMut(I) -> T,
+    {
+        assert!(x_len >= S::ZERO);
+        assert!(y_len >= S::ZERO);
+        assert!(z_len >= S::ZERO);
+        assert!(chunk_len > S::ZERO, "chunk length must be positive");
+        
+        let x_chunks = chunk_count(x_len, chunk_len);
+        let y_chunks = chunk_count(y_len, chunk_len);
+        let z_chunks = chunk_count(z_len, chunk_len);
+        let mut chunks = Vec::with_capacity(x_chunks * y_chunks * z_chunks);
+        for cz in 0..z_chunks {
+            for cy in 0..y_chunks {
+                for cx in 0..x_chunks {
+                    let origin = Vector3 {
+                        x: S::from_usize(cx) * chunk_len,
+                        y: S::from_usize(cy) * chunk_len,
+                        z: S::from_usize(cz) * chunk_len,
+                    };
+                    let chunk = ArrayGrid3::new(
+                        chunk_len.min(x_len - origin.x),
+                        chunk_len.min(y_len - origin.y),
+                        chunk_len.min(z_len - origin.z),
+                        |local: Vector3<S>| startval(I::from(Vector3 {
+                            x: origin.x + local.x,
+                            y: origin.y + local.y,
+                            z: origin.z + local.z,
+                        })));
+                    chunks.push(RwLock::new(chunk));
+                }
+            }
+        }
+        ShardedGrid3 {
+            chunks,
+            x_len,
+            y_len,
+            z_len,
+            chunk_len,
+            x_chunks,
+            y_chunks,
+        }
+    }
+    
+    pub fn broadcast(x_len: S, y_len: S, z_len: S, chunk_len: S, startval: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(x_len, y_len, z_len, chunk_len, |_: Vector3<S>| startval.clone())
+    }
+    
+    pub fn chunk_len(&self) -> S {
+        self.chunk_len
+    }
+    
+    /// Chunk index and coordinate within the chunk.
+    fn locate(&self, coord: Vector3<S>) -> (usize, Vector3<S>) {
+        let Vector3 { x, y, z } = coord;
+        if x < S::ZERO || x >= self.x_len || y < S::ZERO || y >= self.y_len || z < S::ZERO || z >= self.z_len {
+            panic!("invalid index {:?}", coord);
+        }
+        let cx = x / self.chunk_len;
+        let cy = y / self.chunk_len;
+        let cz = z / self.chunk_len;
+        let local = Vector3 {
+            x: x - cx * self.chunk_len,
+            y: y - cy * self.chunk_len,
+            z: z - cz * self.chunk_len,
+        };
+        let chunk = (cz.to_usize() * self.y_chunks + cy.to_usize()) * self.x_chunks + cx.to_usize();
+        (chunk, local)
+    }
+    
+    /// Read an element, locking its chunk for reading.
+    pub fn get<I>(&self, coord: I) -> T
+    where
+        I: Into<Vector3<S>>,
+        T: Clone,
+    {
+        let (chunk, local) = self.locate(coord.into());
+        self.chunks[chunk].read().unwrap().idx(local).clone()
+    }
+    
+    /// Write an element, locking its chunk for writing.
+    pub fn set<I>(&self, coord: I, elem: T)
+    where
+        I: Into<Vector3<S>>,
+    {
+        let (chunk, local) = self.locate(coord.into());
+        self.chunks[chunk].write().unwrap().set(local, elem);
+    }
+    
+    /// Chunk indices overlapping a region, in ascending order.
+    fn region_chunks(&self, x: &Range<S>, y: &Range<S>, z: &Range<S>) -> (Vec<usize>, Vector3<usize>, Vector2<usize>) {
+        if x.start < S::ZERO || x.end > self.x_len
+            || y.start < S::ZERO || y.end > self.y_len
+            || z.start < S::ZERO || z.end > self.z_len
+        {
+            panic!("region {:?} × {:?} × {:?} out of bounds", x, y, z);
+        }
+        let x_range = chunk_range(x, self.chunk_len);
+        let y_range = chunk_range(y, self.chunk_len);
+        let z_range = chunk_range(z, self.chunk_len);
+        let mut chunks = Vec::new();
+        for cz in z_range.clone() {
+            for cy in y_range.clone() {
+                for cx in x_range.clone() {
+                    chunks.push((cz * self.y_chunks + cy) * self.x_chunks + cx);
+                }
+            }
+        }
+        let first_chunk = Vector3 {
+            x: x_range.start,
+            y: y_range.start,
+            z: z_range.start,
+        };
+        (chunks, first_chunk, Vector2 { x: x_range.len(), y: y_range.len() })
+    }
+    
+    fn regions<'a, G>(&'a self, regions: &[(Range<S>, Range<S>, Range<S>)], lock: impl Fn(&'a RwLock<ArrayGrid3<T, S>>) -> G) -> Vec<Region3<G, S>> {
+        let located = regions.iter()
+            .map(|(x, y, z)| self.region_chunks(x, y, z))
+            .collect::<Vec<_>>();
+        let chunks = located.iter()
+            .map(|(chunks, _, _)| chunks.clone())
+            .collect::<Vec<_>>();
+        lock_regions(&self.chunks, &chunks, lock)
+            .into_iter()
+            .zip(located)
+            .zip(regions)
+            .map(|((guards, (_, first_chunk, chunk_lens)), (x, y, z))| Region3 {
+                guards,
+                x: x.clone(),
+                y: y.clone(),
+                z: z.clone(),
+                chunk_len: self.chunk_len,
+                first_chunk,
+                chunk_lens,
+            })
+            .collect()
+    }
+    
+    /// Lock a box for reading.
+    pub fn read_region(&self, x: Range<S>, y: Range<S>, z: Range<S>) -> ReadRegion3<'_, T, S> {
+        self.read_regions(&[(x, y, z)]).pop().unwrap()
+    }
+    
+    /// Lock a box for writing.
+    pub fn write_region(&self, x: Range<S>, y: Range<S>, z: Range<S>) -> WriteRegion3<'_, T, S> {
+        self.write_regions(&[(x, y, z)]).pop().unwrap()
+    }
+    
+    /// Lock several boxes for reading at once.
+    ///
+    /// Panics if boxes share a chunk.
+    pub fn read_regions(&self, regions: &[(Range<S>, Range<S>, Range<S>)]) -> Vec<ReadRegion3<'_, T, S>> {
+        self.regions(regions, |lock| lock.read().unwrap())
+    }
+    
+    /// Lock several boxes for writing at once.
+    ///
+    /// Panics if boxes share a chunk.
+    pub fn write_regions(&self, regions: &[(Range<S>, Range<S>, Range<S>)]) -> Vec<WriteRegion3<'_, T, S>> {
+        self.regions(regions, |lock| lock.write().unwrap())
+    }
+}
+
+impl<T, S: Scalar> Grid3<S> for ShardedGrid3<T, S> {
+    type Item = T;
+    type XBound = Range0To<S>;
+    type YBound = Range0To<S>;
+    type ZBound = Range0To<S>;
+    
+    fn x_bound(&self) -> Range0To<S> {
+        Range0To { end: self.x_len }
+    }
+    
+    fn y_bound(&self) -> Range0To<S> {
+        Range0To { end: self.y_len }
+    }
+    
+    fn z_bound(&self) -> Range0To<S> {
+        Range0To { end: self.z_len }
+    }
+}
+
+impl<T, S: Scalar> Grid3Len<S> for ShardedGrid3<T, S> {}
+
+impl<T: Clone, S: Scalar> Grid3Get<S> for ShardedGrid3<T, S> {
+    fn get<I: Into<Vector3<S>>>(&self, coord: I) -> Self::Item 
+    { ShardedGrid3::get(self, coord) }
+}
+
+impl<T, S: Scalar> Grid3Set<S> for ShardedGrid3<T, S> {
+    fn set<I: Into<Vector3<S>>>(&mut self, coord: I, elem: Self::Item) {
+        let (chunk, local) = self.locate(coord.into());
+        self.chunks[chunk].get_mut().unwrap().set(local, elem);
+    }
+}
+
+/// Locked box of a `ShardedGrid3`.
+///
+/// Coordinates are the same as in the sharded grid.
+pub struct Region3<G, S = i32> {
+    /// Guards of overlapping chunks, in x-major order.
+    guards: Vec<G>,
+    x: Range<S>,
+    y: Range<S>,
+    z: Range<S>,
+    chunk_len: S,
+    /// Chunk coordinate of the first guard.
+    first_chunk: Vector3<usize>,
+    /// Number of overlapping chunks along the x and y axes.
+    chunk_lens: Vector2<usize>,
+}
+
+/// Box of a `ShardedGrid3` locked for reading.
+pub type ReadRegion3<'a, T, S = i32> = Region3<RwLockReadGuard<'a, ArrayGrid3<T, S>>, S>;
+
+/// Box of a `ShardedGrid3` locked for writing.
+pub type WriteRegion3<'a, T, S = i32> = Region3<RwLockWriteGuard<'a, ArrayGrid3<T, S>>, S>;
+
+impl<G, S: Scalar> Region3<G, S> {
+    /// Guard index and coordinate within the chunk.
+    fn locate(&self, coord: Vector3<S>) -> (usize, Vector3<S>) {
+        let Vector3 { x, y, z } = coord;
+        if !self.x.contains(&x) || !self.y.contains(&y) || !self.z.contains(&z) {
+            panic!("invalid index {:?}", coord);
+        }
+        let cx = x / self.chunk_len;
+        let cy = y / self.chunk_len;
+        let cz = z / self.chunk_len;
+        let local = Vector3 {
+            x: x - cx * self.chunk_len,
+            y: y - cy * self.chunk_len,
+            z: z - cz * self.chunk_len,
+        };
+        let gx = cx.to_usize() - self.first_chunk.x;
+        let gy = cy.to_usize() - self.first_chunk.y;
+        let gz = cz.to_usize() - self.first_chunk.z;
+        ((gz * self.chunk_lens.y + gy) * self.chunk_lens.x + gx, local)
+    }
+}
+
+impl<G, T, S> Grid3<S> for Region3<G, S>
+where
+    G: Deref<Target=ArrayGrid3<T, S>>,
+    S: Scalar,
+{
+    type Item = T;
+    type XBound = Range<S>;
+    type YBound = Range<S>;
+    type ZBound = Range<S>;
+    
+    fn x_bound(&self) -> Range<S> {
+        self.x.clone()
+    }
+    
+    fn y_bound(&self) -> Range<S> {
+        self.y.clone()
+    }
+    
+    fn z_bound(&self) -> Range<S> {
+        self.z.clone()
+    }
+}
+
+impl<G, T, S> Grid3Ref<S> for Region3<G, S>
+where
+    G: Deref<Target=ArrayGrid3<T, S>>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let (guard, local) = self.locate(coord.into());
+        self.guards[guard].idx(local)
+    }
+}
+
+impl<G, T, S> Grid3Mut<S> for Region3<G, S>
+where
+    G: DerefMut<Target=ArrayGrid3<T, S>>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let (guard, local) = self.locate(coord.into());
+        self.guards[guard].midx(local)
+    }
+}
+
+impl<G, T, S> Grid3Get<S> for Region3<G, S>
+where
+    G: Deref<Target=ArrayGrid3<T, S>>,
+    T: Clone,
+    S: Scalar,
+{
+    fn get<I: Into<Vector3<S>>>(&self, coord: I) -> Self::Item 
+    { self.idx(coord).clone() }
+}
+
+impl<G, T, S> Grid3Set<S> for Region3<G, S>
+where
+    G: DerefMut<Target=ArrayGrid3<T, S>>,
+    S: Scalar,
+{
+    fn set<I: Into<Vector3<S>>>(&mut self, coord: I, elem: Self::Item) 
+    { *self.midx(coord) = elem; }
+}
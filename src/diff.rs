@@ -0,0 +1,237 @@
+//! Differences between grids.
+//!
+//! A patch records the elements which differ between two grids
+//! with the same bounds, grouped into runs of consecutive changed
+//! elements along the x axis. Patches record both old and new
+//! values, so they can be inverted to undo them.
+
+use crate::{
+    scalar::Scalar,
+    range::BoundRange,
+    grid2::{Grid2Get, Grid2Set},
+    grid3::{Grid3Get, Grid3Set},
+};
+use mint::{Vector2, Vector3};
+use std::fmt::Debug;
+
+/// Consecutive changed elements along the x axis of a Grid2.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Run<T, S = i32> {
+    pub start: Vector2<S>,
+    pub old: Vec<T>,
+    pub new: Vec<T>,
+}
+
+/// Changes from one Grid2 to another.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Patch<T, S = i32> {
+    pub runs: Vec<Run<T, S>>,
+}
+
+/// Consecutive changed elements along the x axis of a Grid3.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Run3<T, S = i32> {
+    pub start: Vector3<S>,
+    pub old: Vec<T>,
+    pub new: Vec<T>,
+}
+
+/// Changes from one Grid3 to another.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Patch3<T, S = i32> {
+    pub runs: Vec<Run3<T, S>>,
+}
+
+/// Lower inclusive and upper exclusive bounds, which must be equal
+/// between the grids.
+fn same_bounds<S, A, B>(a: A, b: B) -> (S, S)
+where
+    A: BoundRange<S> + Debug,
+    B: BoundRange<S> + Debug,
+    S: Scalar,
+{
+    let bounds = (a.lower_inclusive(), a.upper_exclusive());
+    if bounds != (b.lower_inclusive(), b.upper_exclusive()) {
+        panic!("cannot diff grids with bounds {:?} and {:?}", a, b);
+    }
+    bounds
+}
+
+/// Diff one row, given its elements from both grids, into runs
+/// starting at their x coordinate.
+fn diff_row<T, S, F>(x_bounds: (S, S), mut get: F, mut push: impl FnMut(S, Vec<T>, Vec<T>))
+where
+    T: PartialEq,
+    S: Scalar,
+    F: FnMut(S) -> (T, T),
+{
+    let mut run: Option<(S, Vec<T>, Vec<T>)> = None;
+    let mut x = x_bounds.0;
+    while x < x_bounds.1 {
+        let (old, new) = get(x);
+        if old != new {
+            let (_, ref mut olds, ref mut news) = *run.get_or_insert_with(|| (x, Vec::new(), Vec::new()));
+            olds.push(old);
+            news.push(new);
+        } else if let Some((start, olds, news)) = run.take() {
+            push(start, olds, news);
+        }
+        x = x + S::ONE;
+    }
+    if let Some((start, olds, news)) = run.take() {
+        push(start, olds, news);
+    }
+}
+
+/// Changes from a to b, which must have the same bounds.
+pub fn diff<A, B, T, S>(a: &A, b: &B) -> Patch<T, S>
+where
+    A: Grid2Get<S, Item=T>,
+    A::XBound: BoundRange<S> + Debug,
+    A::YBound: BoundRange<S> + Debug,
+    B: Grid2Get<S, Item=T>,
+    B::XBound: BoundRange<S> + Debug,
+    B::YBound: BoundRange<S> + Debug,
+    T: PartialEq,
+    S: Scalar,
+{
+    let x_bounds = same_bounds(a.x_bound(), b.x_bound());
+    let (mut y, y_end) = same_bounds(a.y_bound(), b.y_bound());
+    
+    let mut runs = Vec::new();
+    while y < y_end {
+        diff_row(
+            x_bounds,
+            |x| {
+                let coord = Vector2 { x, y };
+                (a.get(coord), b.get(coord))
+            },
+            |x, old, new| runs.push(Run {
+                start: Vector2 { x, y },
+                old,
+                new,
+            }));
+        y = y + S::ONE;
+    }
+    Patch { runs }
+}
+
+/// Changes from a to b, which must have the same bounds.
+pub fn diff3<A, B, T, S>(a: &A, b: &B) -> Patch3<T, S>
+where
+    A: Grid3Get<S, Item=T>,
+    A::XBound: BoundRange<S> + Debug,
+    A::YBound: BoundRange<S> + Debug,
+    A::ZBound: BoundRange<S> + Debug,
+    B: Grid3Get<S, Item=T>,
+    B::XBound: BoundRange<S> + Debug,
+    B::YBound: BoundRange<S> + Debug,
+    B::ZBound: BoundRange<S> + Debug,
+    T: PartialEq,
+    S: Scalar,
+{
+    let x_bounds = same_bounds(a.x_bound(), b.x_bound());
+    let (y_start, y_end) = same_bounds(a.y_bound(), b.y_bound());
+    let (mut z, z_end) = same_bounds(a.z_bound(), b.z_bound());
+    
+    let mut runs = Vec::new();
+    while z < z_end {
+        let mut y = y_start;
+        while y < y_end {
+            diff_row(
+                x_bounds,
+                |x| {
+                    let coord = Vector3 { x, y, z };
+                    (a.get(coord), b.get(coord))
+                },
+                |x, old, new| runs.push(Run3 {
+                    start: Vector3 { x, y, z },
+                    old,
+                    new,
+                }));
+            y = y + S::ONE;
+        }
+        z = z + S::ONE;
+    }
+    Patch3 { runs }
+}
+
+impl<T, S: Scalar> Patch<T, S> {
+    /// Whether the grids were equal.
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+    
+    /// Number of changed elements.
+    pub fn changed(&self) -> usize {
+        self.runs.iter().map(|run| run.new.len()).sum()
+    }
+    
+    /// Write the new values into a grid.
+    pub fn apply<G>(&self, grid: &mut G)
+    where
+        G: Grid2Set<S, Item=T>,
+        T: Clone,
+    {
+        for run in &self.runs {
+            let mut coord = run.start;
+            for value in &run.new {
+                grid.set(coord, value.clone());
+                coord.x = coord.x + S::ONE;
+            }
+        }
+    }
+    
+    /// Patch which undoes this patch.
+    pub fn invert(self) -> Self {
+        Patch {
+            runs: self.runs.into_iter()
+                .map(|run| Run {
+                    start: run.start,
+                    old: run.new,
+                    new: run.old,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<T, S: Scalar> Patch3<T, S> {
+    /// Whether the grids were equal.
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+    
+    /// Number of changed elements.
+    pub fn changed(&self) -> usize {
+        self.runs.iter().map(|run| run.new.len()).sum()
+    }
+    
+    /// Write the new values into a grid.
+    pub fn apply<G>(&self, grid: &mut G)
+    where
+        G: Grid3Set<S, Item=T>,
+        T: Clone,
+    {
+        for run in &self.runs {
+            let mut coord = run.start;
+            for value in &run.new {
+                grid.set(coord, value.clone());
+                coord.x = coord.x + S::ONE;
+            }
+        }
+    }
+    
+    /// Patch which undoes this patch.
+    pub fn invert(self) -> Self {
+        Patch3 {
+            runs: self.runs.into_iter()
+                .map(|run| Run3 {
+                    start: run.start,
+                    old: run.new,
+                    new: run.old,
+                })
+                .collect(),
+        }
+    }
+}
@@ -1,84 +1,252 @@
 //! Heap-allocated array implementation of Grid2.
 
 use crate::{
-    range::Range0To,
+    range::{Range0To, clip},
+    scalar::Scalar,
     grid2::*,
 };
 use mint::Vector2;
+use std::{
+    convert::Infallible,
+    mem,
+    ops::Range,
+};
 
 /// Heap-allocated array implementation of Grid2.
-pub struct ArrayGrid2<T> {
+pub struct ArrayGrid2<T, S = i32> {
     alloc: Box<[T]>,
-    x_len: i32,
-    y_len: i32,
+    x_len: S,
+    y_len: S,
 }
 
-impl<T> ArrayGrid2<T> {
-    pub fn new<I, F>(x_len: i32, y_len: i32, mut startval: F) -> Self
+impl<T, S: Scalar> ArrayGrid2<T, S> {
+    pub fn new<I, F>(x_len: S, y_len: S, mut startval: F) -> Self
     where
-        I: From<Vector2<i32>>,
+        I: From<Vector2<S>>,
         F: FnMut(I) -> T
     {
-        assert!(x_len >= 0);
-        assert!(y_len >= 0);
+        match Self::try_new(x_len, y_len, |i| Ok::<T, Infallible>(startval(i))) {
+            Ok(grid) => grid,
+            Err(never) => match never {},
+        }
+    }
+    
+    /// Populate with a fallible function, stopping at the first error.
+    pub fn try_new<I, F, E>(x_len: S, y_len: S, mut startval: F) -> Result<Self, E>
+    where
+        I: From<Vector2<S>>,
+        F: FnMut(I) -> Result<T, E>
+    {
+        assert!(x_len >= S::ZERO);
+        assert!(y_len >= S::ZERO);
         
-        let len = x_len * y_len;
-        let mut v: Vec<T> = Vec::with_capacity(len as usize);
-        for y in 0..y_len {
-            for x in 0..x_len {
-                let item = startval(I::from(Vector2 { x, y }));
+        let len = x_len.to_usize() * y_len.to_usize();
+        let mut v: Vec<T> = Vec::with_capacity(len);
+        for y in 0..y_len.to_usize() {
+            for x in 0..x_len.to_usize() {
+                let item = startval(I::from(Vector2 {
+                    x: S::from_usize(x),
+                    y: S::from_usize(y),
+                }))?;
                 v.push(item);
             }
         }
-        ArrayGrid2 {
+        Ok(ArrayGrid2 {
             alloc: v.into_boxed_slice(),
             x_len,
             y_len,
-        }
+        })
     }
     
-    pub fn broadcast(x_len: i32, y_len: i32, startval: T) -> Self
+    pub fn broadcast(x_len: S, y_len: S, startval: T) -> Self
     where
-        T: Clone 
+        T: Clone
     {
-        Self::new(x_len, y_len, |_: Vector2<i32>| startval.clone())
+        Self::new(x_len, y_len, |_: Vector2<S>| startval.clone())
     }
     
-    fn inner_index(&self, x: i32, y: i32) -> Option<i32> {
-        if x < 0 || x >= self.x_len || y < 0 || y >= self.y_len {
+    fn inner_index(&self, x: S, y: S) -> Option<usize> {
+        if x < S::ZERO || x >= self.x_len || y < S::ZERO || y >= self.y_len {
             None
         } else {
-            Some(y * self.x_len + x)
+            Some(y.to_usize() * self.x_len.to_usize() + x.to_usize())
         }
     }
+    
+    /// Keep only the elements within the given ranges, moving the
+    /// lowest corner of the ranges to the origin.
+    ///
+    /// Panics if the ranges are not within this grid's bounds.
+    pub fn crop(&mut self, x: Range<S>, y: Range<S>) {
+        assert!(S::ZERO <= x.start && x.start <= x.end && x.end <= self.x_len,
+            "crop x range out of bounds");
+        assert!(S::ZERO <= y.start && y.start <= y.end && y.end <= self.y_len,
+            "crop y range out of bounds");
+        let origin = [x.start.to_i64(), y.start.to_i64()];
+        self.reframe(x.end - x.start, y.end - y.start, origin, || unreachable!());
+    }
+    
+    /// Rearrange into a grid of the given size, where each coordinate
+    /// takes the element at that coordinate plus origin, or a new one
+    /// from fill if that is out of bounds.
+    ///
+    /// Elements are moved by swapping within the existing buffer: in
+    /// row-major order, kept elements moving backward are visited
+    /// ascending and those moving forward descending, so none is
+    /// overwritten before it has moved.
+    fn reframe<F>(&mut self, x_len: S, y_len: S, origin: [i64; 2], mut fill: F)
+    where
+        F: FnMut() -> T
+    {
+        assert!(x_len >= S::ZERO);
+        assert!(y_len >= S::ZERO);
+        
+        let old_x_len = self.x_len.to_i64();
+        let old_y_len = self.y_len.to_i64();
+        let new_x_len = x_len.to_i64();
+        let len = x_len.to_usize() * y_len.to_usize();
+        let source = |i: usize| -> Option<usize> {
+            let x = i as i64 % new_x_len + origin[0];
+            let y = i as i64 / new_x_len + origin[1];
+            if x < 0 || x >= old_x_len || y < 0 || y >= old_y_len {
+                None
+            } else {
+                Some((y * old_x_len + x) as usize)
+            }
+        };
+        
+        let mut v = mem::take(&mut self.alloc).into_vec();
+        if v.len() < len {
+            v.resize_with(len, &mut fill);
+        }
+        for i in 0..len {
+            match source(i) {
+                Some(j) if j > i => v.swap(i, j),
+                _ => (),
+            }
+        }
+        for i in (0..len).rev() {
+            match source(i) {
+                Some(j) if j < i => v.swap(i, j),
+                _ => (),
+            }
+        }
+        v.truncate(len);
+        for (i, elem) in v.iter_mut().enumerate() {
+            if source(i).is_none() {
+                *elem = fill();
+            }
+        }
+        
+        self.alloc = v.into_boxed_slice();
+        self.x_len = x_len;
+        self.y_len = y_len;
+    }
 }
 
-impl<T> Grid2 for ArrayGrid2<T> {
+impl<T: Clone, S: Scalar> ArrayGrid2<T, S> {
+    /// Copy a grid into this one, with its lowest corner at
+    /// dst_offset, clipped to this grid's bounds.
+    ///
+    /// This is equivalent to `algo::blit::blit`, but copies whole
+    /// rows at a time.
+    pub fn blit_from<I>(&mut self, src: &ArrayGrid2<T, S>, dst_offset: I)
+    where
+        I: Into<Vector2<S>>
+    {
+        let dst_offset = dst_offset.into();
+        let x = clip(S::ZERO..src.x_len, dst_offset.x, S::ZERO..self.x_len);
+        let y = clip(S::ZERO..src.y_len, dst_offset.y, S::ZERO..self.y_len);
+        if let (Some((x_range, src_x)), Some((y_range, src_y))) = (x, y) {
+            let len = (x_range.end - x_range.start).to_usize();
+            let rows = (y_range.end - y_range.start).to_usize();
+            for row in 0..rows {
+                let dst_start = self.inner_index(x_range.start, y_range.start + S::from_usize(row)).unwrap();
+                let src_start = src.inner_index(src_x, src_y + S::from_usize(row)).unwrap();
+                self.alloc[dst_start..dst_start + len]
+                    .clone_from_slice(&src.alloc[src_start..src_start + len]);
+            }
+        }
+    }
+    
+    /// Set every element in a rectangle, clipped to this grid's
+    /// bounds.
+    ///
+    /// This is equivalent to `algo::blit::fill_rect`, but fills
+    /// whole rows at a time.
+    pub fn fill_rect(&mut self, x: Range<S>, y: Range<S>, value: T) {
+        let x = clip(x.clone(), x.start, S::ZERO..self.x_len);
+        let y = clip(y.clone(), y.start, S::ZERO..self.y_len);
+        if let (Some((x_range, _)), Some((y_range, _))) = (x, y) {
+            let len = (x_range.end - x_range.start).to_usize();
+            let mut y = y_range.start;
+            while y < y_range.end {
+                let start = self.inner_index(x_range.start, y).unwrap();
+                self.alloc[start..start + len].fill(value.clone());
+                y = y + S::ONE;
+            }
+        }
+    }
+    
+    /// Change the size of this grid, keeping elements at their
+    /// coordinates, dropping those now out of bounds, and filling new
+    /// space with fill.
+    pub fn resize(&mut self, x_len: S, y_len: S, fill: T) {
+        self.reframe(x_len, y_len, [0, 0], || fill.clone());
+    }
+    
+    /// Grow this grid on each side, filling new space with fill.
+    ///
+    /// Top is the low y side, so existing elements move by
+    /// (left, top).
+    pub fn pad(&mut self, left: S, right: S, top: S, bottom: S, fill: T) {
+        assert!(left >= S::ZERO && right >= S::ZERO && top >= S::ZERO && bottom >= S::ZERO,
+            "negative padding");
+        let x_len = self.x_len + left + right;
+        let y_len = self.y_len + top + bottom;
+        let origin = [-left.to_i64(), -top.to_i64()];
+        self.reframe(x_len, y_len, origin, || fill.clone());
+    }
+    
+    /// Move every element so that the one at origin ends up at
+    /// (0, 0), keeping this grid's size, dropping elements moved out
+    /// of bounds, and filling vacated space with fill.
+    pub fn shift_origin<I>(&mut self, origin: I, fill: T)
+    where
+        I: Into<Vector2<S>>
+    {
+        let origin = origin.into();
+        let origin = [origin.x.to_i64(), origin.y.to_i64()];
+        self.reframe(self.x_len, self.y_len, origin, || fill.clone());
+    }
+}
+
+impl<T, S: Scalar> Grid2<S> for ArrayGrid2<T, S> {
     type Item = T;
-    type XBound = Range0To;
-    type YBound = Range0To;
+    type XBound = Range0To<S>;
+    type YBound = Range0To<S>;
     
-    fn x_bound(&self) -> Range0To {
+    fn x_bound(&self) -> Range0To<S> {
         Range0To { end: self.x_len }
     }
     
-    fn y_bound(&self) -> Range0To {
+    fn y_bound(&self) -> Range0To<S> {
         Range0To { end: self.y_len }
     }
 }
 
-impl<T> Grid2Len for ArrayGrid2<T> {}
+impl<T, S: Scalar> Grid2Len<S> for ArrayGrid2<T, S> {}
 
-impl<T> Grid2Ref for ArrayGrid2<T> {
+impl<T, S: Scalar> Grid2Ref<S> for ArrayGrid2<T, S> {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         let Vector2 { x, y } = coord;
         let option = self
             .inner_index(x, y)
-            .map(|i| &self.alloc[i as usize]);
+            .map(|i| &self.alloc[i]);
         match option {
             Some(o) => o,
             None => panic!("invalid index {:?}", coord),
@@ -86,16 +254,16 @@ impl<T> Grid2Ref for ArrayGrid2<T> {
     }
 }
 
-impl<T> Grid2Mut for ArrayGrid2<T> {
+impl<T, S: Scalar> Grid2Mut<S> for ArrayGrid2<T, S> {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         let Vector2 { x, y } = coord;
         let option = self
             .inner_index(x, y)
-            .map(move |i| &mut self.alloc[i as usize]);
+            .map(move |i| &mut self.alloc[i]);
         match option {
             Some(o) => o,
             None => panic!("invalid index {:?}", coord),
@@ -103,12 +271,180 @@ impl<T> Grid2Mut for ArrayGrid2<T> {
     }
 }
 
-impl<T: Clone> Grid2Get for ArrayGrid2<T> {
-    fn get<I: Into<Vector2<i32>>>(&self, coord: I) -> Self::Item 
+impl<T: Clone, S: Scalar> Grid2Get<S> for ArrayGrid2<T, S> {
+    fn get<I: Into<Vector2<S>>>(&self, coord: I) -> Self::Item 
+    { self.idx(coord).clone() }
+}
+
+impl<T, S: Scalar> Grid2Set<S> for ArrayGrid2<T, S> {
+    fn set<I: Into<Vector2<S>>>(&mut self, coord: I, elem: Self::Item) 
+    { *self.midx(coord) = elem; }
+}
+
+impl<T, S: Scalar> ArrayGrid2<T, S> {
+    /// Mutably borrow the whole grid as a splittable rectangle.
+    pub fn as_slice_mut(&mut self) -> ArrayGrid2SliceMut<'_, T, S> {
+        let rows = if self.x_len == S::ZERO {
+            (0..self.y_len.to_usize()).map(|_| Default::default()).collect()
+        } else {
+            self.alloc.chunks_mut(self.x_len.to_usize()).collect()
+        };
+        ArrayGrid2SliceMut {
+            rows,
+            x: S::ZERO..self.x_len,
+            y: S::ZERO..self.y_len,
+        }
+    }
+    
+    /// Mutably borrow the parts left and right of an x coordinate.
+    pub fn split_at_x(&mut self, x: S) -> (ArrayGrid2SliceMut<'_, T, S>, ArrayGrid2SliceMut<'_, T, S>) {
+        self.as_slice_mut().split_at_x(x)
+    }
+    
+    /// Mutably borrow the parts below and above a y coordinate.
+    pub fn split_at_y(&mut self, y: S) -> (ArrayGrid2SliceMut<'_, T, S>, ArrayGrid2SliceMut<'_, T, S>) {
+        self.as_slice_mut().split_at_y(y)
+    }
+    
+    /// Mutably borrow disjoint rectangles of up to w × h elements,
+    /// covering the grid in row-major order.
+    pub fn chunks_mut(&mut self, w: S, h: S) -> impl Iterator<Item=ArrayGrid2SliceMut<'_, T, S>> {
+        self.as_slice_mut().chunks_mut(w, h)
+    }
+}
+
+/// Mutably borrowed rectangle of an ArrayGrid2.
+///
+/// Coordinates are the same as in the parent grid. Rectangles can
+/// be split further, and disjoint rectangles can be sent to
+/// different threads.
+pub struct ArrayGrid2SliceMut<'a, T, S = i32> {
+    /// Rows, from the lowest y coordinate.
+    rows: Vec<&'a mut [T]>,
+    x: Range<S>,
+    y: Range<S>,
+}
+
+impl<'a, T, S: Scalar> ArrayGrid2SliceMut<'a, T, S> {
+    /// Split into the parts left and right of an x coordinate.
+    pub fn split_at_x(self, x: S) -> (Self, Self) {
+        assert!(self.x.start <= x && x <= self.x.end, "split point {:?} out of bounds", x);
+        
+        let n = (x - self.x.start).to_usize();
+        let (left, right) = self.rows.into_iter()
+            .map(|row| row.split_at_mut(n))
+            .unzip();
+        (
+            ArrayGrid2SliceMut {
+                rows: left,
+                x: self.x.start..x,
+                y: self.y.clone(),
+            },
+            ArrayGrid2SliceMut {
+                rows: right,
+                x: x..self.x.end,
+                y: self.y,
+            },
+        )
+    }
+    
+    /// Split into the parts below and above a y coordinate.
+    pub fn split_at_y(mut self, y: S) -> (Self, Self) {
+        assert!(self.y.start <= y && y <= self.y.end, "split point {:?} out of bounds", y);
+        
+        let above = self.rows.split_off((y - self.y.start).to_usize());
+        (
+            ArrayGrid2SliceMut {
+                rows: self.rows,
+                x: self.x.clone(),
+                y: self.y.start..y,
+            },
+            ArrayGrid2SliceMut {
+                rows: above,
+                x: self.x,
+                y: y..self.y.end,
+            },
+        )
+    }
+    
+    /// Split into disjoint rectangles of up to w × h elements, in
+    /// row-major order.
+    pub fn chunks_mut(self, w: S, h: S) -> impl Iterator<Item=Self> {
+        assert!(w > S::ZERO && h > S::ZERO, "chunk size must be positive");
+        
+        let mut chunks = Vec::new();
+        let mut rest = self;
+        while rest.y.start < rest.y.end {
+            let y = (rest.y.start + h).min(rest.y.end);
+            let (mut band, above) = rest.split_at_y(y);
+            rest = above;
+            while band.x.start < band.x.end {
+                let x = (band.x.start + w).min(band.x.end);
+                let (chunk, right) = band.split_at_x(x);
+                chunks.push(chunk);
+                band = right;
+            }
+        }
+        chunks.into_iter()
+    }
+    
+    fn inner_index(&self, x: S, y: S) -> Option<(usize, usize)> {
+        if self.x.contains(&x) && self.y.contains(&y) {
+            Some(((y - self.y.start).to_usize(), (x - self.x.start).to_usize()))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, S: Scalar> Grid2<S> for ArrayGrid2SliceMut<'_, T, S> {
+    type Item = T;
+    type XBound = Range<S>;
+    type YBound = Range<S>;
+    
+    fn x_bound(&self) -> Range<S> {
+        self.x.clone()
+    }
+    
+    fn y_bound(&self) -> Range<S> {
+        self.y.clone()
+    }
+}
+
+impl<T, S: Scalar> Grid2Ref<S> for ArrayGrid2SliceMut<'_, T, S> {
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = coord.into();
+        let Vector2 { x, y } = coord;
+        match self.inner_index(x, y) {
+            Some((row, i)) => &self.rows[row][i],
+            None => panic!("invalid index {:?}", coord),
+        }
+    }
+}
+
+impl<T, S: Scalar> Grid2Mut<S> for ArrayGrid2SliceMut<'_, T, S> {
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = coord.into();
+        let Vector2 { x, y } = coord;
+        match self.inner_index(x, y) {
+            Some((row, i)) => &mut self.rows[row][i],
+            None => panic!("invalid index {:?}", coord),
+        }
+    }
+}
+
+impl<T: Clone, S: Scalar> Grid2Get<S> for ArrayGrid2SliceMut<'_, T, S> {
+    fn get<I: Into<Vector2<S>>>(&self, coord: I) -> Self::Item 
     { self.idx(coord).clone() }
 }
 
-impl<T> Grid2Set for ArrayGrid2<T> {
-    fn set<I: Into<Vector2<i32>>>(&mut self, coord: I, elem: Self::Item) 
+impl<T, S: Scalar> Grid2Set<S> for ArrayGrid2SliceMut<'_, T, S> {
+    fn set<I: Into<Vector2<S>>>(&mut self, coord: I, elem: Self::Item) 
     { *self.midx(coord) = elem; }
 }
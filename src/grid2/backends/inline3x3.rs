@@ -2,22 +2,25 @@
 
 use crate::{
     range::Range0To,
+    scalar::Scalar,
     grid2::*,
 };
 use mint::Vector2;
+use std::marker::PhantomData;
 
-pub struct Inline3x3Grid<T> {
+pub struct Inline3x3Grid<T, S = i32> {
     array: [[T; 3]; 3],
+    p: PhantomData<fn(S)>,
 }
 
-impl<T> Inline3x3Grid<T> {
+impl<T, S: Scalar> Inline3x3Grid<T, S> {
     pub fn new<I, F>(mut startval: F) -> Self 
     where
-        I: From<Vector2<i32>>,
+        I: From<Vector2<S>>,
         F: FnMut(I) -> T
     {
-        fn xy<I: From<Vector2<i32>>>(x: i32, y: i32) -> I {
-            I::from(Vector2 { x, y })
+        fn xy<I: From<Vector2<S>>, S: Scalar>(x: usize, y: usize) -> I {
+            I::from(Vector2 { x: S::from_usize(x), y: S::from_usize(y) })
         }
         
         let array = [
@@ -25,7 +28,7 @@ impl<T> Inline3x3Grid<T> {
             [startval(xy(1, 0)), startval(xy(1, 1)), startval(xy(1, 2))],
             [startval(xy(2, 0)), startval(xy(2, 1)), startval(xy(2, 2))],
         ];
-        Inline3x3Grid { array }
+        Inline3x3Grid { array, p: PhantomData }
     }
     
     
@@ -33,58 +36,58 @@ impl<T> Inline3x3Grid<T> {
     where
         T: Clone
     {
-        Self::new(|_: Vector2<i32>| startval.clone())
+        Self::new(|_: Vector2<S>| startval.clone())
     }
 }
 
-impl<T> From<[[T; 3]; 3]> for Inline3x3Grid<T> {
+impl<T, S> From<[[T; 3]; 3]> for Inline3x3Grid<T, S> {
     fn from(array: [[T; 3]; 3]) -> Self {
-        Inline3x3Grid { array }
+        Inline3x3Grid { array, p: PhantomData }
     }
 }
 
-impl<T> Grid2 for Inline3x3Grid<T> {
+impl<T, S: Scalar> Grid2<S> for Inline3x3Grid<T, S> {
     type Item = T;
-    type XBound = Range0To;
-    type YBound = Range0To;
+    type XBound = Range0To<S>;
+    type YBound = Range0To<S>;
     
-    fn x_bound(&self) -> Range0To {
-        Range0To { end: 3 }
+    fn x_bound(&self) -> Range0To<S> {
+        Range0To { end: S::from_usize(3) }
     }
     
-    fn y_bound(&self) -> Range0To {
-        Range0To { end: 3 }
+    fn y_bound(&self) -> Range0To<S> {
+        Range0To { end: S::from_usize(3) }
     }
 }
 
-impl<T> Grid2Len for Inline3x3Grid<T> {}
+impl<T, S: Scalar> Grid2Len<S> for Inline3x3Grid<T, S> {}
 
-impl<T> Grid2Ref for Inline3x3Grid<T> {
+impl<T, S: Scalar> Grid2Ref<S> for Inline3x3Grid<T, S> {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
-        &self.array[coord.x as usize][coord.y as usize]
+        &self.array[coord.x.to_usize()][coord.y.to_usize()]
     }
 }
 
-impl<T> Grid2Mut for Inline3x3Grid<T> {
+impl<T, S: Scalar> Grid2Mut<S> for Inline3x3Grid<T, S> {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
-        &mut self.array[coord.x as usize][coord.y as usize]
+        &mut self.array[coord.x.to_usize()][coord.y.to_usize()]
     }
 }
 
-impl<T: Clone> Grid2Get for Inline3x3Grid<T> {
-    fn get<I: Into<Vector2<i32>>>(&self, coord: I) -> Self::Item 
+impl<T: Clone, S: Scalar> Grid2Get<S> for Inline3x3Grid<T, S> {
+    fn get<I: Into<Vector2<S>>>(&self, coord: I) -> Self::Item 
     { self.idx(coord).clone() }
 }
 
-impl<T> Grid2Set for Inline3x3Grid<T> {
-    fn set<I: Into<Vector2<i32>>>(&mut self, coord: I, elem: Self::Item) 
+impl<T, S: Scalar> Grid2Set<S> for Inline3x3Grid<T, S> {
+    fn set<I: Into<Vector2<S>>>(&mut self, coord: I, elem: Self::Item) 
     { *self.midx(coord) = elem; }
 }
@@ -1,6 +1,9 @@
 //! Kolmogorov encoding of Grid2.
 
-use crate::grid2::*;
+use crate::{
+    scalar::Scalar,
+    grid2::*,
+};
 use mint::Vector2;
 use std::{
     ops::RangeFull,
@@ -14,8 +17,7 @@ use std::{
 /// unbounded.
 pub struct KolmoGrid2<F, I, T> 
 where
-    F: Fn(I) -> T,
-    I: From<Vector2<i32>>
+    F: Fn(I) -> T
 {
     func: F,
     p: PhantomData<fn(T, I)>,
@@ -23,8 +25,7 @@ where
 
 impl<F, I, T> KolmoGrid2<F, I, T>
 where
-    F: Fn(I) -> T,
-    I: From<Vector2<i32>>
+    F: Fn(I) -> T
 {
     pub fn new(func: F) -> Self {
         KolmoGrid2 {
@@ -34,10 +35,11 @@ where
     }
 }
 
-impl<F, I, T> Grid2 for KolmoGrid2<F, I, T>
+impl<F, I, T, S> Grid2<S> for KolmoGrid2<F, I, T>
 where
     F: Fn(I) -> T,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
     type Item = T;
     type XBound = RangeFull;
@@ -47,14 +49,15 @@ where
     fn y_bound(&self) -> RangeFull { RangeFull }
 }
 
-impl<F, I, T> Grid2Get for KolmoGrid2<F, I, T>
+impl<F, I, T, S> Grid2Get<S> for KolmoGrid2<F, I, T>
 where
     F: Fn(I) -> T,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
     fn get<C>(&self, coord: C) -> Self::Item
     where
-        C: Into<Vector2<i32>>
+        C: Into<Vector2<S>>
     {
         (self.func)(I::from(coord.into()))
     }
@@ -1,6 +1,9 @@
 //! Kolmogorov by-mutable-reference encoding of Grid2.
 
-use crate::grid2::*;
+use crate::{
+    scalar::Scalar,
+    grid2::*,
+};
 use mint::Vector2;
 use std::{
     ops::RangeFull,
@@ -15,8 +18,7 @@ use std::{
 pub struct KolmoMutGrid2<'a, F, I, T> 
 where
     F: Fn(I) -> &'a mut T,
-    T: 'a,
-    I: From<Vector2<i32>>
+    T: 'a
 {
     func: F,
     p: PhantomData<fn(T, I)>,
@@ -25,8 +27,7 @@ where
 impl<'a, F, I, T> KolmoMutGrid2<'a, F, I, T>
 where
     F: Fn(I) -> &'a mut T,
-    T: 'a,
-    I: From<Vector2<i32>>
+    T: 'a
 {
     pub fn new(func: F) -> Self {
         KolmoMutGrid2 {
@@ -36,11 +37,12 @@ where
     }
 }
 
-impl<'a, F, I, T> Grid2 for KolmoMutGrid2<'a, F, I, T>
+impl<'a, F, I, T, S> Grid2<S> for KolmoMutGrid2<'a, F, I, T>
 where
     F: Fn(I) -> &'a mut T,
     T: 'a,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
     type Item = T;
     type XBound = RangeFull;
@@ -52,26 +54,28 @@ where
 
 
 
-impl<'a, F, I, T> Grid2Mut for KolmoMutGrid2<'a, F, I, T>
+impl<'a, F, I, T, S> Grid2Mut<S> for KolmoMutGrid2<'a, F, I, T>
 where
     F: Fn(I) -> &'a mut T,
     T: 'a,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
     fn midx<C>(&mut self, coord: C) -> &mut Self::Item
     where
-        C: Into<Vector2<i32>>
+        C: Into<Vector2<S>>
     {
         (self.func)(I::from(coord.into()))
     }
 }
 
-impl<'a, F, I, T> Grid2Set for KolmoMutGrid2<'a, F, I, T> 
+impl<'a, F, I, T, S> Grid2Set<S> for KolmoMutGrid2<'a, F, I, T> 
 where
     F: Fn(I) -> &'a mut T,
     T: 'a,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
-    fn set<C: Into<Vector2<i32>>>(&mut self, coord: C, elem: Self::Item) 
+    fn set<C: Into<Vector2<S>>>(&mut self, coord: C, elem: Self::Item) 
     { *self.midx(coord) = elem; }
 }
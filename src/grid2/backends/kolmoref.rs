@@ -1,6 +1,9 @@
 //! Kolmogorov by-refernce encoding of Grid2.
 
-use crate::grid2::*;
+use crate::{
+    scalar::Scalar,
+    grid2::*,
+};
 use mint::Vector2;
 use std::{
     ops::RangeFull,
@@ -15,8 +18,7 @@ use std::{
 pub struct KolmoRefGrid2<'a, F, I, T> 
 where
     F: Fn(I) -> &'a T,
-    T: 'a,
-    I: From<Vector2<i32>>
+    T: 'a
 {
     func: F,
     p: PhantomData<fn(T, I)>,
@@ -25,8 +27,7 @@ where
 impl<'a, F, I, T> KolmoRefGrid2<'a, F, I, T>
 where
     F: Fn(I) -> &'a T,
-    T: 'a,
-    I: From<Vector2<i32>>
+    T: 'a
 {
     pub fn new(func: F) -> Self {
         KolmoRefGrid2 {
@@ -36,11 +37,12 @@ where
     }
 }
 
-impl<'a, F, I, T> Grid2 for KolmoRefGrid2<'a, F, I, T>
+impl<'a, F, I, T, S> Grid2<S> for KolmoRefGrid2<'a, F, I, T>
 where
     F: Fn(I) -> &'a T,
     T: 'a,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
     type Item = T;
     type XBound = RangeFull;
@@ -50,27 +52,29 @@ where
     fn y_bound(&self) -> RangeFull { RangeFull }
 }
 
-impl<'a, F, I, T> Grid2Ref for KolmoRefGrid2<'a, F, I, T>
+impl<'a, F, I, T, S> Grid2Ref<S> for KolmoRefGrid2<'a, F, I, T>
 where
     F: Fn(I) -> &'a T,
     T: 'a,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
     fn idx<C>(&self, coord: C) -> &Self::Item
     where
-        C: Into<Vector2<i32>>
+        C: Into<Vector2<S>>
     {
         (self.func)(I::from(coord.into()))
     }
 }
 
-impl<'a, F, I, T> Grid2Get for KolmoRefGrid2<'a, F, I, T>
+impl<'a, F, I, T, S> Grid2Get<S> for KolmoRefGrid2<'a, F, I, T>
 where
     F: Fn(I) -> &'a T,
     T: Clone + 'a,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
-    fn get<C: Into<Vector2<i32>>>(&self, coord: C) -> Self::Item 
+    fn get<C: Into<Vector2<S>>>(&self, coord: C) -> Self::Item 
     { self.idx(coord).clone() }
 }
 
@@ -1,6 +1,9 @@
 //! Kolmogorov by-refernce read/write encoding of Grid2.
 
-use crate::grid2::*;
+use crate::{
+    scalar::Scalar,
+    grid2::*,
+};
 use mint::Vector2;
 use std::{
     ops::RangeFull,
@@ -20,8 +23,7 @@ use std::{
 pub struct KolmoRwGrid2<I, R, T, Fr, Fw> 
 where
     Fr: Fn(I, &R) -> &T,
-    Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector2<i32>>
+    Fw: FnMut(I, &mut R) -> &mut T
 {
     referent: R,
     reader: Fr,
@@ -32,8 +34,7 @@ where
 impl<I, R, T, Fr, Fw> KolmoRwGrid2<I, R, T, Fr, Fw> 
 where
     Fr: Fn(I, &R) -> &T,
-    Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector2<i32>>
+    Fw: FnMut(I, &mut R) -> &mut T
 {
     pub fn new(referent: R, reader: Fr, writer: Fw) -> Self {
         KolmoRwGrid2 {
@@ -45,11 +46,12 @@ where
     }
 }
 
-impl<I, R, T, Fr, Fw> Grid2 for KolmoRwGrid2<I, R, T, Fr, Fw>
+impl<I, R, T, Fr, Fw, S> Grid2<S> for KolmoRwGrid2<I, R, T, Fr, Fw>
 where
     Fr: Fn(I, &R) -> &T,
     Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
     type Item = T;
     type XBound = RangeFull;
@@ -59,52 +61,56 @@ where
     fn y_bound(&self) -> RangeFull { RangeFull }
 }
 
-impl<I, R, T, Fr, Fw> Grid2Ref for KolmoRwGrid2<I, R, T, Fr, Fw>
+impl<I, R, T, Fr, Fw, S> Grid2Ref<S> for KolmoRwGrid2<I, R, T, Fr, Fw>
 where
     Fr: Fn(I, &R) -> &T,
     Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
     fn idx<C>(&self, coord: C) -> &Self::Item
     where
-        C: Into<Vector2<i32>>
+        C: Into<Vector2<S>>
     {
         (self.reader)(I::from(coord.into()), &self.referent)
     }
 }
 
-impl<I, R, T, Fr, Fw> Grid2Mut for KolmoRwGrid2<I, R, T, Fr, Fw>
+impl<I, R, T, Fr, Fw, S> Grid2Mut<S> for KolmoRwGrid2<I, R, T, Fr, Fw>
 where
     Fr: Fn(I, &R) -> &T,
     Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
     fn midx<C>(&mut self, coord: C) -> &mut Self::Item
     where
-        C: Into<Vector2<i32>>
+        C: Into<Vector2<S>>
     {
         (self.writer)(I::from(coord.into()), &mut self.referent)
     }
 }
 
 
-impl<I, R, T, Fr, Fw> Grid2Get for KolmoRwGrid2<I, R, T, Fr, Fw>
+impl<I, R, T, Fr, Fw, S> Grid2Get<S> for KolmoRwGrid2<I, R, T, Fr, Fw>
 where
     T: Clone,
     Fr: Fn(I, &R) -> &T,
     Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
-    fn get<C: Into<Vector2<i32>>>(&self, coord: C) -> Self::Item 
+    fn get<C: Into<Vector2<S>>>(&self, coord: C) -> Self::Item 
     { self.idx(coord).clone() }
 }
 
-impl<I, R, T, Fr, Fw> Grid2Set for KolmoRwGrid2<I, R, T, Fr, Fw>
+impl<I, R, T, Fr, Fw, S> Grid2Set<S> for KolmoRwGrid2<I, R, T, Fr, Fw>
 where
     Fr: Fn(I, &R) -> &T,
     Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector2<i32>>
+    I: From<Vector2<S>>,
+    S: Scalar,
 {
-    fn set<C: Into<Vector2<i32>>>(&mut self, coord: C, elem: Self::Item) 
+    fn set<C: Into<Vector2<S>>>(&mut self, coord: C, elem: Self::Item) 
     { *self.midx(coord) = elem; }
 }
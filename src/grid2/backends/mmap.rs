@@ -0,0 +1,352 @@
+//! Memory-mapped file implementation of Grid2.
+//!
+//! Files use the `io::binary` layout, uncompressed. Elements are
+//! read and written in place, so grids larger than memory can be
+//! processed without loading them.
+
+use crate::{
+    range::Range0To,
+    scalar::Scalar,
+    grid2::*,
+    io::binary::{self, Compression, Error, Header},
+};
+use memmap2::{Mmap, MmapMut};
+use mint::Vector2;
+use std::{
+    fs::{File, OpenOptions},
+    ops::Deref,
+    path::Path,
+    marker::PhantomData,
+    convert::TryFrom,
+    io,
+    mem,
+    slice,
+};
+
+/// Plain data which can be read directly from the bytes of a file.
+///
+/// # Safety
+///
+/// The type must have no padding, and every bit pattern of its size
+/// must be a valid value.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($t:ty),*)=>{$(
+        unsafe impl Pod for $t {}
+    )*};
+}
+
+impl_pod!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+/// Whether a memory-mapped grid can be written to.
+///
+/// Only grids mapped with `ReadWrite` implement the mutating grid
+/// traits, so writing to a read-only mapping does not compile.
+pub trait Access {
+    #[doc(hidden)]
+    type Map: Deref<Target = [u8]>;
+    
+    #[doc(hidden)]
+    const WRITABLE: bool;
+    
+    #[doc(hidden)]
+    unsafe fn map(file: &File) -> io::Result<Self::Map>;
+}
+
+/// Read-only access to a mapped file.
+pub enum ReadOnly {}
+
+/// Read-write access to a mapped file.
+pub enum ReadWrite {}
+
+impl Access for ReadOnly {
+    type Map = Mmap;
+    
+    const WRITABLE: bool = false;
+    
+    unsafe fn map(file: &File) -> io::Result<Mmap> {
+        Mmap::map(file)
+    }
+}
+
+impl Access for ReadWrite {
+    type Map = MmapMut;
+    
+    const WRITABLE: bool = true;
+    
+    unsafe fn map(file: &File) -> io::Result<MmapMut> {
+        MmapMut::map_mut(file)
+    }
+}
+
+/// Mapped file contents.
+pub(crate) struct Mapping<A: Access> {
+    map: A::Map,
+    /// Length of the header before the elements.
+    offset: usize,
+}
+
+impl<A: Access> Mapping<A> {
+    /// Elements following the header.
+    pub(crate) fn elements<T: Pod>(&self, len: usize) -> &[T] {
+        let bytes = &self.map[self.offset..];
+        // safe because the length and alignment were validated on open
+        unsafe { slice::from_raw_parts(bytes.as_ptr() as *const T, len) }
+    }
+}
+
+impl Mapping<ReadWrite> {
+    /// Elements following the header, mutably.
+    pub(crate) fn elements_mut<T: Pod>(&mut self, len: usize) -> &mut [T] {
+        let bytes = &mut self.map[self.offset..];
+        // safe because the length and alignment were validated on open
+        unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, len) }
+    }
+    
+    pub(crate) fn flush(&self) -> io::Result<()> {
+        self.map.flush()
+    }
+}
+
+/// Convert axis lengths into scalars.
+pub(crate) fn scalar_lens<S: Scalar>(lens: &[usize]) -> Result<Vec<S>, Error> {
+    lens.iter()
+        .map(|&len| {
+            let s = S::from_usize(len);
+            if s >= S::ZERO && s.to_usize() == len {
+                Ok(s)
+            } else {
+                Err(Error::TooLarge)
+            }
+        })
+        .collect()
+}
+
+/// Expected file length for some axis lengths.
+fn file_len<T: Pod>(lens: &[usize]) -> Result<u64, Error> {
+    lens.iter()
+        .try_fold(mem::size_of::<T>(), |acc, &len| acc.checked_mul(len))
+        .and_then(|body| body.checked_add(binary::header_len(lens.len())))
+        .map(|len| len as u64)
+        .ok_or(Error::TooLarge)
+}
+
+/// Map a file, validating its header, returning the mapping and
+/// the length of each axis.
+///
+/// # Safety
+///
+/// The file must not be modified except through the mapping while
+/// the mapping exists.
+pub(crate) unsafe fn map<T: Pod, A: Access>(path: &Path, dims: usize) -> Result<(Mapping<A>, Vec<usize>), Error> {
+    if cfg!(target_endian = "big") {
+        return Err(Error::Unmappable("elements are little-endian"));
+    }
+    
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(A::WRITABLE)
+        .open(path)?;
+    let header = binary::read_header(&mut file)?;
+    if header.bounds.len() != dims {
+        return Err(Error::WrongDimensions {
+            expected: dims,
+            found: header.bounds.len(),
+        });
+    }
+    if header.element_size as usize != mem::size_of::<T>() {
+        return Err(Error::WrongElementSize {
+            expected: mem::size_of::<T>(),
+            found: header.element_size as usize,
+        });
+    }
+    if header.compression != Compression::None {
+        return Err(Error::Unmappable("file is compressed"));
+    }
+    if header.version != binary::VERSION {
+        return Err(Error::Unmappable("file is an older version"));
+    }
+    
+    let lens = header.bounds.iter()
+        .map(|bound| usize::try_from(bound.end - bound.start).map_err(|_| Error::TooLarge))
+        .collect::<Result<Vec<usize>, Error>>()?;
+    let expected = file_len::<T>(&lens)?;
+    let found = file.metadata()?.len();
+    if expected != found {
+        return Err(Error::WrongFileLength { expected, found });
+    }
+    
+    let mapping: Mapping<A> = Mapping {
+        map: A::map(&file)?,
+        offset: binary::header_len(dims),
+    };
+    let start = mapping.map.as_ptr() as usize + mapping.offset;
+    if !start.is_multiple_of(mem::align_of::<T>()) {
+        return Err(Error::Unmappable("elements are misaligned"));
+    }
+    Ok((mapping, lens))
+}
+
+/// Create or truncate a file with zeroed elements, and map it.
+///
+/// # Safety
+///
+/// The file must not be modified except through the mapping while
+/// the mapping exists.
+pub(crate) unsafe fn create<T: Pod>(path: &Path, lens: &[usize]) -> Result<Mapping<ReadWrite>, Error> {
+    let len = file_len::<T>(lens)?;
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    binary::write_header(&mut file, &Header {
+        version: binary::VERSION,
+        compression: Compression::None,
+        element_size: mem::size_of::<T>() as u32,
+        bounds: lens.iter().map(|&len| 0..len as i64).collect(),
+    })?;
+    file.set_len(len)?;
+    map::<T, ReadWrite>(path, lens.len()).map(|(mapping, _)| mapping)
+}
+
+/// Memory-mapped file implementation of Grid2.
+///
+/// Grids opened with `open` are `ReadOnly`, and only those created
+/// with `open_mut` or `create` are `ReadWrite`, and can be written.
+pub struct MmapGrid2<T, S = i32, A: Access = ReadOnly> {
+    mapping: Mapping<A>,
+    x_len: S,
+    y_len: S,
+    p: PhantomData<T>,
+}
+
+impl<T: Pod, S: Scalar, A: Access> MmapGrid2<T, S, A> {
+    fn from_mapping(mapping: Mapping<A>, lens: &[usize]) -> Result<Self, Error> {
+        let lens = scalar_lens(lens)?;
+        Ok(MmapGrid2 {
+            mapping,
+            x_len: lens[0],
+            y_len: lens[1],
+            p: PhantomData,
+        })
+    }
+    
+    fn len(&self) -> usize {
+        self.x_len.to_usize() * self.y_len.to_usize()
+    }
+    
+    fn inner_index(&self, x: S, y: S) -> Option<usize> {
+        if x < S::ZERO || x >= self.x_len || y < S::ZERO || y >= self.y_len {
+            None
+        } else {
+            Some(y.to_usize() * self.x_len.to_usize() + x.to_usize())
+        }
+    }
+}
+
+impl<T: Pod, S: Scalar> MmapGrid2<T, S, ReadOnly> {
+    /// Map a file read-only.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified while this grid exists.
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let (mapping, lens) = map::<T, ReadOnly>(path.as_ref(), 2)?;
+        Self::from_mapping(mapping, &lens)
+    }
+}
+
+impl<T: Pod, S: Scalar> MmapGrid2<T, S, ReadWrite> {
+    /// Map a file read-write.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified except through this grid while
+    /// this grid exists.
+    pub unsafe fn open_mut<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let (mapping, lens) = map::<T, ReadWrite>(path.as_ref(), 2)?;
+        Self::from_mapping(mapping, &lens)
+    }
+    
+    /// Create or truncate a file of zeroed elements, and map it
+    /// read-write.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified except through this grid while
+    /// this grid exists.
+    pub unsafe fn create<P: AsRef<Path>>(path: P, x_len: S, y_len: S) -> Result<Self, Error> {
+        assert!(x_len >= S::ZERO);
+        assert!(y_len >= S::ZERO);
+        
+        let lens = [x_len.to_usize(), y_len.to_usize()];
+        let mapping = create::<T>(path.as_ref(), &lens)?;
+        Self::from_mapping(mapping, &lens)
+    }
+    
+    /// Write modified elements back to the file.
+    pub fn flush(&self) -> io::Result<()> {
+        self.mapping.flush()
+    }
+}
+
+impl<T: Pod, S: Scalar, A: Access> Grid2<S> for MmapGrid2<T, S, A> {
+    type Item = T;
+    type XBound = Range0To<S>;
+    type YBound = Range0To<S>;
+    
+    fn x_bound(&self) -> Range0To<S> {
+        Range0To { end: self.x_len }
+    }
+    
+    fn y_bound(&self) -> Range0To<S> {
+        Range0To { end: self.y_len }
+    }
+}
+
+impl<T: Pod, S: Scalar, A: Access> Grid2Len<S> for MmapGrid2<T, S, A> {}
+
+impl<T: Pod, S: Scalar, A: Access> Grid2Ref<S> for MmapGrid2<T, S, A> {
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = coord.into();
+        let Vector2 { x, y } = coord;
+        let option = self
+            .inner_index(x, y)
+            .map(|i| &self.mapping.elements::<T>(self.len())[i]);
+        match option {
+            Some(o) => o,
+            None => panic!("invalid index {:?}", coord),
+        }
+    }
+}
+
+impl<T: Pod, S: Scalar> Grid2Mut<S> for MmapGrid2<T, S, ReadWrite> {
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = coord.into();
+        let Vector2 { x, y } = coord;
+        let len = self.len();
+        match self.inner_index(x, y) {
+            Some(i) => &mut self.mapping.elements_mut::<T>(len)[i],
+            None => panic!("invalid index {:?}", coord),
+        }
+    }
+}
+
+impl<T: Pod, S: Scalar, A: Access> Grid2Get<S> for MmapGrid2<T, S, A> {
+    fn get<I: Into<Vector2<S>>>(&self, coord: I) -> Self::Item 
+    { *self.idx(coord) }
+}
+
+impl<T: Pod, S: Scalar> Grid2Set<S> for MmapGrid2<T, S, ReadWrite> {
+    fn set<I: Into<Vector2<S>>>(&mut self, coord: I, elem: Self::Item) 
+    { *self.midx(coord) = elem; }
+}
@@ -4,4 +4,6 @@ pub mod kolmo;
 pub mod kolmoref;
 pub mod kolmomut;
 pub mod kolmorw;
-pub mod inline3x3;
\ No newline at end of file
+pub mod inline3x3;
+#[cfg(feature = "mmap")]
+pub mod mmap;
@@ -0,0 +1,151 @@
+//! Block-reducing downscaling.
+
+use crate::{
+    scalar::Scalar,
+    grid2::*,
+    range::RangeBoundsDiv,
+};
+use mint::Vector2;
+use std::marker::PhantomData;
+
+/// Each block of factor.x by factor.y elements in the inner
+/// grid is folded into one element of this grid.
+///
+/// Blocks which overlap the edge of the inner grid only
+/// contain the elements within its bounds.
+pub struct Grid2Downscale<G, F, T, S = i32> {
+    inner: G,
+    func: F,
+    factor: Vector2<S>,
+    p: PhantomData<fn(T)>
+}
+
+impl<G, F, T, S> Grid2Downscale<G, F, T, S>
+where
+    S: Scalar,
+{
+    /// Panics if either factor is not positive.
+    pub fn new<I>(inner: G, factor: I, func: F) -> Self 
+    where
+        I: Into<Vector2<S>>,
+    {
+        let factor = factor.into();
+        assert!(factor.x > S::ZERO && factor.y > S::ZERO, "invalid factor {:?}", factor);
+        Grid2Downscale {
+            inner,
+            func,
+            factor,
+            p: PhantomData,
+        }
+    }
+    
+    pub fn factor<I>(&self) -> I
+    where
+        I: From<Vector2<S>> 
+    {
+        I::from(self.factor)
+    }
+}
+
+impl<G, F, T, S> Grid2<S> for Grid2Downscale<G, F, T, S>
+where
+    G: Grid2<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsDiv<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsDiv<S>,
+    F: Fn(Grid2Block<'_, G, S>) -> T,
+    S: Scalar,
+{
+    type Item = T;
+    type XBound = <G as Grid2<S>>::XBound;
+    type YBound = <G as Grid2<S>>::YBound;
+    
+    fn x_bound(&self) -> Self::XBound { self.inner.x_bound().div(self.factor.x) }
+    fn y_bound(&self) -> Self::YBound { self.inner.y_bound().div(self.factor.y) }
+}
+
+impl<G, F, T, S> Grid2Len<S> for Grid2Downscale<G, F, T, S>
+where
+    G: Grid2Len<S>,
+    F: Fn(Grid2Block<'_, G, S>) -> T,
+    S: Scalar,
+{}
+
+impl<G, F, T, S> Grid2Get<S> for Grid2Downscale<G, F, T, S>
+where
+    G: Grid2<S> + Grid2Get<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsDiv<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsDiv<S>,
+    F: Fn(Grid2Block<'_, G, S>) -> T,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = coord.into();
+        if !self.in_bounds(coord) {
+            panic!("invalid index {:?}", coord);
+        }
+        let Vector2 { x, y } = coord;
+        let start = Vector2 {
+            x: x * self.factor.x,
+            y: y * self.factor.y,
+        };
+        (self.func)(Grid2Block {
+            inner: &self.inner,
+            start,
+            factor: self.factor,
+            next: Vector2 { x: S::ZERO, y: S::ZERO },
+        })
+    }
+}
+
+/// Iterator over the elements of one block of a grid being
+/// downscaled, in row-major order.
+pub struct Grid2Block<'a, G, S = i32> {
+    inner: &'a G,
+    start: Vector2<S>,
+    factor: Vector2<S>,
+    next: Vector2<S>,
+}
+
+impl<'a, G, S> Grid2Block<'a, G, S>
+where
+    S: Scalar,
+{
+    /// Coordinate of the block's first element in the inner grid.
+    pub fn start<I>(&self) -> I
+    where
+        I: From<Vector2<S>> 
+    {
+        I::from(self.start)
+    }
+}
+
+impl<'a, G, S> Iterator for Grid2Block<'a, G, S>
+where
+    G: Grid2Get<S>,
+    S: Scalar,
+{
+    type Item = <G as Grid2<S>>::Item;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next.y < self.factor.y {
+            let coord = Vector2 {
+                x: self.start.x + self.next.x,
+                y: self.start.y + self.next.y,
+            };
+            
+            self.next.x = self.next.x + S::ONE;
+            if self.next.x == self.factor.x {
+                self.next.x = S::ZERO;
+                self.next.y = self.next.y + S::ONE;
+            }
+            
+            if self.inner.in_bounds(coord) {
+                return Some(self.inner.get(coord));
+            }
+        }
+        None
+    }
+}
@@ -1,26 +1,19 @@
 //! Map-with index, aka. enumap, because it's like enumerate + map.
 
-use crate::grid2::*;
+use crate::{
+    scalar::Scalar,
+    grid2::*,
+};
 use mint::Vector2;
 use std::marker::PhantomData;
 
-pub struct Grid2EnuMap<G, F, T, I> 
-where
-    G: Grid2,
-    I: From<Vector2<i32>>,
-    F: Fn(I, <G as Grid2>::Item) -> T,
-{
+pub struct Grid2EnuMap<G, F, T, I> {
     inner: G,
     func: F,
     p: PhantomData<fn(T, I)>
 }
 
-impl<G, F, T, I> Grid2EnuMap<G, F, T, I>
-where
-    G: Grid2,
-    I: From<Vector2<i32>>,
-    F: Fn(I, <G as Grid2>::Item) -> T,
-{
+impl<G, F, T, I> Grid2EnuMap<G, F, T, I> {
     pub fn new(inner: G, func: F) -> Self {
         Grid2EnuMap {
             inner,
@@ -30,36 +23,39 @@ where
     }
 }
 
-impl<G, F, T, I> Grid2 for Grid2EnuMap<G, F, T, I>
+impl<G, F, T, I, S> Grid2<S> for Grid2EnuMap<G, F, T, I>
 where
-    G: Grid2,
-    I: From<Vector2<i32>>,
-    F: Fn(I, <G as Grid2>::Item) -> T,
+    G: Grid2<S>,
+    I: From<Vector2<S>>,
+    F: Fn(I, <G as Grid2<S>>::Item) -> T,
+    S: Scalar,
 {
     type Item = T;
-    type XBound = <G as Grid2>::XBound;
-    type YBound = <G as Grid2>::YBound;
+    type XBound = <G as Grid2<S>>::XBound;
+    type YBound = <G as Grid2<S>>::YBound;
     
     fn x_bound(&self) -> Self::XBound { self.inner.x_bound() }
     fn y_bound(&self) -> Self::YBound { self.inner.y_bound() }
 }
 
-impl<G, F, T, I> Grid2Len for Grid2EnuMap<G, F, T, I>
+impl<G, F, T, I, S> Grid2Len<S> for Grid2EnuMap<G, F, T, I>
 where
-    G: Grid2 + Grid2Len,
-    I: From<Vector2<i32>>,
-    F: Fn(I, <G as Grid2>::Item) -> T,
+    G: Grid2<S> + Grid2Len<S>,
+    I: From<Vector2<S>>,
+    F: Fn(I, <G as Grid2<S>>::Item) -> T,
+    S: Scalar,
 {}
 
-impl<G, F, T, I> Grid2Get for Grid2EnuMap<G, F, T, I>
+impl<G, F, T, I, S> Grid2Get<S> for Grid2EnuMap<G, F, T, I>
 where
-    G: Grid2 + Grid2Get,
-    I: From<Vector2<i32>>,
-    F: Fn(I, <G as Grid2>::Item) -> T,
+    G: Grid2<S> + Grid2Get<S>,
+    I: From<Vector2<S>>,
+    F: Fn(I, <G as Grid2<S>>::Item) -> T,
+    S: Scalar,
 {
     fn get<C>(&self, coord: C) -> Self::Item
     where
-        C: Into<Vector2<i32>>
+        C: Into<Vector2<S>>
     {
         let coord = coord.into();
         (self.func)(I::from(coord), self.inner.get(coord))
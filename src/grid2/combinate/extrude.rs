@@ -0,0 +1,129 @@
+//! Extrusion of a Grid2 into a Grid3.
+
+use crate::{
+    scalar::Scalar,
+    range::Range0To,
+    grid2::*,
+    grid3::{Grid3, Grid3Len, Grid3Get, Grid3Set, Grid3Ref, Grid3Mut},
+};
+use mint::{Vector2, Vector3};
+
+/// Grid2 repeated along the z axis, viewed as a Grid3.
+///
+/// Every z coordinate within the z bound aliases the same
+/// inner element, and z coordinates outside it are invalid.
+pub struct Grid2Extrude<G, Z> {
+    inner: G,
+    z_bound: Z,
+}
+
+impl<G, Z> Grid2Extrude<G, Z> {
+    pub fn new(inner: G, z_bound: Z) -> Self {
+        Grid2Extrude {
+            inner,
+            z_bound,
+        }
+    }
+    
+    pub fn into_inner(self) -> G {
+        self.inner
+    }
+}
+
+impl<G, Z, S> Grid3<S> for Grid2Extrude<G, Z>
+where
+    G: Grid2<S>,
+    Z: RangeBounds<S> + Clone,
+    S: Scalar,
+{
+    type Item = <G as Grid2<S>>::Item;
+    type XBound = <G as Grid2<S>>::XBound;
+    type YBound = <G as Grid2<S>>::YBound;
+    type ZBound = Z;
+    
+    fn x_bound(&self) -> Self::XBound { self.inner.x_bound() }
+    fn y_bound(&self) -> Self::YBound { self.inner.y_bound() }
+    fn z_bound(&self) -> Self::ZBound { self.z_bound.clone() }
+}
+
+impl<G, S> Grid3Len<S> for Grid2Extrude<G, Range0To<S>>
+where
+    G: Grid2Len<S>,
+    S: Scalar,
+{}
+
+impl<G, Z, S> Grid3Get<S> for Grid2Extrude<G, Z>
+where
+    G: Grid2<S> + Grid2Get<S>,
+    Z: RangeBounds<S> + Clone,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        if !self.z_bound.contains(&coord.z) {
+            panic!("invalid index {:?}", coord);
+        }
+        let Vector3 { x, y, .. } = coord;
+        self.inner.get(Vector2 { x, y })
+    }
+}
+
+impl<G, Z, S> Grid3Set<S> for Grid2Extrude<G, Z>
+where
+    G: Grid2<S> + Grid2Set<S>,
+    Z: RangeBounds<S> + Clone,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        if !self.z_bound.contains(&coord.z) {
+            panic!("invalid index {:?}", coord);
+        }
+        let Vector3 { x, y, .. } = coord;
+        self.inner.set(Vector2 { x, y }, elem);
+    }
+}
+
+impl<G, Z, S> Grid3Ref<S> for Grid2Extrude<G, Z>
+where
+    G: Grid2<S> + Grid2Ref<S>,
+    Z: RangeBounds<S> + Clone,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        if !self.z_bound.contains(&coord.z) {
+            panic!("invalid index {:?}", coord);
+        }
+        let Vector3 { x, y, .. } = coord;
+        self.inner.idx(Vector2 { x, y })
+    }
+}
+
+impl<G, Z, S> Grid3Mut<S> for Grid2Extrude<G, Z>
+where
+    G: Grid2<S> + Grid2Mut<S>,
+    Z: RangeBounds<S> + Clone,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        if !self.z_bound.contains(&coord.z) {
+            panic!("invalid index {:?}", coord);
+        }
+        let Vector3 { x, y, .. } = coord;
+        self.inner.midx(Vector2 { x, y })
+    }
+}
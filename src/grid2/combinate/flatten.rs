@@ -1,6 +1,7 @@
 //! Flattening a Grid2 of Grid2.
 
 use crate::{
+    scalar::Scalar,
     grid2::*,
     range::RangeBoundsTimes,
 };
@@ -11,31 +12,33 @@ use mint::Vector2;
 /// This unfortunately cannot detect ahead-of-time bugs
 /// if the inner grid has length less than the stride, 
 /// so panics will probably occur if that happens.
-pub struct Grid2Flat<G>
+pub struct Grid2Flat<G, S = i32>
 where
-    G: Grid2,
-    <G as Grid2>::Item: Grid2,
-    <G as Grid2>::XBound: Clone,
-    <G as Grid2>::YBound: Clone,
+    G: Grid2<S>,
+    <G as Grid2<S>>::Item: Grid2<S>,
+    <G as Grid2<S>>::XBound: Clone,
+    <G as Grid2<S>>::YBound: Clone,
+    S: Scalar,
 {
     doublegrid: G,
-    stride: Vector2<i32>,
-    new_xbound: <G as Grid2>::XBound,
-    new_ybound: <G as Grid2>::YBound,
+    stride: Vector2<S>,
+    new_xbound: <G as Grid2<S>>::XBound,
+    new_ybound: <G as Grid2<S>>::YBound,
 }
 
-impl<G> Grid2Flat<G>
+impl<G, S> Grid2Flat<G, S>
 where
-    G: Grid2,
-    <G as Grid2>::Item: Grid2,
-    <G as Grid2>::XBound: Clone,
-    <G as Grid2>::YBound: Clone,
+    G: Grid2<S>,
+    <G as Grid2<S>>::Item: Grid2<S>,
+    <G as Grid2<S>>::XBound: Clone,
+    <G as Grid2<S>>::YBound: Clone,
+    S: Scalar,
 {
     pub fn new<I>(doublegrid: G, stride: I) -> Self 
     where
-        I: Into<Vector2<i32>>,
-        <G as Grid2>::XBound: RangeBoundsTimes,
-        <G as Grid2>::YBound: RangeBoundsTimes,
+        I: Into<Vector2<S>>,
+        <G as Grid2<S>>::XBound: RangeBoundsTimes<S>,
+        <G as Grid2<S>>::YBound: RangeBoundsTimes<S>,
     {
         let stride = stride.into();
         let new_xbound = doublegrid.x_bound().times(stride.x);
@@ -50,25 +53,25 @@ where
     
     pub fn stride<I>(&self) -> I
     where
-        I: From<Vector2<i32>> 
+        I: From<Vector2<S>> 
     {
         I::from(self.stride)
     }
     
     pub fn outer_inner_coord<I>(&self, coord: I) -> (I, I)
     where
-        I: Into<Vector2<i32>> + From<Vector2<i32>>
+        I: Into<Vector2<S>> + From<Vector2<S>>
     {
         let Vector2 { x, y } = coord.into();
 
         let stride_x = self.stride.x;
         let stride_y = self.stride.y;
         
-        let rem_x = ((x % stride_x) + stride_x) % stride_x;
-        let rem_y = ((y % stride_y) + stride_y) % stride_y;
+        let rem_x = x.rem_euclid(stride_x);
+        let rem_y = y.rem_euclid(stride_y);
         
-        let div_x = (x - rem_x) / stride_x;
-        let div_y = (y - rem_y) / stride_y;
+        let div_x = x.div_euclid(stride_x);
+        let div_y = y.div_euclid(stride_y);
         
         let rem = Vector2 { x: rem_x, y: rem_y };
         let div = Vector2 { x: div_x, y: div_y };
@@ -77,79 +80,84 @@ where
     }
 }
 
-impl<G> Grid2 for Grid2Flat<G>
+impl<G, S> Grid2<S> for Grid2Flat<G, S>
 where
-    G: Grid2,
-    <G as Grid2>::Item: Grid2,
-    <G as Grid2>::XBound: Clone,
-    <G as Grid2>::YBound: Clone,
+    G: Grid2<S>,
+    <G as Grid2<S>>::Item: Grid2<S>,
+    <G as Grid2<S>>::XBound: Clone,
+    <G as Grid2<S>>::YBound: Clone,
+    S: Scalar,
 {
-    type Item = <<G as Grid2>::Item as Grid2>::Item;
-    type XBound = <G as Grid2>::XBound;
-    type YBound = <G as Grid2>::YBound;
+    type Item = <<G as Grid2<S>>::Item as Grid2<S>>::Item;
+    type XBound = <G as Grid2<S>>::XBound;
+    type YBound = <G as Grid2<S>>::YBound;
     
-    fn x_bound(&self) -> <G as Grid2>::XBound { self.new_xbound.clone() }
-    fn y_bound(&self) -> <G as Grid2>::YBound { self.new_ybound.clone() }
+    fn x_bound(&self) -> <G as Grid2<S>>::XBound { self.new_xbound.clone() }
+    fn y_bound(&self) -> <G as Grid2<S>>::YBound { self.new_ybound.clone() }
 }
 
-impl<G> Grid2Get for Grid2Flat<G>
+impl<G, S> Grid2Get<S> for Grid2Flat<G, S>
 where
-    G: Grid2 + Grid2Ref,
-    <G as Grid2>::Item: Grid2 + Grid2Get,
-    <G as Grid2>::XBound: Clone,
-    <G as Grid2>::YBound: Clone,
+    G: Grid2<S> + Grid2Ref<S>,
+    <G as Grid2<S>>::Item: Grid2<S> + Grid2Get<S>,
+    <G as Grid2<S>>::XBound: Clone,
+    <G as Grid2<S>>::YBound: Clone,
+    S: Scalar,
 {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let (outer, inner) = self.outer_inner_coord(coord.into());
         self.doublegrid.idx(outer).get(inner)
     }
 }
 
-impl<G> Grid2Set for Grid2Flat<G>
+impl<G, S> Grid2Set<S> for Grid2Flat<G, S>
 where
-    G: Grid2 + Grid2Mut,
-    <G as Grid2>::Item: Grid2 + Grid2Set,
-    <G as Grid2>::XBound: Clone,
-    <G as Grid2>::YBound: Clone,
+    G: Grid2<S> + Grid2Mut<S>,
+    <G as Grid2<S>>::Item: Grid2<S> + Grid2Set<S>,
+    <G as Grid2<S>>::XBound: Clone,
+    <G as Grid2<S>>::YBound: Clone,
+    S: Scalar,
 {
     fn set<I>(&mut self, coord: I, elem: Self::Item)
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let (outer, inner) = self.outer_inner_coord(coord.into());
         self.doublegrid.midx(outer).set(inner, elem);
     }
 }
 
-impl<G> Grid2Ref for Grid2Flat<G>
+impl<G, S> Grid2Ref<S> for Grid2Flat<G, S>
 where
-    G: Grid2 + Grid2Ref,
-    <G as Grid2>::Item: Grid2 + Grid2Ref,
-    <G as Grid2>::XBound: Clone,
-    <G as Grid2>::YBound: Clone,
+    G: Grid2<S> + Grid2Ref<S>,
+    <G as Grid2<S>>::Item: Grid2<S> + Grid2Ref<S>,
+    <G as Grid2<S>>::XBound: Clone,
+    <G as Grid2<S>>::YBound: Clone,
+    S: Scalar,
 {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let (outer, inner) = self.outer_inner_coord(coord.into());
         self.doublegrid.idx(outer).idx(inner)
     }
 }
 
-impl<G> Grid2Mut for Grid2Flat<G>
+impl<G, S> Grid2Mut<S> for Grid2Flat<G, S>
 where
-    G: Grid2 + Grid2Mut,
-    <G as Grid2>::Item: Grid2 + Grid2Mut,
-    <G as Grid2>::XBound: Clone,
-    <G as Grid2>::YBound: Clone,
+    G: Grid2<S> + Grid2Mut<S>,
+    <G as Grid2<S>>::Item: Grid2<S> + Grid2Mut<S>,
+    <G as Grid2<S>>::XBound: Clone,
+    <G as Grid2<S>>::YBound: Clone,
+    S: Scalar,
 {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let (outer, inner) = self.outer_inner_coord(coord.into());
         self.doublegrid.midx(outer).midx(inner)
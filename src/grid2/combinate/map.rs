@@ -1,24 +1,19 @@
 //! Dependent by-value Grid2 mapping.
 
-use crate::grid2::*;
+use crate::{
+    scalar::Scalar,
+    grid2::*,
+};
 use mint::Vector2;
 use std::marker::PhantomData;
 
-pub struct Grid2Map<G, F, T> 
-where
-    G: Grid2,
-    F: Fn(<G as Grid2>::Item) -> T,
-{
+pub struct Grid2Map<G, F, T> {
     inner: G,
     func: F,
     p: PhantomData<fn(T)>
 }
 
-impl<G, F, T> Grid2Map<G, F, T>
-where
-    G: Grid2,
-    F: Fn(<G as Grid2>::Item) -> T,
-{
+impl<G, F, T> Grid2Map<G, F, T> {
     pub fn new(inner: G, func: F) -> Self {
         Grid2Map {
             inner,
@@ -28,33 +23,36 @@ where
     }
 }
 
-impl<G, F, T> Grid2 for Grid2Map<G, F, T>
+impl<G, F, T, S> Grid2<S> for Grid2Map<G, F, T>
 where
-    G: Grid2,
-    F: Fn(<G as Grid2>::Item) -> T,
+    G: Grid2<S>,
+    F: Fn(<G as Grid2<S>>::Item) -> T,
+    S: Scalar,
 {
     type Item = T;
-    type XBound = <G as Grid2>::XBound;
-    type YBound = <G as Grid2>::YBound;
+    type XBound = <G as Grid2<S>>::XBound;
+    type YBound = <G as Grid2<S>>::YBound;
     
     fn x_bound(&self) -> Self::XBound { self.inner.x_bound() }
     fn y_bound(&self) -> Self::YBound { self.inner.y_bound() }
 }
 
-impl<G, F, T> Grid2Len for Grid2Map<G, F, T>
+impl<G, F, T, S> Grid2Len<S> for Grid2Map<G, F, T>
 where
-    G: Grid2 + Grid2Len,
-    F: Fn(<G as Grid2>::Item) -> T,
+    G: Grid2<S> + Grid2Len<S>,
+    F: Fn(<G as Grid2<S>>::Item) -> T,
+    S: Scalar,
 {}
 
-impl<G, F, T> Grid2Get for Grid2Map<G, F, T>
+impl<G, F, T, S> Grid2Get<S> for Grid2Map<G, F, T>
 where
-    G: Grid2 + Grid2Get,
-    F: Fn(<G as Grid2>::Item) -> T,
+    G: Grid2<S> + Grid2Get<S>,
+    F: Fn(<G as Grid2<S>>::Item) -> T,
+    S: Scalar,
 {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         (self.func)(self.inner.get(coord))
     }
@@ -6,8 +6,15 @@ pub mod wrapping;
 pub mod neworigin;
 pub mod flatten;
 pub mod oobhandler;
+pub mod extrude;
+pub mod stack;
+pub mod upscale;
+pub mod downscale;
+pub mod tracked;
+pub mod journaled;
 
 use super::*;
+use crate::scalar::Scalar;
 use mint::Vector2;
 use std::{
     ops::{Deref, DerefMut},
@@ -15,14 +22,15 @@ use std::{
 
 // ==== elevate pointer types ====
 
-impl<T> Grid2 for T
+impl<T, S> Grid2<S> for T
 where
     T: Deref,
-    <T as Deref>::Target: Grid2
+    <T as Deref>::Target: Grid2<S>,
+    S: Scalar,
 {
-    type Item = <<T as Deref>::Target as Grid2>::Item;
-    type XBound = <<T as Deref>::Target as Grid2>::XBound;
-    type YBound = <<T as Deref>::Target as Grid2>::YBound;
+    type Item = <<T as Deref>::Target as Grid2<S>>::Item;
+    type XBound = <<T as Deref>::Target as Grid2<S>>::XBound;
+    type YBound = <<T as Deref>::Target as Grid2<S>>::YBound;
     
     fn x_bound(&self) -> Self::XBound {
         T::deref(self).x_bound()
@@ -33,59 +41,64 @@ where
     }
 }
 
-impl<T> Grid2Len for T
+impl<T, S> Grid2Len<S> for T
 where
     T: Deref,
-    <T as Deref>::Target: Grid2Len
+    <T as Deref>::Target: Grid2Len<S>,
+    S: Scalar,
 {}
 
-impl<T> Grid2Get for T
+impl<T, S> Grid2Get<S> for T
 where
     T: Deref,
-    <T as Deref>::Target: Grid2Get
+    <T as Deref>::Target: Grid2Get<S>,
+    S: Scalar,
 {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         T::deref(self).get(coord)
     }
 }
 
-impl<T> Grid2Ref for T
+impl<T, S> Grid2Ref<S> for T
 where
     T: Deref,
-    <T as Deref>::Target: Grid2Ref
+    <T as Deref>::Target: Grid2Ref<S>,
+    S: Scalar,
 {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         T::deref(self).idx(coord)
     }
 }
 
-impl<T> Grid2Set for T
+impl<T, S> Grid2Set<S> for T
 where
     T: Deref + DerefMut,
-    <T as Deref>::Target: Grid2Set
+    <T as Deref>::Target: Grid2Set<S>,
+    S: Scalar,
 {
     fn set<I>(&mut self, coord: I, elem: Self::Item)
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         T::deref_mut(self).set(coord, elem)
     }
 }
 
-impl<T> Grid2Mut for T
+impl<T, S> Grid2Mut<S> for T
 where
     T: Deref + DerefMut,
-    <T as Deref>::Target: Grid2Mut
+    <T as Deref>::Target: Grid2Mut<S>,
+    S: Scalar,
 {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         T::deref_mut(self).midx(coord)
     }
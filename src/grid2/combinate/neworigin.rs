@@ -1,35 +1,38 @@
 //! Change to origin.
 
 use crate::{
+    scalar::Scalar,
     grid2::*,
     range::RangeBoundsPlus,
 };
 use mint::Vector2;
 
 /// <0, 0> in the inner grid will be new_origin in this grid.
-pub struct Grid2NewOrigin<G> 
+pub struct Grid2NewOrigin<G, S = i32> 
 where
-    G: Grid2,
-    <G as Grid2>::XBound: RangeBoundsPlus,
-    <G as Grid2>::YBound: RangeBoundsPlus,
+    G: Grid2<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
     inner: G,
-    new_origin: Vector2<i32>,
+    new_origin: Vector2<S>,
     
     // cache these (arbitrary decision)
-    new_xbound: <<G as Grid2>::XBound as RangeBoundsPlus>::Output,
-    new_ybound: <<G as Grid2>::YBound as RangeBoundsPlus>::Output,
+    new_xbound: <<G as Grid2<S>>::XBound as RangeBoundsPlus<S>>::Output,
+    new_ybound: <<G as Grid2<S>>::YBound as RangeBoundsPlus<S>>::Output,
 }
 
-impl<G> Grid2NewOrigin<G> 
+impl<G, S> Grid2NewOrigin<G, S> 
 where
-    G: Grid2,
-    <G as Grid2>::XBound: RangeBoundsPlus,
-    <G as Grid2>::YBound: RangeBoundsPlus,
+    G: Grid2<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
     pub fn new<I>(inner: G, new_origin: I) -> Self 
     where
-        I: Into<Vector2<i32>>,
+        I: Into<Vector2<S>>,
     {
         let new_origin = new_origin.into();
         let new_xbound = inner.x_bound().plus(new_origin.x);
@@ -45,90 +48,95 @@ where
     
     pub fn new_origin<I>(&self) -> I 
     where
-        I: From<Vector2<i32>>
+        I: From<Vector2<S>>
     {
         I::from(self.new_origin)
     }
     
     pub fn adjust_coord<I>(&self, coord: I) -> I 
     where
-        I: From<Vector2<i32>> + Into<Vector2<i32>>
+        I: From<Vector2<S>> + Into<Vector2<S>>
     {
         let mut coord = coord.into();
-        coord.x -= self.new_origin.x;
-        coord.y -= self.new_origin.y;
+        coord.x = coord.x - self.new_origin.x;
+        coord.y = coord.y - self.new_origin.y;
         I::from(coord)
     }
 }
 
-impl<G> Grid2 for Grid2NewOrigin<G> 
+impl<G, S> Grid2<S> for Grid2NewOrigin<G, S> 
 where
-    G: Grid2,
-    <G as Grid2>::XBound: RangeBoundsPlus,
-    <G as Grid2>::YBound: RangeBoundsPlus,
+    G: Grid2<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
-    type Item = <G as Grid2>::Item;
-    type XBound = <<G as Grid2>::XBound as RangeBoundsPlus>::Output;
-    type YBound = <<G as Grid2>::YBound as RangeBoundsPlus>::Output;
+    type Item = <G as Grid2<S>>::Item;
+    type XBound = <<G as Grid2<S>>::XBound as RangeBoundsPlus<S>>::Output;
+    type YBound = <<G as Grid2<S>>::YBound as RangeBoundsPlus<S>>::Output;
     
     fn x_bound(&self) -> Self::XBound { self.new_xbound.clone() }
     fn y_bound(&self) -> Self::YBound { self.new_ybound.clone() }
 }
 
-impl<G> Grid2Get for Grid2NewOrigin<G> 
+impl<G, S> Grid2Get<S> for Grid2NewOrigin<G, S> 
 where
-    G: Grid2 + Grid2Get,
-    <G as Grid2>::XBound: RangeBoundsPlus,
-    <G as Grid2>::YBound: RangeBoundsPlus,
+    G: Grid2<S> + Grid2Get<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = self.adjust_coord(coord.into());
         self.inner.get(coord)
     }
 }
 
-impl<G> Grid2Set for Grid2NewOrigin<G> 
+impl<G, S> Grid2Set<S> for Grid2NewOrigin<G, S> 
 where
-    G: Grid2 + Grid2Set,
-    <G as Grid2>::XBound: RangeBoundsPlus,
-    <G as Grid2>::YBound: RangeBoundsPlus,
+    G: Grid2<S> + Grid2Set<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
     fn set<I>(&mut self, coord: I, elem: Self::Item)
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = self.adjust_coord(coord.into());
         self.inner.set(coord, elem);
     }
 }
 
-impl<G> Grid2Ref for Grid2NewOrigin<G> 
+impl<G, S> Grid2Ref<S> for Grid2NewOrigin<G, S> 
 where
-    G: Grid2 + Grid2Ref,
-    <G as Grid2>::XBound: RangeBoundsPlus,
-    <G as Grid2>::YBound: RangeBoundsPlus,
+    G: Grid2<S> + Grid2Ref<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = self.adjust_coord(coord.into());
         self.inner.idx(coord)
     }
 }
 
-impl<G> Grid2Mut for Grid2NewOrigin<G> 
+impl<G, S> Grid2Mut<S> for Grid2NewOrigin<G, S> 
 where
-    G: Grid2 + Grid2Mut,
-    <G as Grid2>::XBound: RangeBoundsPlus,
-    <G as Grid2>::YBound: RangeBoundsPlus,
+    G: Grid2<S> + Grid2Mut<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = self.adjust_coord(coord.into());
         self.inner.midx(coord)
@@ -1,6 +1,9 @@
 //! Out-of-bounds index handler.
 
-use crate::grid2::*;
+use crate::{
+    scalar::Scalar,
+    grid2::*,
+};
 use mint::Vector2;
 use std::{
     ops::RangeFull,
@@ -8,23 +11,13 @@ use std::{
 };
 
 /// All values outside of a grid are supplied by a function.
-pub struct Grid2OobHandler<G, I, F> 
-where
-    G: Grid2,
-    I: From<Vector2<i32>>,
-    F: Fn(I) -> <G as Grid2>::Item,
-{
+pub struct Grid2OobHandler<G, I, F> {
     inner: G,
     func: F,
     p: PhantomData<fn(I)>,
 }
 
-impl<G, I, F> Grid2OobHandler<G, I, F>
-where
-    G: Grid2,
-    I: From<Vector2<i32>>,
-    F: Fn(I) -> <G as Grid2>::Item,
-{
+impl<G, I, F> Grid2OobHandler<G, I, F> {
     pub fn new(inner: G, func: F) -> Self {
         Grid2OobHandler {
             inner,
@@ -34,13 +27,14 @@ where
     }
 }
 
-impl<G, I, F> Grid2 for Grid2OobHandler<G, I, F>
+impl<G, I, F, S> Grid2<S> for Grid2OobHandler<G, I, F>
 where
-    G: Grid2,
-    I: From<Vector2<i32>>,
-    F: Fn(I) -> <G as Grid2>::Item,
+    G: Grid2<S>,
+    I: From<Vector2<S>>,
+    F: Fn(I) -> <G as Grid2<S>>::Item,
+    S: Scalar,
 {
-    type Item = <G as Grid2>::Item;
+    type Item = <G as Grid2<S>>::Item;
     type XBound = RangeFull;
     type YBound = RangeFull;
     
@@ -48,15 +42,16 @@ where
     fn y_bound(&self) -> RangeFull { RangeFull }
 }
 
-impl<G, I, F> Grid2Get for Grid2OobHandler<G, I, F>
+impl<G, I, F, S> Grid2Get<S> for Grid2OobHandler<G, I, F>
 where
-    G: Grid2 + Grid2Get,
-    I: From<Vector2<i32>>,
-    F: Fn(I) -> <G as Grid2>::Item,
+    G: Grid2<S> + Grid2Get<S>,
+    I: From<Vector2<S>>,
+    F: Fn(I) -> <G as Grid2<S>>::Item,
+    S: Scalar,
 {
     fn get<C>(&self, coord: C) -> Self::Item
     where
-        C: Into<Vector2<i32>>
+        C: Into<Vector2<S>>
     {
         let coord = coord.into();
         if self.inner.in_bounds(coord) {
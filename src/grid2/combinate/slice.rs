@@ -1,12 +1,13 @@
 //! Sub-view of a Grid2.
 
 use crate::{
-    range::Range0To,
+    range::{Range0To, more_strict},
+    scalar::Scalar,
     grid2::*,
 };
 use mint::Vector2;
 use std::{
-    ops::{RangeBounds, Bound},
+    ops::RangeBounds,
     fmt::Debug,
 };
 
@@ -17,67 +18,26 @@ use std::{
 /// of the valid coordinates in the inner grid.
 pub struct Grid2Slice<G, X, Y> 
 where
-    G: Grid2,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
+    X: Clone,
+    Y: Clone,
 {
     inner: G,
     x_bound: X,
     y_bound: Y,
 }
 
-/// Verify that a is more strict than b.
-fn more_strict(a: impl RangeBounds<i32>, b: impl RangeBounds<i32>) -> bool {
-    
-    fn lower_inclusive(bound: Bound<&i32>) -> Option<i32> {
-        match bound {
-            Bound::Included(&i) => Some(i),
-            Bound::Excluded(&i) => Some(i + 1),
-            Bound::Unbounded   => None,
-        }
-    }
-    
-    fn upper_inclusive(bound: Bound<&i32>) -> Option<i32> {
-        match bound {
-            Bound::Included(&i) => Some(i),
-            Bound::Excluded(&i) => Some(i - 1),
-            Bound::Unbounded   => None,
-        }
-    }
-    
-    let lower_ok = match (
-        lower_inclusive(a.start_bound()), 
-        lower_inclusive(b.start_bound()),
-    ) {
-        (Some(i1), Some(i2)) => i1 >= i2,
-        (Some(_), None)      => true,
-        (None, Some(_))      => false,
-        (None, None)         => true,
-    };
-    if !lower_ok { return false; }
-    
-    let upper_ok = match (
-        upper_inclusive(a.end_bound()), 
-        upper_inclusive(b.end_bound()),
-    ) {
-        (Some(i1), Some(i2)) => i1 <= i2,
-        (Some(_), None)      => true,
-        (None, Some(_))      => false,
-        (None, None)         => true,
-    };
-    if !upper_ok { return false; }
-    
-    true
-}
-
 impl<G, X, Y> Grid2Slice<G, X, Y>
 where
-    G: Grid2,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
+    X: Clone,
+    Y: Clone,
 {
     /// Fails if the new bounds are not a subset of the old ones.
-    pub fn try_new(inner: G, new_x: X, new_y: Y) -> Result<Self, G>
+    pub fn try_new<S>(inner: G, new_x: X, new_y: Y) -> Result<Self, G>
+    where
+        G: Grid2<S>,
+        X: RangeBounds<S>,
+        Y: RangeBounds<S>,
+        S: Scalar,
     {
         if more_strict(new_x.clone(), inner.x_bound()) 
             && more_strict(new_y.clone(), inner.y_bound()) {
@@ -92,12 +52,14 @@ where
     }
     
     /// Panics if the new bounds are not a subset of the old ones.
-    pub fn new(inner: G, new_x: X, new_y: Y) -> Self
+    pub fn new<S>(inner: G, new_x: X, new_y: Y) -> Self
     where
-        X: Debug,
-        Y: Debug,
-        <G as Grid2>::XBound: Debug,
-        <G as Grid2>::YBound: Debug,
+        G: Grid2<S>,
+        X: RangeBounds<S> + Debug,
+        Y: RangeBounds<S> + Debug,
+        <G as Grid2<S>>::XBound: Debug,
+        <G as Grid2<S>>::YBound: Debug,
+        S: Scalar,
     {
         if more_strict(new_x.clone(), inner.x_bound()) 
             && more_strict(new_y.clone(), inner.y_bound()) {
@@ -114,13 +76,14 @@ where
     }
 }
 
-impl<G, X, Y> Grid2 for Grid2Slice<G, X, Y> 
+impl<G, X, Y, S> Grid2<S> for Grid2Slice<G, X, Y> 
 where
-    G: Grid2,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
+    G: Grid2<S>,
+    X: RangeBounds<S> + Clone,
+    Y: RangeBounds<S> + Clone,
+    S: Scalar,
 {
-    type Item = <G as Grid2>::Item;
+    type Item = <G as Grid2<S>>::Item;
     type XBound = X;
     type YBound = Y;
     
@@ -128,20 +91,22 @@ where
     fn y_bound(&self) -> Self::YBound { self.y_bound.clone() }
 }
 
-impl<G> Grid2Len for Grid2Slice<G, Range0To, Range0To> 
+impl<G, S> Grid2Len<S> for Grid2Slice<G, Range0To<S>, Range0To<S>> 
 where
-    G: Grid2
+    G: Grid2<S>,
+    S: Scalar,
 {}
 
-impl<G, X, Y> Grid2Get for Grid2Slice<G, X, Y>
+impl<G, X, Y, S> Grid2Get<S> for Grid2Slice<G, X, Y>
 where
-    G: Grid2 + Grid2Get,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
+    G: Grid2<S> + Grid2Get<S>,
+    X: RangeBounds<S> + Clone,
+    Y: RangeBounds<S> + Clone,
+    S: Scalar,
 {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         match self.try_get(coord) {
@@ -152,7 +117,7 @@ where
         
     fn try_get<I>(&self, coord: I) -> Option<Self::Item>
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
@@ -163,15 +128,16 @@ where
     }
 }
 
-impl<G, X, Y> Grid2Set for Grid2Slice<G, X, Y>
+impl<G, X, Y, S> Grid2Set<S> for Grid2Slice<G, X, Y>
 where
-    G: Grid2 + Grid2Set,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
+    G: Grid2<S> + Grid2Set<S>,
+    X: RangeBounds<S> + Clone,
+    Y: RangeBounds<S> + Clone,
+    S: Scalar,
 {
     fn set<I>(&mut self, coord: I, elem: Self::Item)
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         match self.try_set(coord, elem) {
@@ -182,27 +148,28 @@ where
         
     fn try_set<I>(&mut self, coord: I, elem: Self::Item) -> Result<(), Self::Item> 
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
             self.inner.set(coord, elem);
-            Ok({})
+            Ok(())
         } else {
             Err(elem)
         }
     }
 }
 
-impl<G, X, Y> Grid2Ref for Grid2Slice<G, X, Y>
+impl<G, X, Y, S> Grid2Ref<S> for Grid2Slice<G, X, Y>
 where
-    G: Grid2 + Grid2Ref,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
+    G: Grid2<S> + Grid2Ref<S>,
+    X: RangeBounds<S> + Clone,
+    Y: RangeBounds<S> + Clone,
+    S: Scalar,
 {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         match self.try_idx(coord) {
@@ -213,7 +180,7 @@ where
     
     fn try_idx<I>(&self, coord: I) -> Option<&Self::Item>
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
@@ -224,15 +191,16 @@ where
     }
 }
 
-impl<G, X, Y> Grid2Mut for Grid2Slice<G, X, Y>
+impl<G, X, Y, S> Grid2Mut<S> for Grid2Slice<G, X, Y>
 where
-    G: Grid2 + Grid2Mut,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
+    G: Grid2<S> + Grid2Mut<S>,
+    X: RangeBounds<S> + Clone,
+    Y: RangeBounds<S> + Clone,
+    S: Scalar,
 {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         match self.try_midx(coord) {
@@ -243,7 +211,7 @@ where
     
     fn try_midx<I>(&mut self, coord: I) -> Option<&mut Self::Item>
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
@@ -0,0 +1,141 @@
+//! Stacking of Grid2 layers into a Grid3.
+
+use crate::{
+    scalar::Scalar,
+    range::Range0To,
+    grid2::*,
+    grid3::{Grid3, Grid3Len, Grid3Get, Grid3Set, Grid3Ref, Grid3Mut},
+};
+use mint::{Vector2, Vector3};
+
+/// Sequence of Grid2 layers, viewed as a Grid3.
+///
+/// Layer n is the plane at z = n. The x and y bounds are
+/// those of the layers, which must all be the same.
+pub struct Grid2Stack<G> {
+    layers: Vec<G>,
+}
+
+impl<G> Grid2Stack<G> {
+    /// Panics if there are no layers, or if the layers'
+    /// bounds differ.
+    pub fn new<S>(layers: Vec<G>) -> Self
+    where
+        G: Grid2<S>,
+        S: Scalar,
+    {
+        let first = match layers.first() {
+            Some(first) => first,
+            None => panic!("cannot stack zero layers"),
+        };
+        let (x_bound, y_bound) = (first.x_bound(), first.y_bound());
+        for (z, layer) in layers.iter().enumerate() {
+            let (layer_x, layer_y) = (layer.x_bound(), layer.y_bound());
+            if layer_x.start_bound() != x_bound.start_bound()
+                || layer_x.end_bound() != x_bound.end_bound()
+                || layer_y.start_bound() != y_bound.start_bound()
+                || layer_y.end_bound() != y_bound.end_bound() {
+                panic!("layer {} bounds differ from layer 0", z);
+            }
+        }
+        
+        Grid2Stack {
+            layers,
+        }
+    }
+    
+    pub fn into_layers(self) -> Vec<G> {
+        self.layers
+    }
+    
+    fn layer<S: Scalar>(&self, coord: Vector3<S>) -> &G {
+        if coord.z < S::ZERO || coord.z.to_usize() >= self.layers.len() {
+            panic!("invalid index {:?}", coord);
+        }
+        &self.layers[coord.z.to_usize()]
+    }
+    
+    fn layer_mut<S: Scalar>(&mut self, coord: Vector3<S>) -> &mut G {
+        if coord.z < S::ZERO || coord.z.to_usize() >= self.layers.len() {
+            panic!("invalid index {:?}", coord);
+        }
+        &mut self.layers[coord.z.to_usize()]
+    }
+}
+
+impl<G, S> Grid3<S> for Grid2Stack<G>
+where
+    G: Grid2<S>,
+    S: Scalar,
+{
+    type Item = <G as Grid2<S>>::Item;
+    type XBound = <G as Grid2<S>>::XBound;
+    type YBound = <G as Grid2<S>>::YBound;
+    type ZBound = Range0To<S>;
+    
+    fn x_bound(&self) -> Self::XBound { self.layers[0].x_bound() }
+    fn y_bound(&self) -> Self::YBound { self.layers[0].y_bound() }
+    fn z_bound(&self) -> Self::ZBound { Range0To { end: S::from_usize(self.layers.len()) } }
+}
+
+impl<G, S> Grid3Len<S> for Grid2Stack<G>
+where
+    G: Grid2Len<S>,
+    S: Scalar,
+{}
+
+impl<G, S> Grid3Get<S> for Grid2Stack<G>
+where
+    G: Grid2<S> + Grid2Get<S>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        self.layer(coord).get(Vector2 { x: coord.x, y: coord.y })
+    }
+}
+
+impl<G, S> Grid3Set<S> for Grid2Stack<G>
+where
+    G: Grid2<S> + Grid2Set<S>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        self.layer_mut(coord).set(Vector2 { x: coord.x, y: coord.y }, elem);
+    }
+}
+
+impl<G, S> Grid3Ref<S> for Grid2Stack<G>
+where
+    G: Grid2<S> + Grid2Ref<S>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        self.layer(coord).idx(Vector2 { x: coord.x, y: coord.y })
+    }
+}
+
+impl<G, S> Grid3Mut<S> for Grid2Stack<G>
+where
+    G: Grid2<S> + Grid2Mut<S>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        self.layer_mut(coord).midx(Vector2 { x: coord.x, y: coord.y })
+    }
+}
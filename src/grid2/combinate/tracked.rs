@@ -0,0 +1,194 @@
+//! Change tracking.
+
+use crate::{
+    scalar::Scalar,
+    grid2::*,
+};
+use mint::Vector2;
+use std::{
+    collections::HashSet,
+    ops::Range,
+};
+
+/// Records the coordinates written through it.
+///
+/// Writes through `set` and `midx` (and their `try_` variants)
+/// mark their coordinate as dirty, whether or not the value
+/// actually changes. Reads are forwarded unchanged.
+pub struct Grid2Tracked<G, S = i32> {
+    inner: G,
+    dirty: HashSet<Vector2<S>>,
+    // bounding box of dirty, inclusive
+    min: Vector2<S>,
+    max: Vector2<S>,
+}
+
+impl<G, S> Grid2Tracked<G, S>
+where
+    S: Scalar,
+{
+    pub fn new(inner: G) -> Self {
+        Grid2Tracked {
+            inner,
+            dirty: HashSet::new(),
+            min: Vector2 { x: S::ZERO, y: S::ZERO },
+            max: Vector2 { x: S::ZERO, y: S::ZERO },
+        }
+    }
+    
+    pub fn inner(&self) -> &G {
+        &self.inner
+    }
+    
+    /// Mutable access to the inner grid, bypassing tracking.
+    pub fn inner_mut(&mut self) -> &mut G {
+        &mut self.inner
+    }
+    
+    pub fn into_inner(self) -> G {
+        self.inner
+    }
+    
+    fn mark(&mut self, coord: Vector2<S>) {
+        if self.dirty.is_empty() {
+            self.min = coord;
+            self.max = coord;
+        } else {
+            self.min.x = self.min.x.min(coord.x);
+            self.min.y = self.min.y.min(coord.y);
+            self.max.x = self.max.x.max(coord.x);
+            self.max.y = self.max.y.max(coord.y);
+        }
+        self.dirty.insert(coord);
+    }
+    
+    pub fn is_dirty<I>(&self, coord: I) -> bool
+    where
+        I: Into<Vector2<S>>
+    {
+        self.dirty.contains(&coord.into())
+    }
+    
+    /// Number of dirty coordinates.
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len()
+    }
+    
+    /// Smallest rectangle containing every dirty coordinate.
+    pub fn dirty_bounds(&self) -> Option<(Range<S>, Range<S>)> {
+        if self.dirty.is_empty() {
+            None
+        } else {
+            Some((
+                self.min.x..self.max.x + S::ONE,
+                self.min.y..self.max.y + S::ONE,
+            ))
+        }
+    }
+    
+    /// Sorted coordinates of the chunks containing dirty coordinates,
+    /// where chunk <i, j> spans from `<i, j> * chunk_size` inclusive
+    /// to `<i + 1, j + 1> * chunk_size` exclusive.
+    pub fn dirty_chunks<I>(&self, chunk_size: I) -> Vec<Vector2<S>>
+    where
+        I: Into<Vector2<S>>
+    {
+        let chunk_size = chunk_size.into();
+        let chunks: HashSet<Vector2<S>> = self.dirty.iter()
+            .map(|c| Vector2 {
+                x: c.x.div_euclid(chunk_size.x),
+                y: c.y.div_euclid(chunk_size.y),
+            })
+            .collect();
+        let mut chunks: Vec<Vector2<S>> = chunks.into_iter().collect();
+        chunks.sort_by_key(|c| (c.y, c.x));
+        chunks
+    }
+    
+    /// Take the dirty coordinates, sorted, and reset tracking.
+    pub fn drain_dirty(&mut self) -> Vec<Vector2<S>> {
+        let mut dirty: Vec<Vector2<S>> = self.dirty.drain().collect();
+        dirty.sort_by_key(|c| (c.y, c.x));
+        dirty
+    }
+    
+    /// Reset tracking.
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
+}
+
+impl<G, S> Grid2<S> for Grid2Tracked<G, S>
+where
+    G: Grid2<S>,
+    S: Scalar,
+{
+    type Item = <G as Grid2<S>>::Item;
+    type XBound = <G as Grid2<S>>::XBound;
+    type YBound = <G as Grid2<S>>::YBound;
+    
+    fn x_bound(&self) -> Self::XBound { self.inner.x_bound() }
+    fn y_bound(&self) -> Self::YBound { self.inner.y_bound() }
+}
+
+impl<G, S> Grid2Len<S> for Grid2Tracked<G, S>
+where
+    G: Grid2Len<S>,
+    S: Scalar,
+{}
+
+impl<G, S> Grid2Get<S> for Grid2Tracked<G, S>
+where
+    G: Grid2Get<S>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.get(coord)
+    }
+}
+
+impl<G, S> Grid2Set<S> for Grid2Tracked<G, S>
+where
+    G: Grid2Set<S>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = coord.into();
+        self.inner.set(coord, elem);
+        self.mark(coord);
+    }
+}
+
+impl<G, S> Grid2Ref<S> for Grid2Tracked<G, S>
+where
+    G: Grid2Ref<S>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.idx(coord)
+    }
+}
+
+impl<G, S> Grid2Mut<S> for Grid2Tracked<G, S>
+where
+    G: Grid2Mut<S>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = coord.into();
+        self.mark(coord);
+        self.inner.midx(coord)
+    }
+}
@@ -0,0 +1,153 @@
+//! Nearest-neighbor upscaling.
+
+use crate::{
+    scalar::Scalar,
+    grid2::*,
+    range::RangeBoundsTimes,
+};
+use mint::Vector2;
+
+/// Each element of the inner grid becomes a block of
+/// factor.x by factor.y elements in this grid.
+///
+/// All elements of a block alias the same inner element.
+pub struct Grid2Upscale<G, S = i32>
+where
+    G: Grid2<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    inner: G,
+    factor: Vector2<S>,
+    new_xbound: <G as Grid2<S>>::XBound,
+    new_ybound: <G as Grid2<S>>::YBound,
+}
+
+impl<G, S> Grid2Upscale<G, S>
+where
+    G: Grid2<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    /// Panics if either factor is not positive.
+    pub fn new<I>(inner: G, factor: I) -> Self 
+    where
+        I: Into<Vector2<S>>,
+    {
+        let factor = factor.into();
+        assert!(factor.x > S::ZERO && factor.y > S::ZERO, "invalid factor {:?}", factor);
+        let new_xbound = inner.x_bound().times(factor.x);
+        let new_ybound = inner.y_bound().times(factor.y);
+        Grid2Upscale {
+            inner,
+            factor,
+            new_xbound,
+            new_ybound,
+        }
+    }
+    
+    pub fn factor<I>(&self) -> I
+    where
+        I: From<Vector2<S>> 
+    {
+        I::from(self.factor)
+    }
+    
+    pub fn inner_coord<I>(&self, coord: I) -> I
+    where
+        I: Into<Vector2<S>> + From<Vector2<S>>
+    {
+        let Vector2 { x, y } = coord.into();
+        I::from(Vector2 {
+            x: x.div_euclid(self.factor.x),
+            y: y.div_euclid(self.factor.y),
+        })
+    }
+}
+
+impl<G, S> Grid2<S> for Grid2Upscale<G, S>
+where
+    G: Grid2<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    type Item = <G as Grid2<S>>::Item;
+    type XBound = <G as Grid2<S>>::XBound;
+    type YBound = <G as Grid2<S>>::YBound;
+    
+    fn x_bound(&self) -> Self::XBound { self.new_xbound.clone() }
+    fn y_bound(&self) -> Self::YBound { self.new_ybound.clone() }
+}
+
+impl<G, S> Grid2Len<S> for Grid2Upscale<G, S>
+where
+    G: Grid2Len<S>,
+    S: Scalar,
+{}
+
+impl<G, S> Grid2Get<S> for Grid2Upscale<G, S>
+where
+    G: Grid2<S> + Grid2Get<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = self.inner_coord(coord.into());
+        self.inner.get(coord)
+    }
+}
+
+impl<G, S> Grid2Set<S> for Grid2Upscale<G, S>
+where
+    G: Grid2<S> + Grid2Set<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = self.inner_coord(coord.into());
+        self.inner.set(coord, elem);
+    }
+}
+
+impl<G, S> Grid2Ref<S> for Grid2Upscale<G, S>
+where
+    G: Grid2<S> + Grid2Ref<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = self.inner_coord(coord.into());
+        self.inner.idx(coord)
+    }
+}
+
+impl<G, S> Grid2Mut<S> for Grid2Upscale<G, S>
+where
+    G: Grid2<S> + Grid2Mut<S>,
+    <G as Grid2<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid2<S>>::YBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = self.inner_coord(coord.into());
+        self.inner.midx(coord)
+    }
+}
@@ -1,6 +1,7 @@
 //! Grid wrapping around edges.
 
 use crate::{
+    scalar::Scalar,
     grid2::*,
     range::BoundRange,
 };
@@ -8,56 +9,45 @@ use mint::Vector2;
 use std::ops::RangeFull;
 
 
-pub struct Grid2Wrapping<G> 
-where
-    G: Grid2,
-    <G as Grid2>::XBound: BoundRange,
-    <G as Grid2>::YBound: BoundRange,
-{
+pub struct Grid2Wrapping<G> {
     inner: G,
 }
 
-impl<G> Grid2Wrapping<G>
-where
-    G: Grid2,
-    <G as Grid2>::XBound: BoundRange,
-    <G as Grid2>::YBound: BoundRange,
-{
+impl<G> Grid2Wrapping<G> {
     pub fn new(inner: G) -> Self {
         Grid2Wrapping {
             inner
         }
     }
     
-    pub fn wrap_coord<I>(&self, coord: I) -> I 
+    pub fn wrap_coord<I, S>(&self, coord: I) -> I 
     where
-        I: From<Vector2<i32>> + Into<Vector2<i32>>
+        G: Grid2<S>,
+        <G as Grid2<S>>::XBound: BoundRange<S>,
+        <G as Grid2<S>>::YBound: BoundRange<S>,
+        I: From<Vector2<S>> + Into<Vector2<S>>,
+        S: Scalar,
     {
-        let Vector2 { mut x, mut y } = coord.into();
+        let Vector2 { x, y } = coord.into();
+        
+        let x_bound = self.inner.x_bound();
+        let y_bound = self.inner.y_bound();
         
-        let x_start = self.inner.x_bound().lower_inclusive();
-        let x_end = self.inner.x_bound().upper_exclusive();
-            
-        let y_start = self.inner.y_bound().lower_inclusive();
-        let y_end = self.inner.y_bound().upper_exclusive();
-                
-        let x_len = x_end - x_start;
-        let y_len = y_end - y_start;
-            
-        x = ((((x - x_start) % x_len) + x_len) % x_len) + x_start;
-        y = ((((y - y_start) % y_len) + y_len) % y_len) + y_start;
+        let x = x_bound.wrap(x);
+        let y = y_bound.wrap(y);
         
         I::from(Vector2 { x, y })
     }
 }
 
-impl<G> Grid2 for Grid2Wrapping<G>
+impl<G, S> Grid2<S> for Grid2Wrapping<G>
 where
-    G: Grid2,
-    <G as Grid2>::XBound: BoundRange,
-    <G as Grid2>::YBound: BoundRange,
+    G: Grid2<S>,
+    <G as Grid2<S>>::XBound: BoundRange<S>,
+    <G as Grid2<S>>::YBound: BoundRange<S>,
+    S: Scalar,
 {
-    type Item = <G as Grid2>::Item;
+    type Item = <G as Grid2<S>>::Item;
     type XBound = RangeFull;
     type YBound = RangeFull;
     
@@ -65,60 +55,64 @@ where
     fn y_bound(&self) -> RangeFull { RangeFull }
 }
 
-impl<G> Grid2Get for Grid2Wrapping<G> 
+impl<G, S> Grid2Get<S> for Grid2Wrapping<G> 
 where
-    G: Grid2 + Grid2Get,
-    <G as Grid2>::XBound: BoundRange,
-    <G as Grid2>::YBound: BoundRange,
+    G: Grid2<S> + Grid2Get<S>,
+    <G as Grid2<S>>::XBound: BoundRange<S>,
+    <G as Grid2<S>>::YBound: BoundRange<S>,
+    S: Scalar,
 {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = self.wrap_coord(coord.into());
         self.inner.get(coord)
     }
 }
 
-impl<G> Grid2Set for Grid2Wrapping<G> 
+impl<G, S> Grid2Set<S> for Grid2Wrapping<G> 
 where
-    G: Grid2 + Grid2Set,
-    <G as Grid2>::XBound: BoundRange,
-    <G as Grid2>::YBound: BoundRange,
+    G: Grid2<S> + Grid2Set<S>,
+    <G as Grid2<S>>::XBound: BoundRange<S>,
+    <G as Grid2<S>>::YBound: BoundRange<S>,
+    S: Scalar,
 {
     fn set<I>(&mut self, coord: I, elem: Self::Item)
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = self.wrap_coord(coord.into());
         self.inner.set(coord, elem);
     }
 }
 
-impl<G> Grid2Ref for Grid2Wrapping<G> 
+impl<G, S> Grid2Ref<S> for Grid2Wrapping<G> 
 where
-    G: Grid2 + Grid2Ref,
-    <G as Grid2>::XBound: BoundRange,
-    <G as Grid2>::YBound: BoundRange,
+    G: Grid2<S> + Grid2Ref<S>,
+    <G as Grid2<S>>::XBound: BoundRange<S>,
+    <G as Grid2<S>>::YBound: BoundRange<S>,
+    S: Scalar,
 {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = self.wrap_coord(coord.into());
         self.inner.idx(coord)
     }
 }
 
-impl<G> Grid2Mut for Grid2Wrapping<G> 
+impl<G, S> Grid2Mut<S> for Grid2Wrapping<G> 
 where
-    G: Grid2 + Grid2Mut,
-    <G as Grid2>::XBound: BoundRange,
-    <G as Grid2>::YBound: BoundRange,
+    G: Grid2<S> + Grid2Mut<S>,
+    <G as Grid2<S>>::XBound: BoundRange<S>,
+    <G as Grid2<S>>::YBound: BoundRange<S>,
+    S: Scalar,
 {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = self.wrap_coord(coord.into());
         self.inner.midx(coord)
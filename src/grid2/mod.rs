@@ -10,9 +10,11 @@ use crate::{
     range::{
         Range0To,
         RangeBoundsTimes,
+        RangeBoundsDiv,
         RangeBoundsPlus,
         BoundRange,
     },
+    scalar::Scalar,
 };
 use mint::Vector2;
 use std::{
@@ -21,35 +23,39 @@ use std::{
 };
 
 /// Allocate a grid on the heap.
-pub fn alloc<I, T>(x_len: i32, y_len: i32, startval: T) -> backends::heap::ArrayGrid2<T>
+pub fn alloc<T, S>(x_len: S, y_len: S, startval: T) -> backends::heap::ArrayGrid2<T, S>
 where
-    T: Clone
+    T: Clone,
+    S: Scalar,
 {
     backends::heap::ArrayGrid2::broadcast(x_len, y_len, startval)
 }
 
 /// Allocate a grid on the heap, populate with a function.
-pub fn alloc_gen<I, T, F>(x_len: i32, y_len: i32, generator: F) -> backends::heap::ArrayGrid2<T>
+pub fn alloc_gen<I, T, F, S>(x_len: S, y_len: S, generator: F) -> backends::heap::ArrayGrid2<T, S>
 where
-    I: From<Vector2<i32>>,
+    I: From<Vector2<S>>,
     F: FnMut(I) -> T,
+    S: Scalar,
 {
     backends::heap::ArrayGrid2::new(x_len, y_len, generator)
 }
 
 /// Inline 3x3 array grid.
-pub fn array3x3<I, T>(startval: T) -> backends::inline3x3::Inline3x3Grid<T>
+pub fn array3x3<T, S>(startval: T) -> backends::inline3x3::Inline3x3Grid<T, S>
 where
-    T: Clone
+    T: Clone,
+    S: Scalar,
 {
     backends::inline3x3::Inline3x3Grid::broadcast(startval)
 }
 
 /// Inline 3x3 array grid, populate with a function.
-pub fn array3x3_gen<I, T, F>(generator: F) -> backends::inline3x3::Inline3x3Grid<T>
+pub fn array3x3_gen<I, T, F, S>(generator: F) -> backends::inline3x3::Inline3x3Grid<T, S>
 where
-    I: From<Vector2<i32>>,
+    I: From<Vector2<S>>,
     F: FnMut(I) -> T,
+    S: Scalar,
 {
     backends::inline3x3::Inline3x3Grid::new(generator)
 }
@@ -57,7 +63,6 @@ where
 /// Represent a coord → Item function as a grid.
 pub fn value_fn<I, T, F>(f: F) -> backends::kolmo::KolmoGrid2<F, I, T>
 where
-    I: From<Vector2<i32>>,
     F: Fn(I) -> T,
 {
     backends::kolmo::KolmoGrid2::new(f)
@@ -66,7 +71,6 @@ where
 /// Represent a coord → &Item function as a grid.
 pub fn ref_fn<'a, I, T, F>(f: F) -> backends::kolmoref::KolmoRefGrid2<'a, F, I, T>
 where
-    I: From<Vector2<i32>>,
     T: 'a,
     F: Fn(I) -> &'a T,
 {
@@ -76,7 +80,6 @@ where
 /// Represent a coord → &mut Item function as a grid.
 pub fn mut_fn<'a, I, T, F>(f: F) -> backends::kolmomut::KolmoMutGrid2<'a, F, I, T>
 where
-    I: From<Vector2<i32>>,
     T: 'a,
     F: Fn(I) -> &'a mut T,
 {
@@ -92,25 +95,36 @@ where
 /// elements from the referent.
 pub fn reader_writer<I, R, T, Fr, Fw>(referent: R, reader: Fr, writer: Fw) -> backends::kolmorw::KolmoRwGrid2<I, R, T, Fr, Fw>
 where
-    I: From<Vector2<i32>>,
     Fr: Fn(I, &R) -> &T,
     Fw: Fn(I, &mut R) -> &mut T,
 {
     backends::kolmorw::KolmoRwGrid2::new(referent, reader, writer)
 }
 
+/// Stack layers along the z axis into a Grid3.
+///
+/// Layer n becomes the plane at z = n. This will panic if there
+/// are no layers, or if the layers' bounds differ.
+pub fn stack<G, S>(layers: Vec<G>) -> combinate::stack::Grid2Stack<G>
+where
+    G: Grid2<S>,
+    S: Scalar,
+{
+    combinate::stack::Grid2Stack::new(layers)
+}
+
 /// Top-level trait for 2D grids.
-pub trait Grid2 {
+pub trait Grid2<S: Scalar = i32> {
     type Item;
-    type XBound: RangeBounds<i32>;
-    type YBound: RangeBounds<i32>;
+    type XBound: RangeBounds<S>;
+    type YBound: RangeBounds<S>;
     
     fn x_bound(&self) -> Self::XBound;
     fn y_bound(&self) -> Self::YBound;
     
     fn in_bounds<I>(&self, coord: I) -> bool 
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let Vector2 { x, y } = coord.into();
         
@@ -118,6 +132,17 @@ pub trait Grid2 {
         && self.y_bound().contains(&y)
     }
     
+    /// Borrow this grid, to apply combinators without consuming it.
+    fn view(&self) -> crate::view::GridRef<'_, Self> {
+        crate::view::GridRef::new(self)
+    }
+    
+    /// Mutably borrow this grid, to apply combinators without
+    /// consuming it.
+    fn view_mut(&mut self) -> crate::view::GridMut<'_, Self> {
+        crate::view::GridMut::new(self)
+    }
+    
     /// Element by-value mapping.
     fn map<F, T>(self, func: F) -> combinate::map::Grid2Map<Self, F, T>
     where
@@ -131,31 +156,61 @@ pub trait Grid2 {
     fn enumap<I, F, T>(self, func: F) -> combinate::enumap::Grid2EnuMap<Self, F, T, I>
     where
         Self: Sized,
-        I: From<Vector2<i32>>,
+        I: From<Vector2<S>>,
         F: Fn(I, Self::Item) -> T,
     {
         combinate::enumap::Grid2EnuMap::new(self, func)
     }
     
     /// Flattening a grid of grids with a regular stride.
-    fn flatten<I>(self, stride: I) -> combinate::flatten::Grid2Flat<Self>
+    fn flatten<I>(self, stride: I) -> combinate::flatten::Grid2Flat<Self, S>
     where
         Self: Sized,
-        Self::Item: Grid2,
-        Self::XBound: Clone + RangeBoundsTimes,
-        Self::YBound: Clone + RangeBoundsTimes,
-        I: Into<Vector2<i32>>,
+        Self::Item: Grid2<S>,
+        Self::XBound: Clone + RangeBoundsTimes<S>,
+        Self::YBound: Clone + RangeBoundsTimes<S>,
+        I: Into<Vector2<S>>,
     {
         combinate::flatten::Grid2Flat::new(self, stride)
     }
     
+    /// Nearest-neighbor upscaling.
+    ///
+    /// Each element becomes a block of factor.x by factor.y
+    /// elements, which all alias the original element.
+    fn upscale<I>(self, factor: I) -> combinate::upscale::Grid2Upscale<Self, S>
+    where
+        Self: Sized,
+        Self::XBound: RangeBoundsTimes<S>,
+        Self::YBound: RangeBoundsTimes<S>,
+        I: Into<Vector2<S>>,
+    {
+        combinate::upscale::Grid2Upscale::new(self, factor)
+    }
+    
+    /// Downscaling by folding blocks.
+    ///
+    /// Each block of factor.x by factor.y elements is passed to the
+    /// reducer as an iterator, which folds it into one element.
+    /// Blocks at the edges may be partial.
+    fn downscale<I, F, T>(self, factor: I, reducer: F) -> combinate::downscale::Grid2Downscale<Self, F, T, S>
+    where
+        Self: Sized + Grid2Get<S>,
+        Self::XBound: RangeBoundsDiv<S>,
+        Self::YBound: RangeBoundsDiv<S>,
+        I: Into<Vector2<S>>,
+        F: Fn(combinate::downscale::Grid2Block<'_, Self, S>) -> T,
+    {
+        combinate::downscale::Grid2Downscale::new(self, factor, reducer)
+    }
+    
     /// <0, 0> in this grid becomes new_origin in resultant grid.
-    fn new_origin<I>(self, new_origin: I) -> combinate::neworigin::Grid2NewOrigin<Self>
+    fn new_origin<I>(self, new_origin: I) -> combinate::neworigin::Grid2NewOrigin<Self, S>
     where
         Self: Sized,
-        Self::XBound: RangeBoundsPlus,
-        Self::YBound: RangeBoundsPlus,
-        I: Into<Vector2<i32>>,
+        Self::XBound: RangeBoundsPlus<S>,
+        Self::YBound: RangeBoundsPlus<S>,
+        I: Into<Vector2<S>>,
     {
         combinate::neworigin::Grid2NewOrigin::new(self, new_origin)
     }
@@ -166,7 +221,7 @@ pub trait Grid2 {
     fn oob_handler<I, F>(self, handler: F) -> combinate::oobhandler::Grid2OobHandler<Self, I, F>
     where
         Self: Sized,
-        I: From<Vector2<i32>>,
+        I: From<Vector2<S>>,
         F: Fn(I) -> Self::Item,
     {
         combinate::oobhandler::Grid2OobHandler::new(self, handler)
@@ -181,8 +236,8 @@ pub trait Grid2 {
         Self: Sized,
         Self::XBound: Debug,
         Self::YBound: Debug,
-        X: RangeBounds<i32> + Clone + Debug,
-        Y: RangeBounds<i32> + Clone + Debug,
+        X: RangeBounds<S> + Clone + Debug,
+        Y: RangeBounds<S> + Clone + Debug,
     {
         combinate::slice::Grid2Slice::new(self, new_x, new_y)
     }
@@ -196,8 +251,8 @@ pub trait Grid2 {
         Self: Sized,
         Self::XBound: Debug,
         Self::YBound: Debug,
-        X: RangeBounds<i32> + Clone + Debug,
-        Y: RangeBounds<i32> + Clone + Debug,
+        X: RangeBounds<S> + Clone + Debug,
+        Y: RangeBounds<S> + Clone + Debug,
     {
         combinate::slice::Grid2Slice::try_new(self, new_x, new_y)
     }
@@ -206,7 +261,7 @@ pub trait Grid2 {
     /// 
     /// If the new bounds are not a subset of the current bounds,
     /// this will panic.
-    fn subview_0to(self, new_x_len: i32, new_y_len: i32) -> combinate::slice::Grid2Slice<Self, Range0To, Range0To>
+    fn subview_0to(self, new_x_len: S, new_y_len: S) -> combinate::slice::Grid2Slice<Self, Range0To<S>, Range0To<S>>
     where
         Self: Sized,
         Self::XBound: Debug,
@@ -222,7 +277,7 @@ pub trait Grid2 {
     /// 
     /// If the new bounds are not a subset of the current bounds,
     /// this will fail.
-    fn try_subview_0to(self, new_x_len: i32, new_y_len: i32) -> Result<combinate::slice::Grid2Slice<Self, Range0To, Range0To>, Self>
+    fn try_subview_0to(self, new_x_len: S, new_y_len: S) -> Result<combinate::slice::Grid2Slice<Self, Range0To<S>, Range0To<S>>, Self>
     where
         Self: Sized,
         Self::XBound: Debug,
@@ -234,6 +289,24 @@ pub trait Grid2 {
             Range0To { end: new_y_len })
     }
     
+    /// Record the coordinates written through this grid.
+    fn tracked(self) -> combinate::tracked::Grid2Tracked<Self, S>
+    where
+        Self: Sized,
+    {
+        combinate::tracked::Grid2Tracked::new(self)
+    }
+    
+    /// Record writes to this grid, so that they can be undone.
+    ///
+    /// Up to max_transactions transactions of history are kept.
+    fn journaled(self, max_transactions: usize) -> combinate::journaled::Grid2Journaled<Self, S>
+    where
+        Self: Sized,
+    {
+        combinate::journaled::Grid2Journaled::new(self, max_transactions)
+    }
+    
     /// View of this grid which wraps around the edges.
     ///
     /// The input grid must be bounded in all directions, and the
@@ -241,49 +314,71 @@ pub trait Grid2 {
     fn wrapping(self) -> combinate::wrapping::Grid2Wrapping<Self>
     where
         Self: Sized,
-        Self::XBound: BoundRange,
-        Self::YBound: BoundRange,
+        Self::XBound: BoundRange<S>,
+        Self::YBound: BoundRange<S>,
     {
         combinate::wrapping::Grid2Wrapping::new(self)
     }
     
+    /// View this grid as a Grid3, repeated along the z axis.
+    ///
+    /// Every z coordinate within the z bound aliases the same
+    /// element of this grid.
+    fn extrude<Z>(self, z_bound: Z) -> combinate::extrude::Grid2Extrude<Self, Z>
+    where
+        Self: Sized,
+        Z: RangeBounds<S> + Clone,
+    {
+        combinate::extrude::Grid2Extrude::new(self, z_bound)
+    }
+    
+    /// View this grid as a dimension-generic GridN.
+    ///
+    /// All axes must have the same bound type.
+    fn into_gridn(self) -> crate::gridn::combinate::adapt::Grid2AsN<Self>
+    where
+        Self: Sized + Grid2<S, YBound=<Self as Grid2<S>>::XBound>,
+    {
+        crate::gridn::combinate::adapt::Grid2AsN::new(self)
+    }
+    
     /// Collect a grid's elements into a heap allocation.
     ///
     /// The grid must be bound from 0 to a finite limit.
-    fn collect(&self) -> backends::heap::ArrayGrid2<Self::Item>
+    fn collect(&self) -> backends::heap::ArrayGrid2<Self::Item, S>
     where
-        Self: Grid2Get,
-        Self::XBound: Into<Range0To>,
-        Self::YBound: Into<Range0To>,
+        Self: Grid2Get<S>,
+        Self::XBound: Into<Range0To<S>>,
+        Self::YBound: Into<Range0To<S>>,
     {
         let x_len = self.x_bound().into().end;
         let y_len = self.y_bound().into().end;
         backends::heap::ArrayGrid2::new(
             x_len, y_len,
-            |coord: Vector2<i32>| self.get(coord))
+            |coord: Vector2<S>| self.get(coord))
     }
 }
 
 /// 2D grid bounded from 0 to a finite number.
-pub trait Grid2Len: Grid2<XBound=Range0To, YBound=Range0To> {
-    fn x_len(&self) -> i32 {
+pub trait Grid2Len<S: Scalar = i32>: Grid2<S, XBound=Range0To<S>, YBound=Range0To<S>> {
+    fn x_len(&self) -> S {
         self.x_bound().end
     }
     
-    fn y_len(&self) -> i32 {
+    fn y_len(&self) -> S {
         self.y_bound().end
     }
 }
 
 /// 2D grid read by value.
-pub trait Grid2Get: Grid2 {
+pub trait Grid2Get<S: Scalar = i32>: Grid2<S> {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector2<i32>>;
+        I: Into<Vector2<S>>;
         
     fn try_get<I>(&self, coord: I) -> Option<Self::Item>
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
@@ -295,19 +390,19 @@ pub trait Grid2Get: Grid2 {
 }
 
 /// 2D grid write by value.
-pub trait Grid2Set: Grid2 {
+pub trait Grid2Set<S: Scalar = i32>: Grid2<S> {
     fn set<I>(&mut self, coord: I, elem: Self::Item)
     where
-        I: Into<Vector2<i32>>;
+        I: Into<Vector2<S>>;
         
     fn try_set<I>(&mut self, coord: I, elem: Self::Item) -> Result<(), Self::Item> 
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
             self.set(coord, elem);
-            Ok({})
+            Ok(())
         } else {
             Err(elem)
         }
@@ -315,14 +410,14 @@ pub trait Grid2Set: Grid2 {
 }
 
 /// 2D grid read by reference.
-pub trait Grid2Ref: Grid2 {
+pub trait Grid2Ref<S: Scalar = i32>: Grid2<S> {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector2<i32>>;
+        I: Into<Vector2<S>>;
     
     fn try_idx<I>(&self, coord: I) -> Option<&Self::Item>
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
@@ -334,14 +429,14 @@ pub trait Grid2Ref: Grid2 {
 }
 
 /// 2D grid write by reference.
-pub trait Grid2Mut: Grid2 {
+pub trait Grid2Mut<S: Scalar = i32>: Grid2<S> {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector2<i32>>;
+        I: Into<Vector2<S>>;
     
     fn try_midx<I>(&mut self, coord: I) -> Option<&mut Self::Item>
     where
-        I: Into<Vector2<i32>>
+        I: Into<Vector2<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
@@ -1,94 +1,293 @@
 //! Heap-allocated array implementation of Grid3.
 
 use crate::{
-    range::Range0To,
+    range::{Range0To, clip},
+    scalar::Scalar,
     grid3::*,
 };
 use mint::Vector3;
+use std::{
+    convert::Infallible,
+    mem,
+    ops::Range,
+};
 
 /// Heap-allocated array implementation of Grid3.
-pub struct ArrayGrid3<T> {
+pub struct ArrayGrid3<T, S = i32> {
     alloc: Box<[T]>,
-    x_len: i32,
-    y_len: i32,
-    z_len: i32,
+    x_len: S,
+    y_len: S,
+    z_len: S,
 }
 
-impl<T> ArrayGrid3<T> {
-    pub fn new<I, F>(x_len: i32, y_len: i32, z_len: i32, mut startval: F) -> Self
+impl<T, S: Scalar> ArrayGrid3<T, S> {
+    pub fn new<I, F>(x_len: S, y_len: S, z_len: S, mut startval: F) -> Self
     where
-        I: From<Vector3<i32>>,
+        I: From<Vector3<S>>,
         F: FnMut(I) -> T
     {
-        assert!(x_len >= 0);
-        assert!(y_len >= 0);
-        assert!(z_len >= 0);
+        match Self::try_new(x_len, y_len, z_len, |i| Ok::<T, Infallible>(startval(i))) {
+            Ok(grid) => grid,
+            Err(never) => match never {},
+        }
+    }
+    
+    /// Populate with a fallible function, stopping at the first error.
+    pub fn try_new<I, F, E>(x_len: S, y_len: S, z_len: S, mut startval: F) -> Result<Self, E>
+    where
+        I: From<Vector3<S>>,
+        F: FnMut(I) -> Result<T, E>
+    {
+        assert!(x_len >= S::ZERO);
+        assert!(y_len >= S::ZERO);
+        assert!(z_len >= S::ZERO);
         
-        let len = x_len * y_len * z_len;
-        let mut v: Vec<T> = Vec::with_capacity(len as usize);
-        for z in 0..z_len {
-            for y in 0..y_len {
-                for x in 0..x_len {
-                    let item = startval(I::from(Vector3 { x, y, z }));
+        let len = x_len.to_usize() * y_len.to_usize() * z_len.to_usize();
+        let mut v: Vec<T> = Vec::with_capacity(len);
+        for z in 0..z_len.to_usize() {
+            for y in 0..y_len.to_usize() {
+                for x in 0..x_len.to_usize() {
+                    let item = startval(I::from(Vector3 {
+                        x: S::from_usize(x),
+                        y: S::from_usize(y),
+                        z: S::from_usize(z),
+                    }))?;
                     v.push(item);
                 }
             }
         }
-        ArrayGrid3 {
+        Ok(ArrayGrid3 {
             alloc: v.into_boxed_slice(),
             x_len,
             y_len,
             z_len,
-        }
+        })
     }
     
-    pub fn broadcast(x_len: i32, y_len: i32, z_len: i32, startval: T) -> Self
+    pub fn broadcast(x_len: S, y_len: S, z_len: S, startval: T) -> Self
     where
-        T: Clone 
+        T: Clone
     {
-        Self::new(x_len, y_len, z_len, |_: Vector3<i32>| startval.clone())
+        Self::new(x_len, y_len, z_len, |_: Vector3<S>| startval.clone())
     }
     
-    fn inner_index(&self, x: i32, y: i32, z: i32) -> Option<i32> {
-        if x < 0 || x >= self.x_len || y < 0 || y >= self.y_len || z < 0 || z >= self.z_len {
+    fn inner_index(&self, x: S, y: S, z: S) -> Option<usize> {
+        if x < S::ZERO || x >= self.x_len || y < S::ZERO || y >= self.y_len || z < S::ZERO || z >= self.z_len {
             None
         } else {
-            Some(z * self.x_len * self.y_len + y * self.x_len + x)
+            let x_len = self.x_len.to_usize();
+            let y_len = self.y_len.to_usize();
+            Some(z.to_usize() * x_len * y_len + y.to_usize() * x_len + x.to_usize())
+        }
+    }
+    
+    /// Keep only the elements within the given ranges, moving the
+    /// lowest corner of the ranges to the origin.
+    ///
+    /// Panics if the ranges are not within this grid's bounds.
+    pub fn crop(&mut self, x: Range<S>, y: Range<S>, z: Range<S>) {
+        assert!(S::ZERO <= x.start && x.start <= x.end && x.end <= self.x_len,
+            "crop x range out of bounds");
+        assert!(S::ZERO <= y.start && y.start <= y.end && y.end <= self.y_len,
+            "crop y range out of bounds");
+        assert!(S::ZERO <= z.start && z.start <= z.end && z.end <= self.z_len,
+            "crop z range out of bounds");
+        let origin = [x.start.to_i64(), y.start.to_i64(), z.start.to_i64()];
+        self.reframe(x.end - x.start, y.end - y.start, z.end - z.start, origin, || unreachable!());
+    }
+    
+    /// Rearrange into a grid of the given size, where each coordinate
+    /// takes the element at that coordinate plus origin, or a new one
+    /// from fill if that is out of bounds.
+    ///
+    /// Works like `ArrayGrid2::reframe`, swapping within the existing
+    /// buffer.
+    fn reframe<F>(&mut self, x_len: S, y_len: S, z_len: S, origin: [i64; 3], mut fill: F)
+    where
+        F: FnMut() -> T
+    {
+        assert!(x_len >= S::ZERO);
+        assert!(y_len >= S::ZERO);
+        assert!(z_len >= S::ZERO);
+        
+        let old_x_len = self.x_len.to_i64();
+        let old_y_len = self.y_len.to_i64();
+        let old_z_len = self.z_len.to_i64();
+        let new_x_len = x_len.to_i64();
+        let new_y_len = y_len.to_i64();
+        let len = x_len.to_usize() * y_len.to_usize() * z_len.to_usize();
+        let source = |i: usize| -> Option<usize> {
+            let i = i as i64;
+            let x = i % new_x_len + origin[0];
+            let y = i / new_x_len % new_y_len + origin[1];
+            let z = i / new_x_len / new_y_len + origin[2];
+            if x < 0 || x >= old_x_len || y < 0 || y >= old_y_len || z < 0 || z >= old_z_len {
+                None
+            } else {
+                Some(((z * old_y_len + y) * old_x_len + x) as usize)
+            }
+        };
+        
+        let mut v = mem::take(&mut self.alloc).into_vec();
+        if v.len() < len {
+            v.resize_with(len, &mut fill);
+        }
+        for i in 0..len {
+            match source(i) {
+                Some(j) if j > i => v.swap(i, j),
+                _ => (),
+            }
+        }
+        for i in (0..len).rev() {
+            match source(i) {
+                Some(j) if j < i => v.swap(i, j),
+                _ => (),
+            }
+        }
+        v.truncate(len);
+        for (i, elem) in v.iter_mut().enumerate() {
+            if source(i).is_none() {
+                *elem = fill();
+            }
         }
+        
+        self.alloc = v.into_boxed_slice();
+        self.x_len = x_len;
+        self.y_len = y_len;
+        self.z_len = z_len;
     }
 }
 
-impl<T> Grid3 for ArrayGrid3<T> {
+impl<T: Clone, S: Scalar> ArrayGrid3<T, S> {
+    /// Copy a grid into this one, with its lowest corner at
+    /// dst_offset, clipped to this grid's bounds.
+    ///
+    /// This is equivalent to `algo::blit::blit3`, but copies whole
+    /// rows at a time.
+    pub fn blit_from<I>(&mut self, src: &ArrayGrid3<T, S>, dst_offset: I)
+    where
+        I: Into<Vector3<S>>
+    {
+        let dst_offset = dst_offset.into();
+        let x = clip(S::ZERO..src.x_len, dst_offset.x, S::ZERO..self.x_len);
+        let y = clip(S::ZERO..src.y_len, dst_offset.y, S::ZERO..self.y_len);
+        let z = clip(S::ZERO..src.z_len, dst_offset.z, S::ZERO..self.z_len);
+        if let (Some((x_range, src_x)), Some((y_range, src_y)), Some((z_range, src_z))) = (x, y, z) {
+            let len = (x_range.end - x_range.start).to_usize();
+            let rows = (y_range.end - y_range.start).to_usize();
+            let layers = (z_range.end - z_range.start).to_usize();
+            for layer in 0..layers {
+                for row in 0..rows {
+                    let dst_start = self.inner_index(
+                        x_range.start,
+                        y_range.start + S::from_usize(row),
+                        z_range.start + S::from_usize(layer)).unwrap();
+                    let src_start = src.inner_index(
+                        src_x,
+                        src_y + S::from_usize(row),
+                        src_z + S::from_usize(layer)).unwrap();
+                    self.alloc[dst_start..dst_start + len]
+                        .clone_from_slice(&src.alloc[src_start..src_start + len]);
+                }
+            }
+        }
+    }
+    
+    /// Set every element in a box, clipped to this grid's bounds.
+    ///
+    /// This is equivalent to `algo::blit::fill_box`, but fills
+    /// whole rows at a time.
+    pub fn fill_box(&mut self, x: Range<S>, y: Range<S>, z: Range<S>, value: T) {
+        let x = clip(x.clone(), x.start, S::ZERO..self.x_len);
+        let y = clip(y.clone(), y.start, S::ZERO..self.y_len);
+        let z = clip(z.clone(), z.start, S::ZERO..self.z_len);
+        if let (Some((x_range, _)), Some((y_range, _)), Some((z_range, _))) = (x, y, z) {
+            let len = (x_range.end - x_range.start).to_usize();
+            let mut z = z_range.start;
+            while z < z_range.end {
+                let mut y = y_range.start;
+                while y < y_range.end {
+                    let start = self.inner_index(x_range.start, y, z).unwrap();
+                    self.alloc[start..start + len].fill(value.clone());
+                    y = y + S::ONE;
+                }
+                z = z + S::ONE;
+            }
+        }
+    }
+    
+    /// Change the size of this grid, keeping elements at their
+    /// coordinates, dropping those now out of bounds, and filling new
+    /// space with fill.
+    pub fn resize(&mut self, x_len: S, y_len: S, z_len: S, fill: T) {
+        self.reframe(x_len, y_len, z_len, [0, 0, 0], || fill.clone());
+    }
+    
+    /// Grow this grid by low on the low side and high on the high side
+    /// of each axis, filling new space with fill.
+    ///
+    /// Existing elements move by low.
+    pub fn pad<I>(&mut self, low: I, high: I, fill: T)
+    where
+        I: Into<Vector3<S>>
+    {
+        let low = low.into();
+        let high = high.into();
+        assert!(low.x >= S::ZERO && low.y >= S::ZERO && low.z >= S::ZERO,
+            "negative padding");
+        assert!(high.x >= S::ZERO && high.y >= S::ZERO && high.z >= S::ZERO,
+            "negative padding");
+        let x_len = self.x_len + low.x + high.x;
+        let y_len = self.y_len + low.y + high.y;
+        let z_len = self.z_len + low.z + high.z;
+        let origin = [-low.x.to_i64(), -low.y.to_i64(), -low.z.to_i64()];
+        self.reframe(x_len, y_len, z_len, origin, || fill.clone());
+    }
+    
+    /// Move every element so that the one at origin ends up at
+    /// (0, 0, 0), keeping this grid's size, dropping elements moved
+    /// out of bounds, and filling vacated space with fill.
+    pub fn shift_origin<I>(&mut self, origin: I, fill: T)
+    where
+        I: Into<Vector3<S>>
+    {
+        let origin = origin.into();
+        let origin = [origin.x.to_i64(), origin.y.to_i64(), origin.z.to_i64()];
+        self.reframe(self.x_len, self.y_len, self.z_len, origin, || fill.clone());
+    }
+}
+
+impl<T, S: Scalar> Grid3<S> for ArrayGrid3<T, S> {
     type Item = T;
-    type XBound = Range0To;
-    type YBound = Range0To;
-    type ZBound = Range0To;
+    type XBound = Range0To<S>;
+    type YBound = Range0To<S>;
+    type ZBound = Range0To<S>;
     
-    fn x_bound(&self) -> Range0To {
+    fn x_bound(&self) -> Range0To<S> {
         Range0To { end: self.x_len }
     }
     
-    fn y_bound(&self) -> Range0To {
+    fn y_bound(&self) -> Range0To<S> {
         Range0To { end: self.y_len }
     }
     
-    fn z_bound(&self) -> Range0To {
+    fn z_bound(&self) -> Range0To<S> {
         Range0To { end: self.z_len }
     }
 }
 
-impl<T> Grid3Len for ArrayGrid3<T> {}
+impl<T, S: Scalar> Grid3Len<S> for ArrayGrid3<T, S> {}
 
-impl<T> Grid3Ref for ArrayGrid3<T> {
+impl<T, S: Scalar> Grid3Ref<S> for ArrayGrid3<T, S> {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         let Vector3 { x, y, z } = coord;
         let option = self
             .inner_index(x, y, z)
-            .map(|i| &self.alloc[i as usize]);
+            .map(|i| &self.alloc[i]);
         match option {
             Some(o) => o,
             None => panic!("invalid index {:?}", coord),
@@ -96,16 +295,16 @@ impl<T> Grid3Ref for ArrayGrid3<T> {
     }
 }
 
-impl<T> Grid3Mut for ArrayGrid3<T> {
+impl<T, S: Scalar> Grid3Mut<S> for ArrayGrid3<T, S> {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         let Vector3 { x, y, z } = coord;
         let option = self
             .inner_index(x, y, z)
-            .map(move |i| &mut self.alloc[i as usize]);
+            .map(move |i| &mut self.alloc[i]);
         match option {
             Some(o) => o,
             None => panic!("invalid index {:?}", coord),
@@ -113,12 +312,229 @@ impl<T> Grid3Mut for ArrayGrid3<T> {
     }
 }
 
-impl<T: Clone> Grid3Get for ArrayGrid3<T> {
-    fn get<I: Into<Vector3<i32>>>(&self, coord: I) -> Self::Item 
+impl<T: Clone, S: Scalar> Grid3Get<S> for ArrayGrid3<T, S> {
+    fn get<I: Into<Vector3<S>>>(&self, coord: I) -> Self::Item 
+    { self.idx(coord).clone() }
+}
+
+impl<T, S: Scalar> Grid3Set<S> for ArrayGrid3<T, S> {
+    fn set<I: Into<Vector3<S>>>(&mut self, coord: I, elem: Self::Item) 
+    { *self.midx(coord) = elem; }
+}
+
+impl<T, S: Scalar> ArrayGrid3<T, S> {
+    /// Mutably borrow the whole grid as a splittable box.
+    pub fn as_slice_mut(&mut self) -> ArrayGrid3SliceMut<'_, T, S> {
+        let rows = if self.x_len == S::ZERO {
+            (0..self.y_len.to_usize() * self.z_len.to_usize()).map(|_| Default::default()).collect()
+        } else {
+            self.alloc.chunks_mut(self.x_len.to_usize()).collect()
+        };
+        ArrayGrid3SliceMut {
+            rows,
+            x: S::ZERO..self.x_len,
+            y: S::ZERO..self.y_len,
+            z: S::ZERO..self.z_len,
+        }
+    }
+    
+    /// Mutably borrow the parts either side of an x coordinate.
+    pub fn split_at_x(&mut self, x: S) -> (ArrayGrid3SliceMut<'_, T, S>, ArrayGrid3SliceMut<'_, T, S>) {
+        self.as_slice_mut().split_at_x(x)
+    }
+    
+    /// Mutably borrow the parts either side of a y coordinate.
+    pub fn split_at_y(&mut self, y: S) -> (ArrayGrid3SliceMut<'_, T, S>, ArrayGrid3SliceMut<'_, T, S>) {
+        self.as_slice_mut().split_at_y(y)
+    }
+    
+    /// Mutably borrow the parts either side of a z coordinate.
+    pub fn split_at_z(&mut self, z: S) -> (ArrayGrid3SliceMut<'_, T, S>, ArrayGrid3SliceMut<'_, T, S>) {
+        self.as_slice_mut().split_at_z(z)
+    }
+    
+    /// Mutably borrow disjoint boxes of up to w × h × d elements,
+    /// covering the grid in x-major order.
+    pub fn chunks_mut(&mut self, w: S, h: S, d: S) -> impl Iterator<Item=ArrayGrid3SliceMut<'_, T, S>> {
+        self.as_slice_mut().chunks_mut(w, h, d)
+    }
+}
+
+/// Mutably borrowed box of an ArrayGrid3.
+///
+/// Coordinates are the same as in the parent grid. Boxes can be
+/// split further, and disjoint boxes can be sent to different
+/// threads.
+pub struct ArrayGrid3SliceMut<'a, T, S = i32> {
+    /// Rows along the x axis, from the lowest z then y coordinate.
+    rows: Vec<&'a mut [T]>,
+    x: Range<S>,
+    y: Range<S>,
+    z: Range<S>,
+}
+
+impl<'a, T, S: Scalar> ArrayGrid3SliceMut<'a, T, S> {
+    /// Split into the parts either side of an x coordinate.
+    pub fn split_at_x(self, x: S) -> (Self, Self) {
+        assert!(self.x.start <= x && x <= self.x.end, "split point {:?} out of bounds", x);
+        
+        let n = (x - self.x.start).to_usize();
+        let (left, right) = self.rows.into_iter()
+            .map(|row| row.split_at_mut(n))
+            .unzip();
+        (
+            ArrayGrid3SliceMut {
+                rows: left,
+                x: self.x.start..x,
+                y: self.y.clone(),
+                z: self.z.clone(),
+            },
+            ArrayGrid3SliceMut {
+                rows: right,
+                x: x..self.x.end,
+                y: self.y,
+                z: self.z,
+            },
+        )
+    }
+    
+    /// Split into the parts either side of a y coordinate.
+    pub fn split_at_y(self, y: S) -> (Self, Self) {
+        assert!(self.y.start <= y && y <= self.y.end, "split point {:?} out of bounds", y);
+        
+        let n = (y - self.y.start).to_usize();
+        let y_len = (self.y.end - self.y.start).to_usize();
+        let (below, above) = self.rows.into_iter()
+            .enumerate()
+            .partition::<Vec<_>, _>(|&(i, _)| i % y_len < n);
+        (
+            ArrayGrid3SliceMut {
+                rows: below.into_iter().map(|(_, row)| row).collect(),
+                x: self.x.clone(),
+                y: self.y.start..y,
+                z: self.z.clone(),
+            },
+            ArrayGrid3SliceMut {
+                rows: above.into_iter().map(|(_, row)| row).collect(),
+                x: self.x,
+                y: y..self.y.end,
+                z: self.z,
+            },
+        )
+    }
+    
+    /// Split into the parts either side of a z coordinate.
+    pub fn split_at_z(mut self, z: S) -> (Self, Self) {
+        assert!(self.z.start <= z && z <= self.z.end, "split point {:?} out of bounds", z);
+        
+        let y_len = (self.y.end - self.y.start).to_usize();
+        let above = self.rows.split_off((z - self.z.start).to_usize() * y_len);
+        (
+            ArrayGrid3SliceMut {
+                rows: self.rows,
+                x: self.x.clone(),
+                y: self.y.clone(),
+                z: self.z.start..z,
+            },
+            ArrayGrid3SliceMut {
+                rows: above,
+                x: self.x,
+                y: self.y,
+                z: z..self.z.end,
+            },
+        )
+    }
+    
+    /// Split into disjoint boxes of up to w × h × d elements, in
+    /// x-major order.
+    pub fn chunks_mut(self, w: S, h: S, d: S) -> impl Iterator<Item=Self> {
+        assert!(w > S::ZERO && h > S::ZERO && d > S::ZERO, "chunk size must be positive");
+        
+        let mut chunks = Vec::new();
+        let mut rest = self;
+        while rest.z.start < rest.z.end {
+            let z = (rest.z.start + d).min(rest.z.end);
+            let (mut layer, above) = rest.split_at_z(z);
+            rest = above;
+            while layer.y.start < layer.y.end {
+                let y = (layer.y.start + h).min(layer.y.end);
+                let (mut band, behind) = layer.split_at_y(y);
+                layer = behind;
+                while band.x.start < band.x.end {
+                    let x = (band.x.start + w).min(band.x.end);
+                    let (chunk, right) = band.split_at_x(x);
+                    chunks.push(chunk);
+                    band = right;
+                }
+            }
+        }
+        chunks.into_iter()
+    }
+    
+    fn inner_index(&self, x: S, y: S, z: S) -> Option<(usize, usize)> {
+        if self.x.contains(&x) && self.y.contains(&y) && self.z.contains(&z) {
+            let y_len = (self.y.end - self.y.start).to_usize();
+            let row = (z - self.z.start).to_usize() * y_len + (y - self.y.start).to_usize();
+            Some((row, (x - self.x.start).to_usize()))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, S: Scalar> Grid3<S> for ArrayGrid3SliceMut<'_, T, S> {
+    type Item = T;
+    type XBound = Range<S>;
+    type YBound = Range<S>;
+    type ZBound = Range<S>;
+    
+    fn x_bound(&self) -> Range<S> {
+        self.x.clone()
+    }
+    
+    fn y_bound(&self) -> Range<S> {
+        self.y.clone()
+    }
+    
+    fn z_bound(&self) -> Range<S> {
+        self.z.clone()
+    }
+}
+
+impl<T, S: Scalar> Grid3Ref<S> for ArrayGrid3SliceMut<'_, T, S> {
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        let Vector3 { x, y, z } = coord;
+        match self.inner_index(x, y, z) {
+            Some((row, i)) => &self.rows[row][i],
+            None => panic!("invalid index {:?}", coord),
+        }
+    }
+}
+
+impl<T, S: Scalar> Grid3Mut<S> for ArrayGrid3SliceMut<'_, T, S> {
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        let Vector3 { x, y, z } = coord;
+        match self.inner_index(x, y, z) {
+            Some((row, i)) => &mut self.rows[row][i],
+            None => panic!("invalid index {:?}", coord),
+        }
+    }
+}
+
+impl<T: Clone, S: Scalar> Grid3Get<S> for ArrayGrid3SliceMut<'_, T, S> {
+    fn get<I: Into<Vector3<S>>>(&self, coord: I) -> Self::Item 
     { self.idx(coord).clone() }
 }
 
-impl<T> Grid3Set for ArrayGrid3<T> {
-    fn set<I: Into<Vector3<i32>>>(&mut self, coord: I, elem: Self::Item) 
+impl<T, S: Scalar> Grid3Set<S> for ArrayGrid3SliceMut<'_, T, S> {
+    fn set<I: Into<Vector3<S>>>(&mut self, coord: I, elem: Self::Item) 
     { *self.midx(coord) = elem; }
 }
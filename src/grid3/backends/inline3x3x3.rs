@@ -2,22 +2,25 @@
 
 use crate::{
     range::Range0To,
+    scalar::Scalar,
     grid3::*,
 };
 use mint::Vector3;
+use std::marker::PhantomData;
 
-pub struct Inline3x3x3Grid<T> {
+pub struct Inline3x3x3Grid<T, S = i32> {
     array: [[[T; 3]; 3]; 3],
+    p: PhantomData<fn(S)>,
 }
 
-impl<T> Inline3x3x3Grid<T> {
+impl<T, S: Scalar> Inline3x3x3Grid<T, S> {
     pub fn new<I, F>(mut startval: F) -> Self 
     where
-        I: From<Vector3<i32>>,
+        I: From<Vector3<S>>,
         F: FnMut(I) -> T
     {
-        fn xyz<I: From<Vector3<i32>>>(x: i32, y: i32, z: i32) -> I {
-            I::from(Vector3 { x, y, z })
+        fn xyz<I: From<Vector3<S>>, S: Scalar>(x: usize, y: usize, z: usize) -> I {
+            I::from(Vector3 { x: S::from_usize(x), y: S::from_usize(y), z: S::from_usize(z) })
         }
         
         let array =
@@ -38,7 +41,7 @@ impl<T> Inline3x3x3Grid<T> {
                 [startval(xyz(2, 2, 0)), startval(xyz(2, 2, 1)), startval(xyz(2, 2, 2))],
             ]
         ];
-        Inline3x3x3Grid { array }
+        Inline3x3x3Grid { array, p: PhantomData }
     }
     
     
@@ -46,63 +49,63 @@ impl<T> Inline3x3x3Grid<T> {
     where
         T: Clone
     {
-        Self::new(|_: Vector3<i32>| startval.clone())
+        Self::new(|_: Vector3<S>| startval.clone())
     }
 }
 
-impl<T> From<[[[T; 3]; 3]; 3]> for Inline3x3x3Grid<T> {
+impl<T, S> From<[[[T; 3]; 3]; 3]> for Inline3x3x3Grid<T, S> {
     fn from(array: [[[T; 3]; 3]; 3]) -> Self {
-        Inline3x3x3Grid { array }
+        Inline3x3x3Grid { array, p: PhantomData }
     }
 }
 
-impl<T> Grid3 for Inline3x3x3Grid<T> {
+impl<T, S: Scalar> Grid3<S> for Inline3x3x3Grid<T, S> {
     type Item = T;
-    type XBound = Range0To;
-    type YBound = Range0To;
-    type ZBound = Range0To;
+    type XBound = Range0To<S>;
+    type YBound = Range0To<S>;
+    type ZBound = Range0To<S>;
     
-    fn x_bound(&self) -> Range0To {
-        Range0To { end: 3 }
+    fn x_bound(&self) -> Range0To<S> {
+        Range0To { end: S::from_usize(3) }
     }
     
-    fn y_bound(&self) -> Range0To {
-        Range0To { end: 3 }
+    fn y_bound(&self) -> Range0To<S> {
+        Range0To { end: S::from_usize(3) }
     }
     
-    fn z_bound(&self) -> Range0To {
-        Range0To { end: 3 }
+    fn z_bound(&self) -> Range0To<S> {
+        Range0To { end: S::from_usize(3) }
     }
 }
 
-impl<T> Grid3Len for Inline3x3x3Grid<T> {}
+impl<T, S: Scalar> Grid3Len<S> for Inline3x3x3Grid<T, S> {}
 
-impl<T> Grid3Ref for Inline3x3x3Grid<T> {
+impl<T, S: Scalar> Grid3Ref<S> for Inline3x3x3Grid<T, S> {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
-        &self.array[coord.x as usize][coord.y as usize][coord.z as usize]
+        &self.array[coord.x.to_usize()][coord.y.to_usize()][coord.z.to_usize()]
     }
 }
 
-impl<T> Grid3Mut for Inline3x3x3Grid<T> {
+impl<T, S: Scalar> Grid3Mut<S> for Inline3x3x3Grid<T, S> {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
-        &mut self.array[coord.x as usize][coord.y as usize][coord.z as usize]
+        &mut self.array[coord.x.to_usize()][coord.y.to_usize()][coord.z.to_usize()]
     }
 }
 
-impl<T: Clone> Grid3Get for Inline3x3x3Grid<T> {
-    fn get<I: Into<Vector3<i32>>>(&self, coord: I) -> Self::Item 
+impl<T: Clone, S: Scalar> Grid3Get<S> for Inline3x3x3Grid<T, S> {
+    fn get<I: Into<Vector3<S>>>(&self, coord: I) -> Self::Item 
     { self.idx(coord).clone() }
 }
 
-impl<T> Grid3Set for Inline3x3x3Grid<T> {
-    fn set<I: Into<Vector3<i32>>>(&mut self, coord: I, elem: Self::Item) 
+impl<T, S: Scalar> Grid3Set<S> for Inline3x3x3Grid<T, S> {
+    fn set<I: Into<Vector3<S>>>(&mut self, coord: I, elem: Self::Item) 
     { *self.midx(coord) = elem; }
 }
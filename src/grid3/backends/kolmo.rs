@@ -1,6 +1,9 @@
 //! Kolmogorov encoding of Grid3.
 
-use crate::grid3::*;
+use crate::{
+    scalar::Scalar,
+    grid3::*,
+};
 use mint::Vector3;
 use std::{
     ops::RangeFull,
@@ -14,8 +17,7 @@ use std::{
 /// unbounded.
 pub struct KolmoGrid3<F, I, T> 
 where
-    F: Fn(I) -> T,
-    I: From<Vector3<i32>>
+    F: Fn(I) -> T
 {
     func: F,
     p: PhantomData<fn(T, I)>,
@@ -23,8 +25,7 @@ where
 
 impl<F, I, T> KolmoGrid3<F, I, T>
 where
-    F: Fn(I) -> T,
-    I: From<Vector3<i32>>
+    F: Fn(I) -> T
 {
     pub fn new(func: F) -> Self {
         KolmoGrid3 {
@@ -34,10 +35,11 @@ where
     }
 }
 
-impl<F, I, T> Grid3 for KolmoGrid3<F, I, T>
+impl<F, I, T, S> Grid3<S> for KolmoGrid3<F, I, T>
 where
     F: Fn(I) -> T,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
     type Item = T;
     type XBound = RangeFull;
@@ -49,14 +51,15 @@ where
     fn z_bound(&self) -> RangeFull { RangeFull }
 }
 
-impl<F, I, T> Grid3Get for KolmoGrid3<F, I, T>
+impl<F, I, T, S> Grid3Get<S> for KolmoGrid3<F, I, T>
 where
     F: Fn(I) -> T,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
     fn get<C>(&self, coord: C) -> Self::Item
     where
-        C: Into<Vector3<i32>>
+        C: Into<Vector3<S>>
     {
         (self.func)(I::from(coord.into()))
     }
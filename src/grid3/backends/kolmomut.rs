@@ -1,6 +1,9 @@
 //! Kolmogorov by-mutable-reference encoding of Grid3.
 
-use crate::grid3::*;
+use crate::{
+    scalar::Scalar,
+    grid3::*,
+};
 use mint::Vector3;
 use std::{
     ops::RangeFull,
@@ -15,8 +18,7 @@ use std::{
 pub struct KolmoMutGrid3<'a, F, I, T> 
 where
     F: Fn(I) -> &'a mut T,
-    T: 'a,
-    I: From<Vector3<i32>>
+    T: 'a
 {
     func: F,
     p: PhantomData<fn(T, I)>,
@@ -25,8 +27,7 @@ where
 impl<'a, F, I, T> KolmoMutGrid3<'a, F, I, T>
 where
     F: Fn(I) -> &'a mut T,
-    T: 'a,
-    I: From<Vector3<i32>>
+    T: 'a
 {
     pub fn new(func: F) -> Self {
         KolmoMutGrid3 {
@@ -36,11 +37,12 @@ where
     }
 }
 
-impl<'a, F, I, T> Grid3 for KolmoMutGrid3<'a, F, I, T>
+impl<'a, F, I, T, S> Grid3<S> for KolmoMutGrid3<'a, F, I, T>
 where
     F: Fn(I) -> &'a mut T,
     T: 'a,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
     type Item = T;
     type XBound = RangeFull;
@@ -54,26 +56,28 @@ where
 
 
 
-impl<'a, F, I, T> Grid3Mut for KolmoMutGrid3<'a, F, I, T>
+impl<'a, F, I, T, S> Grid3Mut<S> for KolmoMutGrid3<'a, F, I, T>
 where
     F: Fn(I) -> &'a mut T,
     T: 'a,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
     fn midx<C>(&mut self, coord: C) -> &mut Self::Item
     where
-        C: Into<Vector3<i32>>
+        C: Into<Vector3<S>>
     {
         (self.func)(I::from(coord.into()))
     }
 }
 
-impl<'a, F, I, T> Grid3Set for KolmoMutGrid3<'a, F, I, T> 
+impl<'a, F, I, T, S> Grid3Set<S> for KolmoMutGrid3<'a, F, I, T> 
 where
     F: Fn(I) -> &'a mut T,
     T: 'a,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
-    fn set<C: Into<Vector3<i32>>>(&mut self, coord: C, elem: Self::Item) 
+    fn set<C: Into<Vector3<S>>>(&mut self, coord: C, elem: Self::Item) 
     { *self.midx(coord) = elem; }
 }
@@ -1,6 +1,9 @@
 //! Kolmogorov by-refernce encoding of Grid3.
 
-use crate::grid3::*;
+use crate::{
+    scalar::Scalar,
+    grid3::*,
+};
 use mint::Vector3;
 use std::{
     ops::RangeFull,
@@ -15,8 +18,7 @@ use std::{
 pub struct KolmoRefGrid3<'a, F, I, T> 
 where
     F: Fn(I) -> &'a T,
-    T: 'a,
-    I: From<Vector3<i32>>
+    T: 'a
 {
     func: F,
     p: PhantomData<fn(T, I)>,
@@ -25,8 +27,7 @@ where
 impl<'a, F, I, T> KolmoRefGrid3<'a, F, I, T>
 where
     F: Fn(I) -> &'a T,
-    T: 'a,
-    I: From<Vector3<i32>>
+    T: 'a
 {
     pub fn new(func: F) -> Self {
         KolmoRefGrid3 {
@@ -36,11 +37,12 @@ where
     }
 }
 
-impl<'a, F, I, T> Grid3 for KolmoRefGrid3<'a, F, I, T>
+impl<'a, F, I, T, S> Grid3<S> for KolmoRefGrid3<'a, F, I, T>
 where
     F: Fn(I) -> &'a T,
     T: 'a,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
     type Item = T;
     type XBound = RangeFull;
@@ -52,27 +54,29 @@ where
     fn z_bound(&self) -> RangeFull { RangeFull }
 }
 
-impl<'a, F, I, T> Grid3Ref for KolmoRefGrid3<'a, F, I, T>
+impl<'a, F, I, T, S> Grid3Ref<S> for KolmoRefGrid3<'a, F, I, T>
 where
     F: Fn(I) -> &'a T,
     T: 'a,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
     fn idx<C>(&self, coord: C) -> &Self::Item
     where
-        C: Into<Vector3<i32>>
+        C: Into<Vector3<S>>
     {
         (self.func)(I::from(coord.into()))
     }
 }
 
-impl<'a, F, I, T> Grid3Get for KolmoRefGrid3<'a, F, I, T>
+impl<'a, F, I, T, S> Grid3Get<S> for KolmoRefGrid3<'a, F, I, T>
 where
     F: Fn(I) -> &'a T,
     T: Clone + 'a,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
-    fn get<C: Into<Vector3<i32>>>(&self, coord: C) -> Self::Item 
+    fn get<C: Into<Vector3<S>>>(&self, coord: C) -> Self::Item 
     { self.idx(coord).clone() }
 }
 
@@ -1,6 +1,9 @@
 //! Kolmogorov by-refernce read/write encoding of Grid3.
 
-use crate::grid3::*;
+use crate::{
+    scalar::Scalar,
+    grid3::*,
+};
 use mint::Vector3;
 use std::{
     ops::RangeFull,
@@ -20,8 +23,7 @@ use std::{
 pub struct KolmoRwGrid3<I, R, T, Fr, Fw> 
 where
     Fr: Fn(I, &R) -> &T,
-    Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector3<i32>>
+    Fw: FnMut(I, &mut R) -> &mut T
 {
     referent: R,
     reader: Fr,
@@ -32,8 +34,7 @@ where
 impl<I, R, T, Fr, Fw> KolmoRwGrid3<I, R, T, Fr, Fw> 
 where
     Fr: Fn(I, &R) -> &T,
-    Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector3<i32>>
+    Fw: FnMut(I, &mut R) -> &mut T
 {
     pub fn new(referent: R, reader: Fr, writer: Fw) -> Self {
         KolmoRwGrid3 {
@@ -45,11 +46,12 @@ where
     }
 }
 
-impl<I, R, T, Fr, Fw> Grid3 for KolmoRwGrid3<I, R, T, Fr, Fw>
+impl<I, R, T, Fr, Fw, S> Grid3<S> for KolmoRwGrid3<I, R, T, Fr, Fw>
 where
     Fr: Fn(I, &R) -> &T,
     Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
     type Item = T;
     type XBound = RangeFull;
@@ -61,52 +63,56 @@ where
     fn z_bound(&self) -> RangeFull { RangeFull }
 }
 
-impl<I, R, T, Fr, Fw> Grid3Ref for KolmoRwGrid3<I, R, T, Fr, Fw>
+impl<I, R, T, Fr, Fw, S> Grid3Ref<S> for KolmoRwGrid3<I, R, T, Fr, Fw>
 where
     Fr: Fn(I, &R) -> &T,
     Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
     fn idx<C>(&self, coord: C) -> &Self::Item
     where
-        C: Into<Vector3<i32>>
+        C: Into<Vector3<S>>
     {
         (self.reader)(I::from(coord.into()), &self.referent)
     }
 }
 
-impl<I, R, T, Fr, Fw> Grid3Mut for KolmoRwGrid3<I, R, T, Fr, Fw>
+impl<I, R, T, Fr, Fw, S> Grid3Mut<S> for KolmoRwGrid3<I, R, T, Fr, Fw>
 where
     Fr: Fn(I, &R) -> &T,
     Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
     fn midx<C>(&mut self, coord: C) -> &mut Self::Item
     where
-        C: Into<Vector3<i32>>
+        C: Into<Vector3<S>>
     {
         (self.writer)(I::from(coord.into()), &mut self.referent)
     }
 }
 
 
-impl<I, R, T, Fr, Fw> Grid3Get for KolmoRwGrid3<I, R, T, Fr, Fw>
+impl<I, R, T, Fr, Fw, S> Grid3Get<S> for KolmoRwGrid3<I, R, T, Fr, Fw>
 where
     T: Clone,
     Fr: Fn(I, &R) -> &T,
     Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
-    fn get<C: Into<Vector3<i32>>>(&self, coord: C) -> Self::Item 
+    fn get<C: Into<Vector3<S>>>(&self, coord: C) -> Self::Item 
     { self.idx(coord).clone() }
 }
 
-impl<I, R, T, Fr, Fw> Grid3Set for KolmoRwGrid3<I, R, T, Fr, Fw>
+impl<I, R, T, Fr, Fw, S> Grid3Set<S> for KolmoRwGrid3<I, R, T, Fr, Fw>
 where
     Fr: Fn(I, &R) -> &T,
     Fw: FnMut(I, &mut R) -> &mut T,
-    I: From<Vector3<i32>>
+    I: From<Vector3<S>>,
+    S: Scalar,
 {
-    fn set<C: Into<Vector3<i32>>>(&mut self, coord: C, elem: Self::Item) 
+    fn set<C: Into<Vector3<S>>>(&mut self, coord: C, elem: Self::Item) 
     { *self.midx(coord) = elem; }
 }
@@ -0,0 +1,170 @@
+//! Memory-mapped file implementation of Grid3.
+//!
+//! Files use the `io::binary` layout, uncompressed. Elements are
+//! read and written in place, so grids larger than memory can be
+//! processed without loading them.
+
+use crate::{
+    range::Range0To,
+    scalar::Scalar,
+    grid3::*,
+    grid2::backends::mmap::{self, Mapping},
+    io::binary::Error,
+};
+use mint::Vector3;
+use std::{
+    path::Path,
+    marker::PhantomData,
+    io,
+};
+
+pub use crate::grid2::backends::mmap::{Pod, Access, ReadOnly, ReadWrite};
+
+/// Memory-mapped file implementation of Grid3.
+///
+/// Grids opened with `open` are `ReadOnly`, and only those created
+/// with `open_mut` or `create` are `ReadWrite`, and can be written.
+pub struct MmapGrid3<T, S = i32, A: Access = ReadOnly> {
+    mapping: Mapping<A>,
+    x_len: S,
+    y_len: S,
+    z_len: S,
+    p: PhantomData<T>,
+}
+
+impl<T: Pod, S: Scalar, A: Access> MmapGrid3<T, S, A> {
+    fn from_mapping(mapping: Mapping<A>, lens: &[usize]) -> Result<Self, Error> {
+        let lens = mmap::scalar_lens(lens)?;
+        Ok(MmapGrid3 {
+            mapping,
+            x_len: lens[0],
+            y_len: lens[1],
+            z_len: lens[2],
+            p: PhantomData,
+        })
+    }
+    
+    fn len(&self) -> usize {
+        self.x_len.to_usize() * self.y_len.to_usize() * self.z_len.to_usize()
+    }
+    
+    fn inner_index(&self, x: S, y: S, z: S) -> Option<usize> {
+        if x < S::ZERO || x >= self.x_len || y < S::ZERO || y >= self.y_len || z < S::ZERO || z >= self.z_len {
+            None
+        } else {
+            let x_len = self.x_len.to_usize();
+            let y_len = self.y_len.to_usize();
+            Some(z.to_usize() * x_len * y_len + y.to_usize() * x_len + x.to_usize())
+        }
+    }
+}
+
+impl<T: Pod, S: Scalar> MmapGrid3<T, S, ReadOnly> {
+    /// Map a file read-only.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified while this grid exists.
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let (mapping, lens) = mmap::map::<T, ReadOnly>(path.as_ref(), 3)?;
+        Self::from_mapping(mapping, &lens)
+    }
+}
+
+impl<T: Pod, S: Scalar> MmapGrid3<T, S, ReadWrite> {
+    /// Map a file read-write.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified except through this grid while
+    /// this grid exists.
+    pub unsafe fn open_mut<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let (mapping, lens) = mmap::map::<T, ReadWrite>(path.as_ref(), 3)?;
+        Self::from_mapping(mapping, &lens)
+    }
+    
+    /// Create or truncate a file of zeroed elements, and map it
+    /// read-write.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified except through this grid while
+    /// this grid exists.
+    pub unsafe fn create<P: AsRef<Path>>(path: P, x_len: S, y_len: S, z_len: S) -> Result<Self, Error> {
+        assert!(x_len >= S::ZERO);
+        assert!(y_len >= S::ZERO);
+        assert!(z_len >= S::ZERO);
+        
+        let lens = [x_len.to_usize(), y_len.to_usize(), z_len.to_usize()];
+        let mapping = mmap::create::<T>(path.as_ref(), &lens)?;
+        Self::from_mapping(mapping, &lens)
+    }
+    
+    /// Write modified elements back to the file.
+    pub fn flush(&self) -> io::Result<()> {
+        self.mapping.flush()
+    }
+}
+
+impl<T: Pod, S: Scalar, A: Access> Grid3<S> for MmapGrid3<T, S, A> {
+    type Item = T;
+    type XBound = Range0To<S>;
+    type YBound = Range0To<S>;
+    type ZBound = Range0To<S>;
+    
+    fn x_bound(&self) -> Range0To<S> {
+        Range0To { end: self.x_len }
+    }
+    
+    fn y_bound(&self) -> Range0To<S> {
+        Range0To { end: self.y_len }
+    }
+    
+    fn z_bound(&self) -> Range0To<S> {
+        Range0To { end: self.z_len }
+    }
+}
+
+impl<T: Pod, S: Scalar, A: Access> Grid3Len<S> for MmapGrid3<T, S, A> {}
+
+impl<T: Pod, S: Scalar, A: Access> Grid3Ref<S> for MmapGrid3<T, S, A> {
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        let Vector3 { x, y, z } = coord;
+        let option = self
+            .inner_index(x, y, z)
+            .map(|i| &self.mapping.elements::<T>(self.len())[i]);
+        match option {
+            Some(o) => o,
+            None => panic!("invalid index {:?}", coord),
+        }
+    }
+}
+
+impl<T: Pod, S: Scalar> Grid3Mut<S> for MmapGrid3<T, S, ReadWrite> {
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        let Vector3 { x, y, z } = coord;
+        let len = self.len();
+        match self.inner_index(x, y, z) {
+            Some(i) => &mut self.mapping.elements_mut::<T>(len)[i],
+            None => panic!("invalid index {:?}", coord),
+        }
+    }
+}
+
+impl<T: Pod, S: Scalar, A: Access> Grid3Get<S> for MmapGrid3<T, S, A> {
+    fn get<I: Into<Vector3<S>>>(&self, coord: I) -> Self::Item 
+    { *self.idx(coord) }
+}
+
+impl<T: Pod, S: Scalar> Grid3Set<S> for MmapGrid3<T, S, ReadWrite> {
+    fn set<I: Into<Vector3<S>>>(&mut self, coord: I, elem: Self::Item) 
+    { *self.midx(coord) = elem; }
+}
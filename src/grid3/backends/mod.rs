@@ -5,3 +5,8 @@ pub mod kolmoref;
 pub mod kolmomut;
 pub mod kolmorw;
 pub mod inline3x3x3;
+pub mod paletted;
+pub mod rle;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod paged;
@@ -0,0 +1,398 @@
+//! Paged implementation of Grid3.
+//!
+//! The grid is unbounded, and divided into cubic chunks. A bounded
+//! number of chunks are resident in memory at a time, and the rest
+//! live in a `ChunkStore`. Accessing a chunk which is not resident
+//! loads it from the store, evicting the least recently used chunk
+//! if necessary. Evicted chunks are written back to the store only
+//! if they were modified.
+
+use crate::{
+    scalar::Scalar,
+    grid3::*,
+    grid3::backends::heap::ArrayGrid3,
+    io::binary::{self, Compression},
+};
+use mint::Vector3;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, BTreeMap},
+    ops::RangeFull,
+    path::PathBuf,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter},
+    mem,
+};
+
+/// Storage for chunks which are not resident in memory.
+///
+/// Chunks are identified by their chunk coordinate, which is the
+/// element coordinate divided by the chunk length, rounding down.
+pub trait ChunkStore<T> {
+    /// Load a chunk, or `None` if it has never been stored.
+    fn load(&mut self, key: Vector3<i64>) -> io::Result<Option<ArrayGrid3<T>>>;
+    
+    /// Store a chunk, replacing any previous version.
+    fn store(&mut self, key: Vector3<i64>, chunk: &ArrayGrid3<T>) -> io::Result<()>;
+}
+
+/// Chunk store which keeps chunks in memory.
+pub struct MemoryChunkStore<T> {
+    chunks: HashMap<Vector3<i64>, ArrayGrid3<T>>,
+}
+
+impl<T> MemoryChunkStore<T> {
+    pub fn new() -> Self {
+        MemoryChunkStore {
+            chunks: HashMap::new(),
+        }
+    }
+    
+    /// Number of chunks stored.
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+}
+
+impl<T> Default for MemoryChunkStore<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> ChunkStore<T> for MemoryChunkStore<T> {
+    fn load(&mut self, key: Vector3<i64>) -> io::Result<Option<ArrayGrid3<T>>> {
+        Ok(self.chunks.get(&key).map(|chunk| chunk.collect()))
+    }
+    
+    fn store(&mut self, key: Vector3<i64>, chunk: &ArrayGrid3<T>) -> io::Result<()> {
+        self.chunks.insert(key, chunk.collect());
+        Ok(())
+    }
+}
+
+/// Chunk store which keeps chunks as files in a directory.
+///
+/// Each chunk is a file in the `io::binary` format.
+pub struct FsChunkStore {
+    dir: PathBuf,
+    compression: Compression,
+}
+
+impl FsChunkStore {
+    /// Store chunks in a directory, which must exist.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        FsChunkStore {
+            dir: dir.into(),
+            compression: Compression::Rle,
+        }
+    }
+    
+    /// Builder method to set the compression of written chunks.
+    ///
+    /// Defaults to run-length encoding.
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+    
+    fn path(&self, key: Vector3<i64>) -> PathBuf {
+        self.dir.join(format!("{}_{}_{}.grid", key.x, key.y, key.z))
+    }
+}
+
+fn to_io_error(e: binary::Error) -> io::Error {
+    match e {
+        binary::Error::Io(e) => e,
+        e => io::Error::new(io::ErrorKind::InvalidData, e),
+    }
+}
+
+impl<T: binary::Element + Clone> ChunkStore<T> for FsChunkStore {
+    fn load(&mut self, key: Vector3<i64>) -> io::Result<Option<ArrayGrid3<T>>> {
+        let file = match File::open(self.path(key)) {
+            Ok(file) => file,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        binary::read_grid3(BufReader::new(file))
+            .map(Some)
+            .map_err(to_io_error)
+    }
+    
+    /// Chunks are written to a temporary file which then replaces
+    /// the old chunk, so a failed write leaves the old chunk intact.
+    fn store(&mut self, key: Vector3<i64>, chunk: &ArrayGrid3<T>) -> io::Result<()> {
+        let path = self.path(key);
+        let tmp = path.with_extension("tmp");
+        let written = File::create(&tmp).and_then(|file| {
+            let mut out = BufWriter::new(file);
+            binary::write_grid3(chunk, &mut out, self.compression)?;
+            out.into_inner()?.sync_all()
+        });
+        match written {
+            Ok(()) => fs::rename(&tmp, &path),
+            Err(e) => {
+                let _ = fs::remove_file(&tmp);
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Resident chunk.
+struct Page<T> {
+    chunk: ArrayGrid3<T>,
+    dirty: bool,
+    /// Tick of last access, which is its key in the LRU order.
+    tick: u64,
+}
+
+struct Pages<T, C> {
+    store: C,
+    pages: HashMap<Vector3<i64>, Page<T>>,
+    /// Resident chunks by tick of last access.
+    lru: BTreeMap<u64, Vector3<i64>>,
+    tick: u64,
+    max_pages: usize,
+    chunk_len: i32,
+    default: T,
+}
+
+impl<T: Clone, C: ChunkStore<T>> Pages<T, C> {
+    /// Get a chunk, loading it if it is not resident.
+    fn page(&mut self, key: Vector3<i64>) -> io::Result<&mut Page<T>> {
+        self.tick += 1;
+        if let Some(page) = self.pages.get_mut(&key) {
+            self.lru.remove(&page.tick);
+            page.tick = self.tick;
+            self.lru.insert(self.tick, key);
+            return Ok(self.pages.get_mut(&key).unwrap());
+        }
+        
+        while self.pages.len() >= self.max_pages {
+            self.evict()?;
+        }
+        let len = self.chunk_len;
+        let chunk = match self.store.load(key)? {
+            Some(chunk) => {
+                if (chunk.x_len(), chunk.y_len(), chunk.z_len()) != (len, len, len) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("stored chunk {:?} has wrong size", key)));
+                }
+                chunk
+            }
+            None => ArrayGrid3::broadcast(len, len, len, self.default.clone()),
+        };
+        self.lru.insert(self.tick, key);
+        Ok(self.pages.entry(key).or_insert(Page {
+            chunk,
+            dirty: false,
+            tick: self.tick,
+        }))
+    }
+    
+    /// Evict the least recently used chunk.
+    ///
+    /// If writing it back fails, it remains resident.
+    fn evict(&mut self) -> io::Result<()> {
+        let (&tick, &key) = self.lru.iter().next().unwrap();
+        let page = &self.pages[&key];
+        if page.dirty {
+            self.store.store(key, &page.chunk)?;
+        }
+        self.lru.remove(&tick);
+        self.pages.remove(&key);
+        Ok(())
+    }
+    
+    fn flush(&mut self) -> io::Result<()> {
+        for (&key, page) in self.pages.iter_mut() {
+            if page.dirty {
+                self.store.store(key, &page.chunk)?;
+                page.dirty = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Paged implementation of Grid3.
+///
+/// Modified chunks are written back when evicted, flushed, or when
+/// the grid is dropped. Errors while writing back on drop are
+/// ignored, so call `flush` to observe them.
+pub struct PagedGrid3<T, C, S = i32>
+where
+    T: Clone,
+    C: ChunkStore<T>,
+    S: Scalar,
+{
+    pages: RefCell<Pages<T, C>>,
+    chunk_len: S,
+}
+
+impl<T, C, S> PagedGrid3<T, C, S>
+where
+    T: Clone,
+    C: ChunkStore<T>,
+    S: Scalar,
+{
+    /// Elements of chunks which have never been stored are
+    /// `default`. As many chunks are kept resident as fit in
+    /// `max_bytes`, counting `size_of::<T>()` per element, but at
+    /// least one.
+    pub fn new(store: C, chunk_len: S, default: T, max_bytes: usize) -> Self {
+        assert!(chunk_len > S::ZERO, "chunk length must be positive");
+        assert!(chunk_len.to_i64() <= i32::MAX as i64, "chunk length is too large");
+        
+        let chunk_bytes = chunk_len.to_usize().checked_pow(3)
+            .and_then(|volume| volume.checked_mul(mem::size_of::<T>().max(1)))
+            .expect("chunk length is too large: chunk size overflows usize");
+        PagedGrid3 {
+            pages: RefCell::new(Pages {
+                store,
+                pages: HashMap::new(),
+                lru: BTreeMap::new(),
+                tick: 0,
+                max_pages: (max_bytes / chunk_bytes).max(1),
+                chunk_len: chunk_len.to_i64() as i32,
+                default,
+            }),
+            chunk_len,
+        }
+    }
+    
+    pub fn chunk_len(&self) -> S {
+        self.chunk_len
+    }
+    
+    /// Maximum number of resident chunks.
+    pub fn max_resident_chunks(&self) -> usize {
+        self.pages.borrow().max_pages
+    }
+    
+    /// Number of resident chunks.
+    pub fn resident_chunks(&self) -> usize {
+        self.pages.borrow().pages.len()
+    }
+    
+    /// Number of resident chunks which have been modified since
+    /// they were loaded or flushed.
+    pub fn dirty_chunks(&self) -> usize {
+        self.pages.borrow().pages.values()
+            .filter(|page| page.dirty)
+            .count()
+    }
+    
+    /// Write all modified chunks back to the store.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.pages.get_mut().flush()
+    }
+    
+    /// Chunk key and coordinate within the chunk.
+    fn locate(&self, coord: Vector3<S>) -> (Vector3<i64>, Vector3<i32>) {
+        let len = self.chunk_len;
+        let key = Vector3 {
+            x: coord.x.div_euclid(len).to_i64(),
+            y: coord.y.div_euclid(len).to_i64(),
+            z: coord.z.div_euclid(len).to_i64(),
+        };
+        let local = Vector3 {
+            x: coord.x.rem_euclid(len).to_i64() as i32,
+            y: coord.y.rem_euclid(len).to_i64() as i32,
+            z: coord.z.rem_euclid(len).to_i64() as i32,
+        };
+        (key, local)
+    }
+    
+    /// Read an element, returning store errors.
+    pub fn read<I>(&self, coord: I) -> io::Result<T>
+    where
+        I: Into<Vector3<S>>
+    {
+        let (key, local) = self.locate(coord.into());
+        let mut pages = self.pages.borrow_mut();
+        let page = pages.page(key)?;
+        Ok(page.chunk.idx(local).clone())
+    }
+    
+    /// Write an element, returning store errors.
+    pub fn write<I>(&mut self, coord: I, elem: T) -> io::Result<()>
+    where
+        I: Into<Vector3<S>>
+    {
+        let (key, local) = self.locate(coord.into());
+        let page = self.pages.get_mut().page(key)?;
+        page.chunk.set(local, elem);
+        page.dirty = true;
+        Ok(())
+    }
+}
+
+impl<T, C, S> Drop for PagedGrid3<T, C, S>
+where
+    T: Clone,
+    C: ChunkStore<T>,
+    S: Scalar,
+{
+    fn drop(&mut self) {
+        let _ = self.pages.get_mut().flush();
+    }
+}
+
+impl<T, C, S> Grid3<S> for PagedGrid3<T, C, S>
+where
+    T: Clone,
+    C: ChunkStore<T>,
+    S: Scalar,
+{
+    type Item = T;
+    type XBound = RangeFull;
+    type YBound = RangeFull;
+    type ZBound = RangeFull;
+    
+    fn x_bound(&self) -> RangeFull { RangeFull }
+    fn y_bound(&self) -> RangeFull { RangeFull }
+    fn z_bound(&self) -> RangeFull { RangeFull }
+}
+
+impl<T, C, S> Grid3Get<S> for PagedGrid3<T, C, S>
+where
+    T: Clone,
+    C: ChunkStore<T>,
+    S: Scalar,
+{
+    /// Panics if the chunk store fails.
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        match self.read(coord) {
+            Ok(elem) => elem,
+            Err(e) => panic!("failed to load chunk: {}", e),
+        }
+    }
+}
+
+impl<T, C, S> Grid3Set<S> for PagedGrid3<T, C, S>
+where
+    T: Clone,
+    C: ChunkStore<T>,
+    S: Scalar,
+{
+    /// Panics if the chunk store fails.
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector3<S>>
+    {
+        if let Err(e) = self.write(coord, elem) {
+            panic!("failed to load chunk: {}", e);
+        }
+    }
+}
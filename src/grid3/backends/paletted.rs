@@ -0,0 +1,293 @@
+//! Palette-compressed implementation of Grid3.
+
+use crate::{
+    range::Range0To,
+    scalar::Scalar,
+    grid3::*,
+    grid3::backends::heap::ArrayGrid3,
+};
+use mint::Vector3;
+use std::{
+    collections::HashMap,
+    hash::Hash,
+};
+
+/// Palette-compressed implementation of Grid3.
+///
+/// Each distinct element is stored once in a palette, and the grid
+/// stores bit-packed palette indices. The bit width grows as new
+/// elements are written. A grid with a single distinct element
+/// uses no index storage at all.
+pub struct PalettedGrid3<T, S = i32> {
+    palette: Vec<T>,
+    lookup: HashMap<T, usize>,
+    bits: u32,
+    words: Vec<u64>,
+    x_len: S,
+    y_len: S,
+    z_len: S,
+}
+
+/// Bits needed to index a palette of some length.
+fn bits_for(palette_len: usize) -> u32 {
+    if palette_len <= 1 {
+        0
+    } else {
+        usize::BITS - (palette_len - 1).leading_zeros()
+    }
+}
+
+/// Number of u64 words needed to pack len indices.
+fn words_for(len: usize, bits: u32) -> usize {
+    match 64u32.checked_div(bits) {
+        Some(per_word) => len.div_ceil(per_word as usize),
+        None => 0,
+    }
+}
+
+impl<T, S: Scalar> PalettedGrid3<T, S> {
+    pub fn new<I, F>(x_len: S, y_len: S, z_len: S, mut startval: F) -> Self
+    where
+        I: From<Vector3<S>>,
+        F: FnMut(I) -> T,
+        T: Clone + Eq + Hash,
+    {
+        assert!(x_len >= S::ZERO);
+        assert!(y_len >= S::ZERO);
+        assert!(z_len >= S::ZERO);
+        
+        let mut grid = PalettedGrid3 {
+            palette: Vec::new(),
+            lookup: HashMap::new(),
+            bits: 0,
+            words: Vec::new(),
+            x_len,
+            y_len,
+            z_len,
+        };
+        let mut i = 0;
+        for z in 0..z_len.to_usize() {
+            for y in 0..y_len.to_usize() {
+                for x in 0..x_len.to_usize() {
+                    let item = startval(I::from(Vector3 {
+                        x: S::from_usize(x),
+                        y: S::from_usize(y),
+                        z: S::from_usize(z),
+                    }));
+                    let index = grid.palette_index(item);
+                    grid.set_index(i, index);
+                    i += 1;
+                }
+            }
+        }
+        grid
+    }
+    
+    pub fn broadcast(x_len: S, y_len: S, z_len: S, startval: T) -> Self
+    where
+        T: Clone + Eq + Hash,
+    {
+        Self::new(x_len, y_len, z_len, |_: Vector3<S>| startval.clone())
+    }
+    
+    /// Distinct elements which have been stored in this grid.
+    ///
+    /// This may include elements which have since been overwritten,
+    /// until `compact` is called.
+    pub fn palette(&self) -> &[T] {
+        &self.palette
+    }
+    
+    /// Current bit width of each stored palette index.
+    pub fn bits_per_element(&self) -> u32 {
+        self.bits
+    }
+    
+    /// Remove palette elements which are no longer used, and shrink
+    /// the bit width to match.
+    pub fn compact(&mut self)
+    where
+        T: Clone + Eq + Hash,
+    {
+        let len = self.len();
+        let mut used = vec![false; self.palette.len()];
+        for i in 0..len {
+            used[self.index(i)] = true;
+        }
+        
+        let mut remap = vec![0; self.palette.len()];
+        let mut palette = Vec::new();
+        for (old, elem) in self.palette.drain(..).enumerate() {
+            if used[old] {
+                remap[old] = palette.len();
+                palette.push(elem);
+            }
+        }
+        let indices = (0..len)
+            .map(|i| remap[self.index(i)])
+            .collect::<Vec<usize>>();
+        
+        self.lookup = palette.iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, elem)| (elem, i))
+            .collect();
+        self.palette = palette;
+        self.bits = bits_for(self.palette.len());
+        self.words = vec![0; words_for(len, self.bits)];
+        for (i, index) in indices.into_iter().enumerate() {
+            self.set_index(i, index);
+        }
+    }
+    
+    fn len(&self) -> usize {
+        self.x_len.to_usize() * self.y_len.to_usize() * self.z_len.to_usize()
+    }
+    
+    fn inner_index(&self, x: S, y: S, z: S) -> Option<usize> {
+        if x < S::ZERO || x >= self.x_len || y < S::ZERO || y >= self.y_len || z < S::ZERO || z >= self.z_len {
+            None
+        } else {
+            let x_len = self.x_len.to_usize();
+            let y_len = self.y_len.to_usize();
+            Some(z.to_usize() * x_len * y_len + y.to_usize() * x_len + x.to_usize())
+        }
+    }
+    
+    /// Word, shift and mask of the packed index for element i,
+    /// unless the bit width is zero.
+    fn slot(&self, i: usize) -> Option<(usize, u32, u64)> {
+        64u32.checked_div(self.bits).map(|per_word| {
+            let per_word = per_word as usize;
+            let shift = (i % per_word) as u32 * self.bits;
+            let mask = (1u64 << self.bits) - 1;
+            (i / per_word, shift, mask)
+        })
+    }
+    
+    fn index(&self, i: usize) -> usize {
+        match self.slot(i) {
+            Some((word, shift, mask)) => ((self.words[word] >> shift) & mask) as usize,
+            None => 0,
+        }
+    }
+    
+    fn set_index(&mut self, i: usize, index: usize) {
+        if let Some((word, shift, mask)) = self.slot(i) {
+            let word = &mut self.words[word];
+            *word = (*word & !(mask << shift)) | ((index as u64) << shift);
+        }
+    }
+    
+    /// Palette index of an element, adding it and growing the bit
+    /// width if necessary.
+    fn palette_index(&mut self, elem: T) -> usize
+    where
+        T: Clone + Eq + Hash,
+    {
+        if let Some(&index) = self.lookup.get(&elem) {
+            return index;
+        }
+        
+        let index = self.palette.len();
+        self.palette.push(elem.clone());
+        self.lookup.insert(elem, index);
+        
+        let bits = bits_for(self.palette.len());
+        if bits != self.bits {
+            let len = self.len();
+            let indices = (0..len)
+                .map(|i| self.index(i))
+                .collect::<Vec<usize>>();
+            self.bits = bits;
+            self.words = vec![0; words_for(len, bits)];
+            for (i, index) in indices.into_iter().enumerate() {
+                self.set_index(i, index);
+            }
+        }
+        index
+    }
+}
+
+impl<T, S: Scalar> Grid3<S> for PalettedGrid3<T, S> {
+    type Item = T;
+    type XBound = Range0To<S>;
+    type YBound = Range0To<S>;
+    type ZBound = Range0To<S>;
+    
+    fn x_bound(&self) -> Range0To<S> {
+        Range0To { end: self.x_len }
+    }
+    
+    fn y_bound(&self) -> Range0To<S> {
+        Range0To { end: self.y_len }
+    }
+    
+    fn z_bound(&self) -> Range0To<S> {
+        Range0To { end: self.z_len }
+    }
+}
+
+impl<T, S: Scalar> Grid3Len<S> for PalettedGrid3<T, S> {}
+
+impl<T, S: Scalar> Grid3Ref<S> for PalettedGrid3<T, S> {
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        let Vector3 { x, y, z } = coord;
+        let option = self
+            .inner_index(x, y, z)
+            .map(|i| &self.palette[self.index(i)]);
+        match option {
+            Some(o) => o,
+            None => panic!("invalid index {:?}", coord),
+        }
+    }
+}
+
+impl<T: Clone, S: Scalar> Grid3Get<S> for PalettedGrid3<T, S> {
+    fn get<I: Into<Vector3<S>>>(&self, coord: I) -> Self::Item 
+    { self.idx(coord).clone() }
+}
+
+impl<T, S> Grid3Set<S> for PalettedGrid3<T, S>
+where
+    T: Clone + Eq + Hash,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        let Vector3 { x, y, z } = coord;
+        let i = match self.inner_index(x, y, z) {
+            Some(i) => i,
+            None => panic!("invalid index {:?}", coord),
+        };
+        let index = self.palette_index(elem);
+        self.set_index(i, index);
+    }
+}
+
+impl<T, S> From<ArrayGrid3<T, S>> for PalettedGrid3<T, S>
+where
+    T: Clone + Eq + Hash,
+    S: Scalar,
+{
+    fn from(array: ArrayGrid3<T, S>) -> Self {
+        PalettedGrid3::new(array.x_len(), array.y_len(), array.z_len(), |coord: Vector3<S>| array.idx(coord).clone())
+    }
+}
+
+impl<T, S> From<PalettedGrid3<T, S>> for ArrayGrid3<T, S>
+where
+    T: Clone,
+    S: Scalar,
+{
+    fn from(paletted: PalettedGrid3<T, S>) -> Self {
+        ArrayGrid3::new(paletted.x_len, paletted.y_len, paletted.z_len, |coord: Vector3<S>| paletted.idx(coord).clone())
+    }
+}
@@ -0,0 +1,196 @@
+//! Run-length encoded implementation of Grid3.
+
+use crate::{
+    range::Range0To,
+    scalar::Scalar,
+    grid3::*,
+    grid3::backends::heap::ArrayGrid3,
+};
+use mint::Vector3;
+
+/// Run-length encoded implementation of Grid3.
+///
+/// Elements are stored as runs of equal elements, ordered with x
+/// fastest, then y, then z. Reads are a binary search over the
+/// runs, and writes split and merge runs, so this suits grids
+/// which are mostly read and contain large uniform regions.
+pub struct RleGrid3<T, S = i32> {
+    /// Exclusive end index of each run, and its element.
+    runs: Vec<(usize, T)>,
+    x_len: S,
+    y_len: S,
+    z_len: S,
+}
+
+impl<T, S: Scalar> RleGrid3<T, S> {
+    pub fn new<I, F>(x_len: S, y_len: S, z_len: S, mut startval: F) -> Self
+    where
+        I: From<Vector3<S>>,
+        F: FnMut(I) -> T,
+        T: PartialEq,
+    {
+        assert!(x_len >= S::ZERO);
+        assert!(y_len >= S::ZERO);
+        assert!(z_len >= S::ZERO);
+        
+        let mut runs: Vec<(usize, T)> = Vec::new();
+        let mut i = 0;
+        for z in 0..z_len.to_usize() {
+            for y in 0..y_len.to_usize() {
+                for x in 0..x_len.to_usize() {
+                    let item = startval(I::from(Vector3 {
+                        x: S::from_usize(x),
+                        y: S::from_usize(y),
+                        z: S::from_usize(z),
+                    }));
+                    i += 1;
+                    match runs.last_mut() {
+                        Some(&mut (ref mut end, ref elem)) if *elem == item => *end = i,
+                        _ => runs.push((i, item)),
+                    }
+                }
+            }
+        }
+        RleGrid3 {
+            runs,
+            x_len,
+            y_len,
+            z_len,
+        }
+    }
+    
+    pub fn broadcast(x_len: S, y_len: S, z_len: S, startval: T) -> Self
+    where
+        T: Clone + PartialEq,
+    {
+        Self::new(x_len, y_len, z_len, |_: Vector3<S>| startval.clone())
+    }
+    
+    /// Number of runs currently stored.
+    pub fn run_count(&self) -> usize {
+        self.runs.len()
+    }
+    
+    fn inner_index(&self, x: S, y: S, z: S) -> Option<usize> {
+        if x < S::ZERO || x >= self.x_len || y < S::ZERO || y >= self.y_len || z < S::ZERO || z >= self.z_len {
+            None
+        } else {
+            let x_len = self.x_len.to_usize();
+            let y_len = self.y_len.to_usize();
+            Some(z.to_usize() * x_len * y_len + y.to_usize() * x_len + x.to_usize())
+        }
+    }
+    
+    /// Index of the run containing element index i.
+    fn run(&self, i: usize) -> usize {
+        self.runs.partition_point(|&(end, _)| end <= i)
+    }
+}
+
+impl<T, S: Scalar> Grid3<S> for RleGrid3<T, S> {
+    type Item = T;
+    type XBound = Range0To<S>;
+    type YBound = Range0To<S>;
+    type ZBound = Range0To<S>;
+    
+    fn x_bound(&self) -> Range0To<S> {
+        Range0To { end: self.x_len }
+    }
+    
+    fn y_bound(&self) -> Range0To<S> {
+        Range0To { end: self.y_len }
+    }
+    
+    fn z_bound(&self) -> Range0To<S> {
+        Range0To { end: self.z_len }
+    }
+}
+
+impl<T, S: Scalar> Grid3Len<S> for RleGrid3<T, S> {}
+
+impl<T, S: Scalar> Grid3Ref<S> for RleGrid3<T, S> {
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        let Vector3 { x, y, z } = coord;
+        let option = self
+            .inner_index(x, y, z)
+            .map(|i| &self.runs[self.run(i)].1);
+        match option {
+            Some(o) => o,
+            None => panic!("invalid index {:?}", coord),
+        }
+    }
+}
+
+impl<T: Clone, S: Scalar> Grid3Get<S> for RleGrid3<T, S> {
+    fn get<I: Into<Vector3<S>>>(&self, coord: I) -> Self::Item 
+    { self.idx(coord).clone() }
+}
+
+impl<T, S> Grid3Set<S> for RleGrid3<T, S>
+where
+    T: Clone + PartialEq,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        let Vector3 { x, y, z } = coord;
+        let i = match self.inner_index(x, y, z) {
+            Some(i) => i,
+            None => panic!("invalid index {:?}", coord),
+        };
+        
+        let r = self.run(i);
+        if self.runs[r].1 == elem {
+            return;
+        }
+        
+        // split the run around i
+        let start = if r == 0 { 0 } else { self.runs[r - 1].0 };
+        let (end, old) = self.runs[r].clone();
+        let mut pieces = Vec::with_capacity(3);
+        if start < i {
+            pieces.push((i, old.clone()));
+        }
+        let j = r + pieces.len();
+        pieces.push((i + 1, elem));
+        if i + 1 < end {
+            pieces.push((end, old));
+        }
+        self.runs.splice(r..=r, pieces);
+        
+        // merge with equal neighbors
+        if j + 1 < self.runs.len() && self.runs[j].1 == self.runs[j + 1].1 {
+            self.runs.remove(j);
+        }
+        if j > 0 && self.runs[j - 1].1 == self.runs[j].1 {
+            self.runs.remove(j - 1);
+        }
+    }
+}
+
+impl<T, S> From<ArrayGrid3<T, S>> for RleGrid3<T, S>
+where
+    T: Clone + PartialEq,
+    S: Scalar,
+{
+    fn from(array: ArrayGrid3<T, S>) -> Self {
+        RleGrid3::new(array.x_len(), array.y_len(), array.z_len(), |coord: Vector3<S>| array.idx(coord).clone())
+    }
+}
+
+impl<T, S> From<RleGrid3<T, S>> for ArrayGrid3<T, S>
+where
+    T: Clone,
+    S: Scalar,
+{
+    fn from(rle: RleGrid3<T, S>) -> Self {
+        ArrayGrid3::new(rle.x_len, rle.y_len, rle.z_len, |coord: Vector3<S>| rle.idx(coord).clone())
+    }
+}
@@ -0,0 +1,161 @@
+//! Block-reducing downscaling.
+
+use crate::{
+    scalar::Scalar,
+    grid3::*,
+    range::RangeBoundsDiv,
+};
+use mint::Vector3;
+use std::marker::PhantomData;
+
+/// Each block of factor.x by factor.y by factor.z elements in the inner
+/// grid is folded into one element of this grid.
+///
+/// Blocks which overlap the edge of the inner grid only
+/// contain the elements within its bounds.
+pub struct Grid3Downscale<G, F, T, S = i32> {
+    inner: G,
+    func: F,
+    factor: Vector3<S>,
+    p: PhantomData<fn(T)>
+}
+
+impl<G, F, T, S> Grid3Downscale<G, F, T, S>
+where
+    S: Scalar,
+{
+    /// Panics if any factor is not positive.
+    pub fn new<I>(inner: G, factor: I, func: F) -> Self 
+    where
+        I: Into<Vector3<S>>,
+    {
+        let factor = factor.into();
+        assert!(factor.x > S::ZERO && factor.y > S::ZERO && factor.z > S::ZERO, "invalid factor {:?}", factor);
+        Grid3Downscale {
+            inner,
+            func,
+            factor,
+            p: PhantomData,
+        }
+    }
+    
+    pub fn factor<I>(&self) -> I
+    where
+        I: From<Vector3<S>> 
+    {
+        I::from(self.factor)
+    }
+}
+
+impl<G, F, T, S> Grid3<S> for Grid3Downscale<G, F, T, S>
+where
+    G: Grid3<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsDiv<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsDiv<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsDiv<S>,
+    F: Fn(Grid3Block<'_, G, S>) -> T,
+    S: Scalar,
+{
+    type Item = T;
+    type XBound = <G as Grid3<S>>::XBound;
+    type YBound = <G as Grid3<S>>::YBound;
+    type ZBound = <G as Grid3<S>>::ZBound;
+    
+    fn x_bound(&self) -> Self::XBound { self.inner.x_bound().div(self.factor.x) }
+    fn y_bound(&self) -> Self::YBound { self.inner.y_bound().div(self.factor.y) }
+    fn z_bound(&self) -> Self::ZBound { self.inner.z_bound().div(self.factor.z) }
+}
+
+impl<G, F, T, S> Grid3Len<S> for Grid3Downscale<G, F, T, S>
+where
+    G: Grid3Len<S>,
+    F: Fn(Grid3Block<'_, G, S>) -> T,
+    S: Scalar,
+{}
+
+impl<G, F, T, S> Grid3Get<S> for Grid3Downscale<G, F, T, S>
+where
+    G: Grid3<S> + Grid3Get<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsDiv<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsDiv<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsDiv<S>,
+    F: Fn(Grid3Block<'_, G, S>) -> T,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        if !self.in_bounds(coord) {
+            panic!("invalid index {:?}", coord);
+        }
+        let Vector3 { x, y, z } = coord;
+        let start = Vector3 {
+            x: x * self.factor.x,
+            y: y * self.factor.y,
+            z: z * self.factor.z,
+        };
+        (self.func)(Grid3Block {
+            inner: &self.inner,
+            start,
+            factor: self.factor,
+            next: Vector3 { x: S::ZERO, y: S::ZERO, z: S::ZERO },
+        })
+    }
+}
+
+/// Iterator over the elements of one block of a grid being
+/// downscaled, with x varying fastest.
+pub struct Grid3Block<'a, G, S = i32> {
+    inner: &'a G,
+    start: Vector3<S>,
+    factor: Vector3<S>,
+    next: Vector3<S>,
+}
+
+impl<'a, G, S> Grid3Block<'a, G, S>
+where
+    S: Scalar,
+{
+    /// Coordinate of the block's first element in the inner grid.
+    pub fn start<I>(&self) -> I
+    where
+        I: From<Vector3<S>> 
+    {
+        I::from(self.start)
+    }
+}
+
+impl<'a, G, S> Iterator for Grid3Block<'a, G, S>
+where
+    G: Grid3Get<S>,
+    S: Scalar,
+{
+    type Item = <G as Grid3<S>>::Item;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next.z < self.factor.z {
+            let coord = Vector3 {
+                x: self.start.x + self.next.x,
+                y: self.start.y + self.next.y,
+                z: self.start.z + self.next.z,
+            };
+            
+            self.next.x = self.next.x + S::ONE;
+            if self.next.x == self.factor.x {
+                self.next.x = S::ZERO;
+                self.next.y = self.next.y + S::ONE;
+                if self.next.y == self.factor.y {
+                    self.next.y = S::ZERO;
+                    self.next.z = self.next.z + S::ONE;
+                }
+            }
+            
+            if self.inner.in_bounds(coord) {
+                return Some(self.inner.get(coord));
+            }
+        }
+        None
+    }
+}
@@ -1,6 +1,7 @@
 //! Flattening a Grid3 of Grid3.
 
 use crate::{
+    scalar::Scalar,
     grid3::*,
     range::RangeBoundsTimes,
 };
@@ -11,35 +12,37 @@ use mint::Vector3;
 /// This unfortunately cannot detect ahead-of-time bugs
 /// if the inner grid has length less than the stride, 
 /// so panics will probably occur if that happens.
-pub struct Grid3Flat<G>
+pub struct Grid3Flat<G, S = i32>
 where
-    G: Grid3,
-    <G as Grid3>::Item: Grid3,
-    <G as Grid3>::XBound: Clone,
-    <G as Grid3>::YBound: Clone,
-    <G as Grid3>::ZBound: Clone,
+    G: Grid3<S>,
+    <G as Grid3<S>>::Item: Grid3<S>,
+    <G as Grid3<S>>::XBound: Clone,
+    <G as Grid3<S>>::YBound: Clone,
+    <G as Grid3<S>>::ZBound: Clone,
+    S: Scalar,
 {
     doublegrid: G,
-    stride: Vector3<i32>,
-    new_xbound: <G as Grid3>::XBound,
-    new_ybound: <G as Grid3>::YBound,
-    new_zbound: <G as Grid3>::ZBound,
+    stride: Vector3<S>,
+    new_xbound: <G as Grid3<S>>::XBound,
+    new_ybound: <G as Grid3<S>>::YBound,
+    new_zbound: <G as Grid3<S>>::ZBound,
 }
 
-impl<G> Grid3Flat<G>
+impl<G, S> Grid3Flat<G, S>
 where
-    G: Grid3,
-    <G as Grid3>::Item: Grid3,
-    <G as Grid3>::XBound: Clone,
-    <G as Grid3>::YBound: Clone,
-    <G as Grid3>::ZBound: Clone,
+    G: Grid3<S>,
+    <G as Grid3<S>>::Item: Grid3<S>,
+    <G as Grid3<S>>::XBound: Clone,
+    <G as Grid3<S>>::YBound: Clone,
+    <G as Grid3<S>>::ZBound: Clone,
+    S: Scalar,
 {
     pub fn new<I>(doublegrid: G, stride: I) -> Self 
     where
-        I: Into<Vector3<i32>>,
-        <G as Grid3>::XBound: RangeBoundsTimes,
-        <G as Grid3>::YBound: RangeBoundsTimes,
-        <G as Grid3>::ZBound: RangeBoundsTimes,
+        I: Into<Vector3<S>>,
+        <G as Grid3<S>>::XBound: RangeBoundsTimes<S>,
+        <G as Grid3<S>>::YBound: RangeBoundsTimes<S>,
+        <G as Grid3<S>>::ZBound: RangeBoundsTimes<S>,
     {
         let stride = stride.into();
         let new_xbound = doublegrid.x_bound().times(stride.x);
@@ -56,14 +59,14 @@ where
     
     pub fn stride<I>(&self) -> I
     where
-        I: From<Vector3<i32>> 
+        I: From<Vector3<S>> 
     {
         I::from(self.stride)
     }
     
     pub fn outer_inner_coord<I>(&self, coord: I) -> (I, I)
     where
-        I: Into<Vector3<i32>> + From<Vector3<i32>>
+        I: Into<Vector3<S>> + From<Vector3<S>>
     {
         let Vector3 { x, y, z } = coord.into();
 
@@ -71,13 +74,13 @@ where
         let stride_y = self.stride.y;
         let stride_z = self.stride.z;
         
-        let rem_x = ((x % stride_x) + stride_x) % stride_x;
-        let rem_y = ((y % stride_y) + stride_y) % stride_y;
-        let rem_z = ((z % stride_z) + stride_z) % stride_z;
+        let rem_x = x.rem_euclid(stride_x);
+        let rem_y = y.rem_euclid(stride_y);
+        let rem_z = z.rem_euclid(stride_z);
         
-        let div_x = (x - rem_x) / stride_x;
-        let div_y = (y - rem_y) / stride_y;
-        let div_z = (z - rem_z) / stride_z;
+        let div_x = x.div_euclid(stride_x);
+        let div_y = y.div_euclid(stride_y);
+        let div_z = z.div_euclid(stride_z);
         
         let rem = Vector3 { x: rem_x, y: rem_y, z: rem_z, };
         let div = Vector3 { x: div_x, y: div_y, z: div_z, };
@@ -86,86 +89,91 @@ where
     }
 }
 
-impl<G> Grid3 for Grid3Flat<G>
+impl<G, S> Grid3<S> for Grid3Flat<G, S>
 where
-    G: Grid3,
-    <G as Grid3>::Item: Grid3,
-    <G as Grid3>::XBound: Clone,
-    <G as Grid3>::YBound: Clone,
-    <G as Grid3>::ZBound: Clone,
+    G: Grid3<S>,
+    <G as Grid3<S>>::Item: Grid3<S>,
+    <G as Grid3<S>>::XBound: Clone,
+    <G as Grid3<S>>::YBound: Clone,
+    <G as Grid3<S>>::ZBound: Clone,
+    S: Scalar,
 {
-    type Item = <<G as Grid3>::Item as Grid3>::Item;
-    type XBound = <G as Grid3>::XBound;
-    type YBound = <G as Grid3>::YBound;
-    type ZBound = <G as Grid3>::ZBound;
+    type Item = <<G as Grid3<S>>::Item as Grid3<S>>::Item;
+    type XBound = <G as Grid3<S>>::XBound;
+    type YBound = <G as Grid3<S>>::YBound;
+    type ZBound = <G as Grid3<S>>::ZBound;
     
-    fn x_bound(&self) -> <G as Grid3>::XBound { self.new_xbound.clone() }
-    fn y_bound(&self) -> <G as Grid3>::YBound { self.new_ybound.clone() }
-    fn z_bound(&self) -> <G as Grid3>::ZBound { self.new_zbound.clone() }
+    fn x_bound(&self) -> <G as Grid3<S>>::XBound { self.new_xbound.clone() }
+    fn y_bound(&self) -> <G as Grid3<S>>::YBound { self.new_ybound.clone() }
+    fn z_bound(&self) -> <G as Grid3<S>>::ZBound { self.new_zbound.clone() }
 }
 
-impl<G> Grid3Get for Grid3Flat<G>
+impl<G, S> Grid3Get<S> for Grid3Flat<G, S>
 where
-    G: Grid3 + Grid3Ref,
-    <G as Grid3>::Item: Grid3 + Grid3Get,
-    <G as Grid3>::XBound: Clone,
-    <G as Grid3>::YBound: Clone,
-    <G as Grid3>::ZBound: Clone,
+    G: Grid3<S> + Grid3Ref<S>,
+    <G as Grid3<S>>::Item: Grid3<S> + Grid3Get<S>,
+    <G as Grid3<S>>::XBound: Clone,
+    <G as Grid3<S>>::YBound: Clone,
+    <G as Grid3<S>>::ZBound: Clone,
+    S: Scalar,
 {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let (outer, inner) = self.outer_inner_coord(coord.into());
         self.doublegrid.idx(outer).get(inner)
     }
 }
 
-impl<G> Grid3Set for Grid3Flat<G>
+impl<G, S> Grid3Set<S> for Grid3Flat<G, S>
 where
-    G: Grid3 + Grid3Mut,
-    <G as Grid3>::Item: Grid3 + Grid3Set,
-    <G as Grid3>::XBound: Clone,
-    <G as Grid3>::YBound: Clone,
-    <G as Grid3>::ZBound: Clone
+    G: Grid3<S> + Grid3Mut<S>,
+    <G as Grid3<S>>::Item: Grid3<S> + Grid3Set<S>,
+    <G as Grid3<S>>::XBound: Clone,
+    <G as Grid3<S>>::YBound: Clone,
+    <G as Grid3<S>>::ZBound: Clone,
+    S: Scalar,
 {
     fn set<I>(&mut self, coord: I, elem: Self::Item)
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let (outer, inner) = self.outer_inner_coord(coord.into());
         self.doublegrid.midx(outer).set(inner, elem);
     }
 }
 
-impl<G> Grid3Ref for Grid3Flat<G>
+impl<G, S> Grid3Ref<S> for Grid3Flat<G, S>
 where
-    G: Grid3 + Grid3Ref,
-    <G as Grid3>::Item: Grid3 + Grid3Ref,
-    <G as Grid3>::XBound: Clone,
-    <G as Grid3>::YBound: Clone,
-    <G as Grid3>::ZBound: Clone
+    G: Grid3<S> + Grid3Ref<S>,
+    <G as Grid3<S>>::Item: Grid3<S> + Grid3Ref<S>,
+    <G as Grid3<S>>::XBound: Clone,
+    <G as Grid3<S>>::YBound: Clone,
+    <G as Grid3<S>>::ZBound: Clone,
+    S: Scalar,
 {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let (outer, inner) = self.outer_inner_coord(coord.into());
         self.doublegrid.idx(outer).idx(inner)
     }
 }
 
-impl<G> Grid3Mut for Grid3Flat<G>
+impl<G, S> Grid3Mut<S> for Grid3Flat<G, S>
 where
-    G: Grid3 + Grid3Mut,
-    <G as Grid3>::Item: Grid3 + Grid3Mut,
-    <G as Grid3>::XBound: Clone,
-    <G as Grid3>::YBound: Clone,
-    <G as Grid3>::ZBound: Clone
+    G: Grid3<S> + Grid3Mut<S>,
+    <G as Grid3<S>>::Item: Grid3<S> + Grid3Mut<S>,
+    <G as Grid3<S>>::XBound: Clone,
+    <G as Grid3<S>>::YBound: Clone,
+    <G as Grid3<S>>::ZBound: Clone,
+    S: Scalar,
 {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let (outer, inner) = self.outer_inner_coord(coord.into());
         self.doublegrid.midx(outer).midx(inner)
@@ -0,0 +1,254 @@
+//! Undo/redo history.
+
+use crate::{
+    scalar::Scalar,
+    grid3::*,
+};
+use mint::Vector3;
+use std::collections::{HashSet, VecDeque};
+
+/// Old values of the coordinates written by one transaction,
+/// in the order they were first written.
+type Edits<T, S> = Vec<(Vector3<S>, T)>;
+
+/// Records old values of writes, so that they can be undone
+/// and redone.
+///
+/// Writes between `begin_transaction` and `commit` are undone
+/// together, and only the first write to each coordinate is
+/// recorded. Writes outside a transaction are each their own
+/// transaction. Any new transaction clears the redo history.
+pub struct Grid3Journaled<G, S = i32>
+where
+    G: Grid3<S>,
+    S: Scalar,
+{
+    inner: G,
+    max_transactions: usize,
+    open: Option<(HashSet<Vector3<S>>, Edits<<G as Grid3<S>>::Item, S>)>,
+    undo: VecDeque<Edits<<G as Grid3<S>>::Item, S>>,
+    redo: Vec<Edits<<G as Grid3<S>>::Item, S>>,
+}
+
+impl<G, S> Grid3Journaled<G, S>
+where
+    G: Grid3<S>,
+    S: Scalar,
+{
+    /// Keep up to max_transactions transactions of undo history.
+    pub fn new(inner: G, max_transactions: usize) -> Self {
+        Grid3Journaled {
+            inner,
+            max_transactions,
+            open: None,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+        }
+    }
+    
+    pub fn inner(&self) -> &G {
+        &self.inner
+    }
+    
+    pub fn into_inner(self) -> G {
+        self.inner
+    }
+    
+    /// Begin grouping writes into one transaction.
+    ///
+    /// Panics if a transaction is already open.
+    pub fn begin_transaction(&mut self) {
+        if self.open.is_some() {
+            panic!("transaction already open");
+        }
+        self.open = Some((HashSet::new(), Vec::new()));
+    }
+    
+    /// End the open transaction, if any.
+    pub fn commit(&mut self) {
+        if let Some((_, edits)) = self.open.take() {
+            self.push_undo(edits);
+        }
+    }
+    
+    pub fn in_transaction(&self) -> bool {
+        self.open.is_some()
+    }
+    
+    /// Number of transactions which can be undone.
+    pub fn undo_count(&self) -> usize {
+        self.undo.len()
+    }
+    
+    /// Number of transactions which can be redone.
+    pub fn redo_count(&self) -> usize {
+        self.redo.len()
+    }
+    
+    /// Discard all history.
+    pub fn clear_history(&mut self) {
+        self.open = None;
+        self.undo.clear();
+        self.redo.clear();
+    }
+    
+    fn push_undo(&mut self, edits: Edits<<G as Grid3<S>>::Item, S>) {
+        if edits.is_empty() {
+            return;
+        }
+        self.redo.clear();
+        self.undo.push_back(edits);
+        while self.undo.len() > self.max_transactions {
+            self.undo.pop_front();
+        }
+    }
+    
+    /// Record the old value at a coordinate, before writing to it.
+    fn record(&mut self, coord: Vector3<S>)
+    where
+        G: Grid3Get<S>,
+    {
+        match self.open {
+            Some((ref mut written, ref mut edits)) => {
+                if written.insert(coord) {
+                    edits.push((coord, self.inner.get(coord)));
+                }
+            }
+            None => {
+                let old = self.inner.get(coord);
+                self.push_undo(vec![(coord, old)]);
+            }
+        }
+    }
+    
+    /// Write edits into the inner grid, returning the values
+    /// they replaced, in reverse order.
+    fn swap(&mut self, edits: Edits<<G as Grid3<S>>::Item, S>) -> Edits<<G as Grid3<S>>::Item, S>
+    where
+        G: Grid3Get<S> + Grid3Set<S>,
+    {
+        edits.into_iter()
+            .rev()
+            .map(|(coord, value)| {
+                let replaced = self.inner.get(coord);
+                self.inner.set(coord, value);
+                (coord, replaced)
+            })
+            .collect()
+    }
+    
+    /// Undo the most recent transaction, committing the open
+    /// transaction first.
+    ///
+    /// Returns whether there was anything to undo.
+    pub fn undo(&mut self) -> bool
+    where
+        G: Grid3Get<S> + Grid3Set<S>,
+    {
+        self.commit();
+        match self.undo.pop_back() {
+            Some(edits) => {
+                let redo = self.swap(edits);
+                self.redo.push(redo);
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Redo the most recently undone transaction, committing the
+    /// open transaction first.
+    ///
+    /// Returns whether there was anything to redo.
+    pub fn redo(&mut self) -> bool
+    where
+        G: Grid3Get<S> + Grid3Set<S>,
+    {
+        self.commit();
+        match self.redo.pop() {
+            Some(edits) => {
+                let undo = self.swap(edits);
+                self.undo.push_back(undo);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<G, S> Grid3<S> for Grid3Journaled<G, S>
+where
+    G: Grid3<S>,
+    S: Scalar,
+{
+    type Item = <G as Grid3<S>>::Item;
+    type XBound = <G as Grid3<S>>::XBound;
+    type YBound = <G as Grid3<S>>::YBound;
+    type ZBound = <G as Grid3<S>>::ZBound;
+    
+    fn x_bound(&self) -> Self::XBound { self.inner.x_bound() }
+    fn y_bound(&self) -> Self::YBound { self.inner.y_bound() }
+    fn z_bound(&self) -> Self::ZBound { self.inner.z_bound() }
+}
+
+impl<G, S> Grid3Len<S> for Grid3Journaled<G, S>
+where
+    G: Grid3Len<S>,
+    S: Scalar,
+{}
+
+impl<G, S> Grid3Get<S> for Grid3Journaled<G, S>
+where
+    G: Grid3Get<S>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        self.inner.get(coord)
+    }
+}
+
+impl<G, S> Grid3Set<S> for Grid3Journaled<G, S>
+where
+    G: Grid3Get<S> + Grid3Set<S>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        self.record(coord);
+        self.inner.set(coord, elem);
+    }
+}
+
+impl<G, S> Grid3Ref<S> for Grid3Journaled<G, S>
+where
+    G: Grid3Ref<S>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        self.inner.idx(coord)
+    }
+}
+
+impl<G, S> Grid3Mut<S> for Grid3Journaled<G, S>
+where
+    G: Grid3Get<S> + Grid3Mut<S>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        self.record(coord);
+        self.inner.midx(coord)
+    }
+}
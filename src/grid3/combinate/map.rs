@@ -1,24 +1,19 @@
 //! Dependent by-value Grid3 mapping.
 
-use crate::grid3::*;
+use crate::{
+    scalar::Scalar,
+    grid3::*,
+};
 use mint::Vector3;
 use std::marker::PhantomData;
 
-pub struct Grid3Map<G, F, T> 
-where
-    G: Grid3,
-    F: Fn(<G as Grid3>::Item) -> T,
-{
+pub struct Grid3Map<G, F, T> {
     inner: G,
     func: F,
     p: PhantomData<fn(T)>
 }
 
-impl<G, F, T> Grid3Map<G, F, T>
-where
-    G: Grid3,
-    F: Fn(<G as Grid3>::Item) -> T,
-{
+impl<G, F, T> Grid3Map<G, F, T> {
     pub fn new(inner: G, func: F) -> Self {
         Grid3Map {
             inner,
@@ -28,35 +23,38 @@ where
     }
 }
 
-impl<G, F, T> Grid3 for Grid3Map<G, F, T>
+impl<G, F, T, S> Grid3<S> for Grid3Map<G, F, T>
 where
-    G: Grid3,
-    F: Fn(<G as Grid3>::Item) -> T,
+    G: Grid3<S>,
+    F: Fn(<G as Grid3<S>>::Item) -> T,
+    S: Scalar,
 {
     type Item = T;
-    type XBound = <G as Grid3>::XBound;
-    type YBound = <G as Grid3>::YBound;
-    type ZBound = <G as Grid3>::ZBound;
+    type XBound = <G as Grid3<S>>::XBound;
+    type YBound = <G as Grid3<S>>::YBound;
+    type ZBound = <G as Grid3<S>>::ZBound;
     
     fn x_bound(&self) -> Self::XBound { self.inner.x_bound() }
     fn y_bound(&self) -> Self::YBound { self.inner.y_bound() }
     fn z_bound(&self) -> Self::ZBound { self.inner.z_bound() }
 }
 
-impl<G, F, T> Grid3Len for Grid3Map<G, F, T>
+impl<G, F, T, S> Grid3Len<S> for Grid3Map<G, F, T>
 where
-    G: Grid3 + Grid3Len,
-    F: Fn(<G as Grid3>::Item) -> T,
+    G: Grid3<S> + Grid3Len<S>,
+    F: Fn(<G as Grid3<S>>::Item) -> T,
+    S: Scalar,
 {}
 
-impl<G, F, T> Grid3Get for Grid3Map<G, F, T>
+impl<G, F, T, S> Grid3Get<S> for Grid3Map<G, F, T>
 where
-    G: Grid3 + Grid3Get,
-    F: Fn(<G as Grid3>::Item) -> T,
+    G: Grid3<S> + Grid3Get<S>,
+    F: Fn(<G as Grid3<S>>::Item) -> T,
+    S: Scalar,
 {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         (self.func)(self.inner.get(coord))
     }
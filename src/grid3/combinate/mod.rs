@@ -6,8 +6,14 @@ pub mod wrapping;
 pub mod neworigin;
 pub mod flatten;
 pub mod oobhandler;
+pub mod plane;
+pub mod upscale;
+pub mod downscale;
+pub mod tracked;
+pub mod journaled;
 
 use super::*;
+use crate::scalar::Scalar;
 use mint::Vector3;
 use std::{
     ops::{Deref, DerefMut},
@@ -15,15 +21,16 @@ use std::{
 
 // ==== elevate pointer types ====
 
-impl<T> Grid3 for T
+impl<T, S> Grid3<S> for T
 where
     T: Deref,
-    <T as Deref>::Target: Grid3
+    <T as Deref>::Target: Grid3<S>,
+    S: Scalar,
 {
-    type Item = <<T as Deref>::Target as Grid3>::Item;
-    type XBound = <<T as Deref>::Target as Grid3>::XBound;
-    type YBound = <<T as Deref>::Target as Grid3>::YBound;
-    type ZBound = <<T as Deref>::Target as Grid3>::ZBound;
+    type Item = <<T as Deref>::Target as Grid3<S>>::Item;
+    type XBound = <<T as Deref>::Target as Grid3<S>>::XBound;
+    type YBound = <<T as Deref>::Target as Grid3<S>>::YBound;
+    type ZBound = <<T as Deref>::Target as Grid3<S>>::ZBound;
     
     fn x_bound(&self) -> Self::XBound {
         T::deref(self).x_bound()
@@ -38,59 +45,64 @@ where
     }
 }
 
-impl<T> Grid3Len for T
+impl<T, S> Grid3Len<S> for T
 where
     T: Deref,
-    <T as Deref>::Target: Grid3Len
+    <T as Deref>::Target: Grid3Len<S>,
+    S: Scalar,
 {}
 
-impl<T> Grid3Get for T
+impl<T, S> Grid3Get<S> for T
 where
     T: Deref,
-    <T as Deref>::Target: Grid3Get
+    <T as Deref>::Target: Grid3Get<S>,
+    S: Scalar,
 {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         T::deref(self).get(coord)
     }
 }
 
-impl<T> Grid3Ref for T
+impl<T, S> Grid3Ref<S> for T
 where
     T: Deref,
-    <T as Deref>::Target: Grid3Ref
+    <T as Deref>::Target: Grid3Ref<S>,
+    S: Scalar,
 {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         T::deref(self).idx(coord)
     }
 }
 
-impl<T> Grid3Set for T
+impl<T, S> Grid3Set<S> for T
 where
     T: Deref + DerefMut,
-    <T as Deref>::Target: Grid3Set
+    <T as Deref>::Target: Grid3Set<S>,
+    S: Scalar,
 {
     fn set<I>(&mut self, coord: I, elem: Self::Item)
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         T::deref_mut(self).set(coord, elem)
     }
 }
 
-impl<T> Grid3Mut for T
+impl<T, S> Grid3Mut<S> for T
 where
     T: Deref + DerefMut,
-    <T as Deref>::Target: Grid3Mut
+    <T as Deref>::Target: Grid3Mut<S>,
+    S: Scalar,
 {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         T::deref_mut(self).midx(coord)
     }
@@ -1,38 +1,41 @@
 //! Change to origin.
 
 use crate::{
+    scalar::Scalar,
     grid3::*,
     range::RangeBoundsPlus,
 };
 use mint::Vector3;
 
 /// <0, 0> in the inner grid will be new_origin in this grid.
-pub struct Grid3NewOrigin<G> 
+pub struct Grid3NewOrigin<G, S = i32> 
 where
-    G: Grid3,
-    <G as Grid3>::XBound: RangeBoundsPlus,
-    <G as Grid3>::YBound: RangeBoundsPlus,
-    <G as Grid3>::ZBound: RangeBoundsPlus,
+    G: Grid3<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
     inner: G,
-    new_origin: Vector3<i32>,
+    new_origin: Vector3<S>,
     
     // cache these (arbitrary decision)
-    new_xbound: <<G as Grid3>::XBound as RangeBoundsPlus>::Output,
-    new_ybound: <<G as Grid3>::YBound as RangeBoundsPlus>::Output,
-    new_zbound: <<G as Grid3>::ZBound as RangeBoundsPlus>::Output,
+    new_xbound: <<G as Grid3<S>>::XBound as RangeBoundsPlus<S>>::Output,
+    new_ybound: <<G as Grid3<S>>::YBound as RangeBoundsPlus<S>>::Output,
+    new_zbound: <<G as Grid3<S>>::ZBound as RangeBoundsPlus<S>>::Output,
 }
 
-impl<G> Grid3NewOrigin<G> 
+impl<G, S> Grid3NewOrigin<G, S> 
 where
-    G: Grid3,
-    <G as Grid3>::XBound: RangeBoundsPlus,
-    <G as Grid3>::YBound: RangeBoundsPlus,
-    <G as Grid3>::ZBound: RangeBoundsPlus,
+    G: Grid3<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
     pub fn new<I>(inner: G, new_origin: I) -> Self 
     where
-        I: Into<Vector3<i32>>,
+        I: Into<Vector3<S>>,
     {
         let new_origin = new_origin.into();
         let new_xbound = inner.x_bound().plus(new_origin.x);
@@ -50,98 +53,103 @@ where
     
     pub fn new_origin<I>(&self) -> I 
     where
-        I: From<Vector3<i32>>
+        I: From<Vector3<S>>
     {
         I::from(self.new_origin)
     }
     
     pub fn adjust_coord<I>(&self, coord: I) -> I 
     where
-        I: From<Vector3<i32>> + Into<Vector3<i32>>
+        I: From<Vector3<S>> + Into<Vector3<S>>
     {
         let mut coord = coord.into();
-        coord.x -= self.new_origin.x;
-        coord.y -= self.new_origin.y;
-        coord.z -= self.new_origin.z;
+        coord.x = coord.x - self.new_origin.x;
+        coord.y = coord.y - self.new_origin.y;
+        coord.z = coord.z - self.new_origin.z;
         I::from(coord)
     }
 }
 
-impl<G> Grid3 for Grid3NewOrigin<G> 
+impl<G, S> Grid3<S> for Grid3NewOrigin<G, S> 
 where
-    G: Grid3,
-    <G as Grid3>::XBound: RangeBoundsPlus,
-    <G as Grid3>::YBound: RangeBoundsPlus,
-    <G as Grid3>::ZBound: RangeBoundsPlus,
+    G: Grid3<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
-    type Item = <G as Grid3>::Item;
-    type XBound = <<G as Grid3>::XBound as RangeBoundsPlus>::Output;
-    type YBound = <<G as Grid3>::YBound as RangeBoundsPlus>::Output;
-    type ZBound = <<G as Grid3>::ZBound as RangeBoundsPlus>::Output;
+    type Item = <G as Grid3<S>>::Item;
+    type XBound = <<G as Grid3<S>>::XBound as RangeBoundsPlus<S>>::Output;
+    type YBound = <<G as Grid3<S>>::YBound as RangeBoundsPlus<S>>::Output;
+    type ZBound = <<G as Grid3<S>>::ZBound as RangeBoundsPlus<S>>::Output;
     
     fn x_bound(&self) -> Self::XBound { self.new_xbound.clone() }
     fn y_bound(&self) -> Self::YBound { self.new_ybound.clone() }
     fn z_bound(&self) -> Self::ZBound { self.new_zbound.clone() }
 }
 
-impl<G> Grid3Get for Grid3NewOrigin<G> 
+impl<G, S> Grid3Get<S> for Grid3NewOrigin<G, S> 
 where
-    G: Grid3 + Grid3Get,
-    <G as Grid3>::XBound: RangeBoundsPlus,
-    <G as Grid3>::YBound: RangeBoundsPlus,
-    <G as Grid3>::ZBound: RangeBoundsPlus,
+    G: Grid3<S> + Grid3Get<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = self.adjust_coord(coord.into());
         self.inner.get(coord)
     }
 }
 
-impl<G> Grid3Set for Grid3NewOrigin<G> 
+impl<G, S> Grid3Set<S> for Grid3NewOrigin<G, S> 
 where
-    G: Grid3 + Grid3Set,
-    <G as Grid3>::XBound: RangeBoundsPlus,
-    <G as Grid3>::YBound: RangeBoundsPlus,
-    <G as Grid3>::ZBound: RangeBoundsPlus,
+    G: Grid3<S> + Grid3Set<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
     fn set<I>(&mut self, coord: I, elem: Self::Item)
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = self.adjust_coord(coord.into());
         self.inner.set(coord, elem);
     }
 }
 
-impl<G> Grid3Ref for Grid3NewOrigin<G> 
+impl<G, S> Grid3Ref<S> for Grid3NewOrigin<G, S> 
 where
-    G: Grid3 + Grid3Ref,
-    <G as Grid3>::XBound: RangeBoundsPlus,
-    <G as Grid3>::YBound: RangeBoundsPlus,
-    <G as Grid3>::ZBound: RangeBoundsPlus,
+    G: Grid3<S> + Grid3Ref<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = self.adjust_coord(coord.into());
         self.inner.idx(coord)
     }
 }
 
-impl<G> Grid3Mut for Grid3NewOrigin<G> 
+impl<G, S> Grid3Mut<S> for Grid3NewOrigin<G, S> 
 where
-    G: Grid3 + Grid3Mut,
-    <G as Grid3>::XBound: RangeBoundsPlus,
-    <G as Grid3>::YBound: RangeBoundsPlus,
-    <G as Grid3>::ZBound: RangeBoundsPlus,
+    G: Grid3<S> + Grid3Mut<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsPlus<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsPlus<S>,
+    S: Scalar,
 {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = self.adjust_coord(coord.into());
         self.inner.midx(coord)
@@ -1,6 +1,9 @@
 //! Out-of-bounds index handler.
 
-use crate::grid3::*;
+use crate::{
+    scalar::Scalar,
+    grid3::*,
+};
 use mint::Vector3;
 use std::{
     ops::RangeFull,
@@ -8,23 +11,13 @@ use std::{
 };
 
 /// All values outside of a grid are supplied by a function.
-pub struct Grid3OobHandler<G, I, F>
-where
-    G: Grid3,
-    I: From<Vector3<i32>>,
-    F: Fn(I) -> <G as Grid3>::Item,
-{
+pub struct Grid3OobHandler<G, I, F> {
     inner: G,
     func: F,
     p: PhantomData<fn(I)>,
 }
 
-impl<G, I, F> Grid3OobHandler<G, I, F>
-where
-    G: Grid3,
-    I: From<Vector3<i32>>,
-    F: Fn(I) -> <G as Grid3>::Item,
-{
+impl<G, I, F> Grid3OobHandler<G, I, F> {
     pub fn new(inner: G, func: F) -> Self {
         Grid3OobHandler {
             inner,
@@ -34,13 +27,14 @@ where
     }
 }
 
-impl<G, I, F> Grid3 for Grid3OobHandler<G, I, F>
+impl<G, I, F, S> Grid3<S> for Grid3OobHandler<G, I, F>
 where
-    G: Grid3,
-    I: From<Vector3<i32>>,
-    F: Fn(I) -> <G as Grid3>::Item,
+    G: Grid3<S>,
+    I: From<Vector3<S>>,
+    F: Fn(I) -> <G as Grid3<S>>::Item,
+    S: Scalar,
 {
-    type Item = <G as Grid3>::Item;
+    type Item = <G as Grid3<S>>::Item;
     type XBound = RangeFull;
     type YBound = RangeFull;
     type ZBound = RangeFull;
@@ -50,15 +44,16 @@ where
     fn z_bound(&self) -> RangeFull { RangeFull }
 }
 
-impl<G, I, F> Grid3Get for Grid3OobHandler<G, I, F>
+impl<G, I, F, S> Grid3Get<S> for Grid3OobHandler<G, I, F>
 where
-    G: Grid3 + Grid3Get,
-    I: From<Vector3<i32>>,
-    F: Fn(I) -> <G as Grid3>::Item,
+    G: Grid3<S> + Grid3Get<S>,
+    I: From<Vector3<S>>,
+    F: Fn(I) -> <G as Grid3<S>>::Item,
+    S: Scalar,
 {
     fn get<C>(&self, coord: C) -> Self::Item
     where
-        C: Into<Vector3<i32>>
+        C: Into<Vector3<S>>
     {
         let coord = coord.into();
         if self.inner.in_bounds(coord) {
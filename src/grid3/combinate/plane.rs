@@ -0,0 +1,330 @@
+//! 2D planes through a Grid3.
+
+use crate::{
+    scalar::Scalar,
+    range::Range0To,
+    grid2::{Grid2, Grid2Len, Grid2Get, Grid2Set, Grid2Ref, Grid2Mut},
+    grid3::*,
+};
+use mint::{Vector2, Vector3};
+
+/// Plane of a Grid3 with a fixed z coordinate, viewed as a Grid2.
+///
+/// Grid2 x corresponds to Grid3 x, and Grid2 y corresponds
+/// to Grid3 y.
+pub struct Grid3PlaneXY<G, S = i32> {
+    inner: G,
+    z: S,
+}
+
+impl<G, S> Grid3PlaneXY<G, S>
+where
+    G: Grid3<S>,
+    S: Scalar,
+{
+    /// Panics if z is outside the inner grid's z bound.
+    pub fn new(inner: G, z: S) -> Self {
+        if !inner.z_bound().contains(&z) {
+            panic!("plane z={:?} is outside of grid", z);
+        }
+        Grid3PlaneXY {
+            inner,
+            z,
+        }
+    }
+    
+    pub fn to_3d<I>(&self, coord: I) -> Vector3<S>
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = coord.into();
+        Vector3 { x: coord.x, y: coord.y, z: self.z }
+    }
+}
+
+impl<G, S> Grid2<S> for Grid3PlaneXY<G, S>
+where
+    G: Grid3<S>,
+    S: Scalar,
+{
+    type Item = <G as Grid3<S>>::Item;
+    type XBound = <G as Grid3<S>>::XBound;
+    type YBound = <G as Grid3<S>>::YBound;
+    
+    fn x_bound(&self) -> Self::XBound { self.inner.x_bound() }
+    fn y_bound(&self) -> Self::YBound { self.inner.y_bound() }
+}
+
+impl<G, S> Grid2Len<S> for Grid3PlaneXY<G, S>
+where
+    G: Grid3<S, XBound=Range0To<S>, YBound=Range0To<S>>,
+    S: Scalar,
+{}
+
+impl<G, S> Grid2Get<S> for Grid3PlaneXY<G, S>
+where
+    G: Grid3<S> + Grid3Get<S>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.get(self.to_3d(coord))
+    }
+}
+
+impl<G, S> Grid2Set<S> for Grid3PlaneXY<G, S>
+where
+    G: Grid3<S> + Grid3Set<S>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = self.to_3d(coord);
+        self.inner.set(coord, elem);
+    }
+}
+
+impl<G, S> Grid2Ref<S> for Grid3PlaneXY<G, S>
+where
+    G: Grid3<S> + Grid3Ref<S>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.idx(self.to_3d(coord))
+    }
+}
+
+impl<G, S> Grid2Mut<S> for Grid3PlaneXY<G, S>
+where
+    G: Grid3<S> + Grid3Mut<S>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = self.to_3d(coord);
+        self.inner.midx(coord)
+    }
+}
+
+/// Plane of a Grid3 with a fixed y coordinate, viewed as a Grid2.
+///
+/// Grid2 x corresponds to Grid3 x, and Grid2 y corresponds
+/// to Grid3 z.
+pub struct Grid3PlaneXZ<G, S = i32> {
+    inner: G,
+    y: S,
+}
+
+impl<G, S> Grid3PlaneXZ<G, S>
+where
+    G: Grid3<S>,
+    S: Scalar,
+{
+    /// Panics if y is outside the inner grid's y bound.
+    pub fn new(inner: G, y: S) -> Self {
+        if !inner.y_bound().contains(&y) {
+            panic!("plane y={:?} is outside of grid", y);
+        }
+        Grid3PlaneXZ {
+            inner,
+            y,
+        }
+    }
+    
+    pub fn to_3d<I>(&self, coord: I) -> Vector3<S>
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = coord.into();
+        Vector3 { x: coord.x, y: self.y, z: coord.y }
+    }
+}
+
+impl<G, S> Grid2<S> for Grid3PlaneXZ<G, S>
+where
+    G: Grid3<S>,
+    S: Scalar,
+{
+    type Item = <G as Grid3<S>>::Item;
+    type XBound = <G as Grid3<S>>::XBound;
+    type YBound = <G as Grid3<S>>::ZBound;
+    
+    fn x_bound(&self) -> Self::XBound { self.inner.x_bound() }
+    fn y_bound(&self) -> Self::YBound { self.inner.z_bound() }
+}
+
+impl<G, S> Grid2Len<S> for Grid3PlaneXZ<G, S>
+where
+    G: Grid3<S, XBound=Range0To<S>, ZBound=Range0To<S>>,
+    S: Scalar,
+{}
+
+impl<G, S> Grid2Get<S> for Grid3PlaneXZ<G, S>
+where
+    G: Grid3<S> + Grid3Get<S>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.get(self.to_3d(coord))
+    }
+}
+
+impl<G, S> Grid2Set<S> for Grid3PlaneXZ<G, S>
+where
+    G: Grid3<S> + Grid3Set<S>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = self.to_3d(coord);
+        self.inner.set(coord, elem);
+    }
+}
+
+impl<G, S> Grid2Ref<S> for Grid3PlaneXZ<G, S>
+where
+    G: Grid3<S> + Grid3Ref<S>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.idx(self.to_3d(coord))
+    }
+}
+
+impl<G, S> Grid2Mut<S> for Grid3PlaneXZ<G, S>
+where
+    G: Grid3<S> + Grid3Mut<S>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = self.to_3d(coord);
+        self.inner.midx(coord)
+    }
+}
+
+/// Plane of a Grid3 with a fixed x coordinate, viewed as a Grid2.
+///
+/// Grid2 x corresponds to Grid3 y, and Grid2 y corresponds
+/// to Grid3 z.
+pub struct Grid3PlaneYZ<G, S = i32> {
+    inner: G,
+    x: S,
+}
+
+impl<G, S> Grid3PlaneYZ<G, S>
+where
+    G: Grid3<S>,
+    S: Scalar,
+{
+    /// Panics if x is outside the inner grid's x bound.
+    pub fn new(inner: G, x: S) -> Self {
+        if !inner.x_bound().contains(&x) {
+            panic!("plane x={:?} is outside of grid", x);
+        }
+        Grid3PlaneYZ {
+            inner,
+            x,
+        }
+    }
+    
+    pub fn to_3d<I>(&self, coord: I) -> Vector3<S>
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = coord.into();
+        Vector3 { x: self.x, y: coord.x, z: coord.y }
+    }
+}
+
+impl<G, S> Grid2<S> for Grid3PlaneYZ<G, S>
+where
+    G: Grid3<S>,
+    S: Scalar,
+{
+    type Item = <G as Grid3<S>>::Item;
+    type XBound = <G as Grid3<S>>::YBound;
+    type YBound = <G as Grid3<S>>::ZBound;
+    
+    fn x_bound(&self) -> Self::XBound { self.inner.y_bound() }
+    fn y_bound(&self) -> Self::YBound { self.inner.z_bound() }
+}
+
+impl<G, S> Grid2Len<S> for Grid3PlaneYZ<G, S>
+where
+    G: Grid3<S, YBound=Range0To<S>, ZBound=Range0To<S>>,
+    S: Scalar,
+{}
+
+impl<G, S> Grid2Get<S> for Grid3PlaneYZ<G, S>
+where
+    G: Grid3<S> + Grid3Get<S>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.get(self.to_3d(coord))
+    }
+}
+
+impl<G, S> Grid2Set<S> for Grid3PlaneYZ<G, S>
+where
+    G: Grid3<S> + Grid3Set<S>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = self.to_3d(coord);
+        self.inner.set(coord, elem);
+    }
+}
+
+impl<G, S> Grid2Ref<S> for Grid3PlaneYZ<G, S>
+where
+    G: Grid3<S> + Grid3Ref<S>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.idx(self.to_3d(coord))
+    }
+}
+
+impl<G, S> Grid2Mut<S> for Grid3PlaneYZ<G, S>
+where
+    G: Grid3<S> + Grid3Mut<S>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let coord = self.to_3d(coord);
+        self.inner.midx(coord)
+    }
+}
@@ -1,12 +1,13 @@
 //! Sub-view of a Grid3.
 
 use crate::{
-    range::Range0To,
+    range::{Range0To, more_strict},
+    scalar::Scalar,
     grid3::*,
 };
 use mint::Vector3;
 use std::{
-    ops::{RangeBounds, Bound},
+    ops::RangeBounds,
     fmt::Debug,
 };
 
@@ -17,10 +18,9 @@ use std::{
 /// of the valid coordinates in the inner grid.
 pub struct Grid3Slice<G, X, Y, Z> 
 where
-    G: Grid3,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
-    Z: RangeBounds<i32> + Clone,
+    X: Clone,
+    Y: Clone,
+    Z: Clone,
 {
     inner: G,
     x_bound: X,
@@ -28,59 +28,20 @@ where
     z_bound: Z,
 }
 
-/// Verify that a is more strict than b.
-fn more_strict(a: impl RangeBounds<i32>, b: impl RangeBounds<i32>) -> bool {
-    
-    fn lower_inclusive(bound: Bound<&i32>) -> Option<i32> {
-        match bound {
-            Bound::Included(&i) => Some(i),
-            Bound::Excluded(&i) => Some(i + 1),
-            Bound::Unbounded   => None,
-        }
-    }
-    
-    fn upper_inclusive(bound: Bound<&i32>) -> Option<i32> {
-        match bound {
-            Bound::Included(&i) => Some(i),
-            Bound::Excluded(&i) => Some(i - 1),
-            Bound::Unbounded   => None,
-        }
-    }
-    
-    let lower_ok = match (
-        lower_inclusive(a.start_bound()), 
-        lower_inclusive(b.start_bound()),
-    ) {
-        (Some(i1), Some(i2)) => i1 >= i2,
-        (Some(_), None)      => true,
-        (None, Some(_))      => false,
-        (None, None)         => true,
-    };
-    if !lower_ok { return false; }
-    
-    let upper_ok = match (
-        upper_inclusive(a.end_bound()), 
-        upper_inclusive(b.end_bound()),
-    ) {
-        (Some(i1), Some(i2)) => i1 <= i2,
-        (Some(_), None)      => true,
-        (None, Some(_))      => false,
-        (None, None)         => true,
-    };
-    if !upper_ok { return false; }
-    
-    true
-}
-
 impl<G, X, Y, Z> Grid3Slice<G, X, Y, Z>
 where
-    G: Grid3,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
-    Z: RangeBounds<i32> + Clone,
+    X: Clone,
+    Y: Clone,
+    Z: Clone,
 {
     /// Fails if the new bounds are not a subset of the old ones.
-    pub fn try_new(inner: G, new_x: X, new_y: Y, new_z: Z) -> Result<Self, G>
+    pub fn try_new<S>(inner: G, new_x: X, new_y: Y, new_z: Z) -> Result<Self, G>
+    where
+        G: Grid3<S>,
+        X: RangeBounds<S>,
+        Y: RangeBounds<S>,
+        Z: RangeBounds<S>,
+        S: Scalar,
     {
         if more_strict(new_x.clone(), inner.x_bound()) 
             && more_strict(new_y.clone(), inner.y_bound())
@@ -98,14 +59,16 @@ where
     }
     
     /// Panics if the new bounds are not a subset of the old ones.
-    pub fn new(inner: G, new_x: X, new_y: Y, new_z: Z) -> Self
+    pub fn new<S>(inner: G, new_x: X, new_y: Y, new_z: Z) -> Self
     where
-        X: Debug,
-        Y: Debug,
-        Z: Debug,
-        <G as Grid3>::XBound: Debug,
-        <G as Grid3>::YBound: Debug,
-        <G as Grid3>::ZBound: Debug,
+        G: Grid3<S>,
+        X: RangeBounds<S> + Debug,
+        Y: RangeBounds<S> + Debug,
+        Z: RangeBounds<S> + Debug,
+        <G as Grid3<S>>::XBound: Debug,
+        <G as Grid3<S>>::YBound: Debug,
+        <G as Grid3<S>>::ZBound: Debug,
+        S: Scalar,
     {
         if more_strict(new_x.clone(), inner.x_bound()) 
             && more_strict(new_y.clone(), inner.y_bound())
@@ -125,14 +88,15 @@ where
     }
 }
 
-impl<G, X, Y, Z> Grid3 for Grid3Slice<G, X, Y, Z>
+impl<G, X, Y, Z, S> Grid3<S> for Grid3Slice<G, X, Y, Z>
 where
-    G: Grid3,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
-    Z: RangeBounds<i32> + Clone,
+    G: Grid3<S>,
+    X: RangeBounds<S> + Clone,
+    Y: RangeBounds<S> + Clone,
+    Z: RangeBounds<S> + Clone,
+    S: Scalar,
 {
-    type Item = <G as Grid3>::Item;
+    type Item = <G as Grid3<S>>::Item;
     type XBound = X;
     type YBound = Y;
     type ZBound = Z;
@@ -142,21 +106,23 @@ where
     fn z_bound(&self) -> Self::ZBound { self.z_bound.clone() }
 }
 
-impl<G> Grid3Len for Grid3Slice<G, Range0To, Range0To, Range0To> 
+impl<G, S> Grid3Len<S> for Grid3Slice<G, Range0To<S>, Range0To<S>, Range0To<S>> 
 where
-    G: Grid3
+    G: Grid3<S>,
+    S: Scalar,
 {}
 
-impl<G, X, Y, Z> Grid3Get for Grid3Slice<G, X, Y, Z>
+impl<G, X, Y, Z, S> Grid3Get<S> for Grid3Slice<G, X, Y, Z>
 where
-    G: Grid3 + Grid3Get,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
-    Z: RangeBounds<i32> + Clone,
+    G: Grid3<S> + Grid3Get<S>,
+    X: RangeBounds<S> + Clone,
+    Y: RangeBounds<S> + Clone,
+    Z: RangeBounds<S> + Clone,
+    S: Scalar,
 {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         match self.try_get(coord) {
@@ -167,7 +133,7 @@ where
         
     fn try_get<I>(&self, coord: I) -> Option<Self::Item>
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
@@ -178,16 +144,17 @@ where
     }
 }
 
-impl<G, X, Y, Z> Grid3Set for Grid3Slice<G, X, Y, Z>
+impl<G, X, Y, Z, S> Grid3Set<S> for Grid3Slice<G, X, Y, Z>
 where
-    G: Grid3 + Grid3Set,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
-    Z: RangeBounds<i32> + Clone,
+    G: Grid3<S> + Grid3Set<S>,
+    X: RangeBounds<S> + Clone,
+    Y: RangeBounds<S> + Clone,
+    Z: RangeBounds<S> + Clone,
+    S: Scalar,
 {
     fn set<I>(&mut self, coord: I, elem: Self::Item)
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         match self.try_set(coord, elem) {
@@ -198,28 +165,29 @@ where
         
     fn try_set<I>(&mut self, coord: I, elem: Self::Item) -> Result<(), Self::Item> 
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
             self.inner.set(coord, elem);
-            Ok({})
+            Ok(())
         } else {
             Err(elem)
         }
     }
 }
 
-impl<G, X, Y, Z> Grid3Ref for Grid3Slice<G, X, Y, Z>
+impl<G, X, Y, Z, S> Grid3Ref<S> for Grid3Slice<G, X, Y, Z>
 where
-    G: Grid3 + Grid3Ref,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
-    Z: RangeBounds<i32> + Clone,
+    G: Grid3<S> + Grid3Ref<S>,
+    X: RangeBounds<S> + Clone,
+    Y: RangeBounds<S> + Clone,
+    Z: RangeBounds<S> + Clone,
+    S: Scalar,
 {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         match self.try_idx(coord) {
@@ -230,7 +198,7 @@ where
     
     fn try_idx<I>(&self, coord: I) -> Option<&Self::Item>
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
@@ -241,16 +209,17 @@ where
     }
 }
 
-impl<G, X, Y, Z> Grid3Mut for Grid3Slice<G, X, Y, Z>
+impl<G, X, Y, Z, S> Grid3Mut<S> for Grid3Slice<G, X, Y, Z>
 where
-    G: Grid3 + Grid3Mut,
-    X: RangeBounds<i32> + Clone,
-    Y: RangeBounds<i32> + Clone,
-    Z: RangeBounds<i32> + Clone,
+    G: Grid3<S> + Grid3Mut<S>,
+    X: RangeBounds<S> + Clone,
+    Y: RangeBounds<S> + Clone,
+    Z: RangeBounds<S> + Clone,
+    S: Scalar,
 {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         match self.try_midx(coord) {
@@ -261,7 +230,7 @@ where
     
     fn try_midx<I>(&mut self, coord: I) -> Option<&mut Self::Item>
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
@@ -0,0 +1,200 @@
+//! Change tracking.
+
+use crate::{
+    scalar::Scalar,
+    grid3::*,
+};
+use mint::Vector3;
+use std::{
+    collections::HashSet,
+    ops::Range,
+};
+
+/// Records the coordinates written through it.
+///
+/// Writes through `set` and `midx` (and their `try_` variants)
+/// mark their coordinate as dirty, whether or not the value
+/// actually changes. Reads are forwarded unchanged.
+pub struct Grid3Tracked<G, S = i32> {
+    inner: G,
+    dirty: HashSet<Vector3<S>>,
+    // bounding box of dirty, inclusive
+    min: Vector3<S>,
+    max: Vector3<S>,
+}
+
+impl<G, S> Grid3Tracked<G, S>
+where
+    S: Scalar,
+{
+    pub fn new(inner: G) -> Self {
+        Grid3Tracked {
+            inner,
+            dirty: HashSet::new(),
+            min: Vector3 { x: S::ZERO, y: S::ZERO, z: S::ZERO },
+            max: Vector3 { x: S::ZERO, y: S::ZERO, z: S::ZERO },
+        }
+    }
+    
+    pub fn inner(&self) -> &G {
+        &self.inner
+    }
+    
+    /// Mutable access to the inner grid, bypassing tracking.
+    pub fn inner_mut(&mut self) -> &mut G {
+        &mut self.inner
+    }
+    
+    pub fn into_inner(self) -> G {
+        self.inner
+    }
+    
+    fn mark(&mut self, coord: Vector3<S>) {
+        if self.dirty.is_empty() {
+            self.min = coord;
+            self.max = coord;
+        } else {
+            self.min.x = self.min.x.min(coord.x);
+            self.min.y = self.min.y.min(coord.y);
+            self.min.z = self.min.z.min(coord.z);
+            self.max.x = self.max.x.max(coord.x);
+            self.max.y = self.max.y.max(coord.y);
+            self.max.z = self.max.z.max(coord.z);
+        }
+        self.dirty.insert(coord);
+    }
+    
+    pub fn is_dirty<I>(&self, coord: I) -> bool
+    where
+        I: Into<Vector3<S>>
+    {
+        self.dirty.contains(&coord.into())
+    }
+    
+    /// Number of dirty coordinates.
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len()
+    }
+    
+    /// Smallest box containing every dirty coordinate.
+    pub fn dirty_bounds(&self) -> Option<(Range<S>, Range<S>, Range<S>)> {
+        if self.dirty.is_empty() {
+            None
+        } else {
+            Some((
+                self.min.x..self.max.x + S::ONE,
+                self.min.y..self.max.y + S::ONE,
+                self.min.z..self.max.z + S::ONE,
+            ))
+        }
+    }
+    
+    /// Sorted coordinates of the chunks containing dirty coordinates,
+    /// where chunk <i, j, k> spans from `<i, j, k> * chunk_size`
+    /// inclusive to `<i + 1, j + 1, k + 1> * chunk_size` exclusive.
+    pub fn dirty_chunks<I>(&self, chunk_size: I) -> Vec<Vector3<S>>
+    where
+        I: Into<Vector3<S>>
+    {
+        let chunk_size = chunk_size.into();
+        let chunks: HashSet<Vector3<S>> = self.dirty.iter()
+            .map(|c| Vector3 {
+                x: c.x.div_euclid(chunk_size.x),
+                y: c.y.div_euclid(chunk_size.y),
+                z: c.z.div_euclid(chunk_size.z),
+            })
+            .collect();
+        let mut chunks: Vec<Vector3<S>> = chunks.into_iter().collect();
+        chunks.sort_by_key(|c| (c.z, c.y, c.x));
+        chunks
+    }
+    
+    /// Take the dirty coordinates, sorted, and reset tracking.
+    pub fn drain_dirty(&mut self) -> Vec<Vector3<S>> {
+        let mut dirty: Vec<Vector3<S>> = self.dirty.drain().collect();
+        dirty.sort_by_key(|c| (c.z, c.y, c.x));
+        dirty
+    }
+    
+    /// Reset tracking.
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
+}
+
+impl<G, S> Grid3<S> for Grid3Tracked<G, S>
+where
+    G: Grid3<S>,
+    S: Scalar,
+{
+    type Item = <G as Grid3<S>>::Item;
+    type XBound = <G as Grid3<S>>::XBound;
+    type YBound = <G as Grid3<S>>::YBound;
+    type ZBound = <G as Grid3<S>>::ZBound;
+    
+    fn x_bound(&self) -> Self::XBound { self.inner.x_bound() }
+    fn y_bound(&self) -> Self::YBound { self.inner.y_bound() }
+    fn z_bound(&self) -> Self::ZBound { self.inner.z_bound() }
+}
+
+impl<G, S> Grid3Len<S> for Grid3Tracked<G, S>
+where
+    G: Grid3Len<S>,
+    S: Scalar,
+{}
+
+impl<G, S> Grid3Get<S> for Grid3Tracked<G, S>
+where
+    G: Grid3Get<S>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        self.inner.get(coord)
+    }
+}
+
+impl<G, S> Grid3Set<S> for Grid3Tracked<G, S>
+where
+    G: Grid3Set<S>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        self.inner.set(coord, elem);
+        self.mark(coord);
+    }
+}
+
+impl<G, S> Grid3Ref<S> for Grid3Tracked<G, S>
+where
+    G: Grid3Ref<S>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        self.inner.idx(coord)
+    }
+}
+
+impl<G, S> Grid3Mut<S> for Grid3Tracked<G, S>
+where
+    G: Grid3Mut<S>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = coord.into();
+        self.mark(coord);
+        self.inner.midx(coord)
+    }
+}
@@ -0,0 +1,166 @@
+//! Nearest-neighbor upscaling.
+
+use crate::{
+    scalar::Scalar,
+    grid3::*,
+    range::RangeBoundsTimes,
+};
+use mint::Vector3;
+
+/// Each element of the inner grid becomes a block of
+/// factor.x by factor.y by factor.z elements in this grid.
+///
+/// All elements of a block alias the same inner element.
+pub struct Grid3Upscale<G, S = i32>
+where
+    G: Grid3<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    inner: G,
+    factor: Vector3<S>,
+    new_xbound: <G as Grid3<S>>::XBound,
+    new_ybound: <G as Grid3<S>>::YBound,
+    new_zbound: <G as Grid3<S>>::ZBound,
+}
+
+impl<G, S> Grid3Upscale<G, S>
+where
+    G: Grid3<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    /// Panics if any factor is not positive.
+    pub fn new<I>(inner: G, factor: I) -> Self 
+    where
+        I: Into<Vector3<S>>,
+    {
+        let factor = factor.into();
+        assert!(factor.x > S::ZERO && factor.y > S::ZERO && factor.z > S::ZERO, "invalid factor {:?}", factor);
+        let new_xbound = inner.x_bound().times(factor.x);
+        let new_ybound = inner.y_bound().times(factor.y);
+        let new_zbound = inner.z_bound().times(factor.z);
+        Grid3Upscale {
+            inner,
+            factor,
+            new_xbound,
+            new_ybound,
+            new_zbound,
+        }
+    }
+    
+    pub fn factor<I>(&self) -> I
+    where
+        I: From<Vector3<S>> 
+    {
+        I::from(self.factor)
+    }
+    
+    pub fn inner_coord<I>(&self, coord: I) -> I
+    where
+        I: Into<Vector3<S>> + From<Vector3<S>>
+    {
+        let Vector3 { x, y, z } = coord.into();
+        I::from(Vector3 {
+            x: x.div_euclid(self.factor.x),
+            y: y.div_euclid(self.factor.y),
+            z: z.div_euclid(self.factor.z),
+        })
+    }
+}
+
+impl<G, S> Grid3<S> for Grid3Upscale<G, S>
+where
+    G: Grid3<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    type Item = <G as Grid3<S>>::Item;
+    type XBound = <G as Grid3<S>>::XBound;
+    type YBound = <G as Grid3<S>>::YBound;
+    type ZBound = <G as Grid3<S>>::ZBound;
+    
+    fn x_bound(&self) -> Self::XBound { self.new_xbound.clone() }
+    fn y_bound(&self) -> Self::YBound { self.new_ybound.clone() }
+    fn z_bound(&self) -> Self::ZBound { self.new_zbound.clone() }
+}
+
+impl<G, S> Grid3Len<S> for Grid3Upscale<G, S>
+where
+    G: Grid3Len<S>,
+    S: Scalar,
+{}
+
+impl<G, S> Grid3Get<S> for Grid3Upscale<G, S>
+where
+    G: Grid3<S> + Grid3Get<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = self.inner_coord(coord.into());
+        self.inner.get(coord)
+    }
+}
+
+impl<G, S> Grid3Set<S> for Grid3Upscale<G, S>
+where
+    G: Grid3<S> + Grid3Set<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = self.inner_coord(coord.into());
+        self.inner.set(coord, elem);
+    }
+}
+
+impl<G, S> Grid3Ref<S> for Grid3Upscale<G, S>
+where
+    G: Grid3<S> + Grid3Ref<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = self.inner_coord(coord.into());
+        self.inner.idx(coord)
+    }
+}
+
+impl<G, S> Grid3Mut<S> for Grid3Upscale<G, S>
+where
+    G: Grid3<S> + Grid3Mut<S>,
+    <G as Grid3<S>>::XBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::YBound: RangeBoundsTimes<S>,
+    <G as Grid3<S>>::ZBound: RangeBoundsTimes<S>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let coord = self.inner_coord(coord.into());
+        self.inner.midx(coord)
+    }
+}
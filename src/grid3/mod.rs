@@ -10,9 +10,11 @@ use crate::{
     range::{
         Range0To,
         RangeBoundsTimes,
+        RangeBoundsDiv,
         RangeBoundsPlus,
         BoundRange,
     },
+    scalar::Scalar,
 };
 use mint::Vector3;
 use std::{
@@ -21,35 +23,39 @@ use std::{
 };
 
 /// Allocate a grid on the heap.
-pub fn alloc<I, T>(x_len: i32, y_len: i32, z_len: i32, startval: T) -> backends::heap::ArrayGrid3<T>
+pub fn alloc<T, S>(x_len: S, y_len: S, z_len: S, startval: T) -> backends::heap::ArrayGrid3<T, S>
 where
-    T: Clone
+    T: Clone,
+    S: Scalar,
 {
     backends::heap::ArrayGrid3::broadcast(x_len, y_len, z_len, startval)
 }
 
 /// Allocate a grid on the heap, populate with a function.
-pub fn alloc_gen<I, T, F>(x_len: i32, y_len: i32, z_len: i32, generator: F) -> backends::heap::ArrayGrid3<T>
+pub fn alloc_gen<I, T, F, S>(x_len: S, y_len: S, z_len: S, generator: F) -> backends::heap::ArrayGrid3<T, S>
 where
-    I: From<Vector3<i32>>,
+    I: From<Vector3<S>>,
     F: FnMut(I) -> T,
+    S: Scalar,
 {
     backends::heap::ArrayGrid3::new(x_len, y_len, z_len, generator)
 }
 
 /// Inline 3x3x3 array grid.
-pub fn array3x3x3<I, T>(startval: T) -> backends::inline3x3x3::Inline3x3x3Grid<T>
+pub fn array3x3x3<T, S>(startval: T) -> backends::inline3x3x3::Inline3x3x3Grid<T, S>
 where
-    T: Clone
+    T: Clone,
+    S: Scalar,
 {
     backends::inline3x3x3::Inline3x3x3Grid::broadcast(startval)
 }
 
 /// Inline 3x3x3 array grid, populate with a function.
-pub fn array3x3x3_gen<I, T, F>(generator: F) -> backends::inline3x3x3::Inline3x3x3Grid<T>
+pub fn array3x3x3_gen<I, T, F, S>(generator: F) -> backends::inline3x3x3::Inline3x3x3Grid<T, S>
 where
-    I: From<Vector3<i32>>,
+    I: From<Vector3<S>>,
     F: FnMut(I) -> T,
+    S: Scalar,
 {
     backends::inline3x3x3::Inline3x3x3Grid::new(generator)
 }
@@ -57,7 +63,6 @@ where
 /// Represent a coord → Item function as a grid.
 pub fn value_fn<I, T, F>(f: F) -> backends::kolmo::KolmoGrid3<F, I, T>
 where
-    I: From<Vector3<i32>>,
     F: Fn(I) -> T,
 {
     backends::kolmo::KolmoGrid3::new(f)
@@ -66,7 +71,6 @@ where
 /// Represent a coord → &Item function as a grid.
 pub fn ref_fn<'a, I, T, F>(f: F) -> backends::kolmoref::KolmoRefGrid3<'a, F, I, T>
 where
-    I: From<Vector3<i32>>,
     T: 'a,
     F: Fn(I) -> &'a T,
 {
@@ -76,7 +80,6 @@ where
 /// Represent a coord → &mut Item function as a grid.
 pub fn mut_fn<'a, I, T, F>(f: F) -> backends::kolmomut::KolmoMutGrid3<'a, F, I, T>
 where
-    I: From<Vector3<i32>>,
     T: 'a,
     F: Fn(I) -> &'a mut T,
 {
@@ -92,7 +95,6 @@ where
 /// elements from the referent.
 pub fn reader_writer<I, R, T, Fr, Fw>(referent: R, reader: Fr, writer: Fw) -> backends::kolmorw::KolmoRwGrid3<I, R, T, Fr, Fw>
 where
-    I: From<Vector3<i32>>,
     Fr: Fn(I, &R) -> &T,
     Fw: Fn(I, &mut R) -> &mut T,
 {
@@ -100,11 +102,11 @@ where
 }
 
 /// Top-level trait for 2D grids.
-pub trait Grid3 {
+pub trait Grid3<S: Scalar = i32> {
     type Item;
-    type XBound: RangeBounds<i32>;
-    type YBound: RangeBounds<i32>;
-    type ZBound: RangeBounds<i32>;
+    type XBound: RangeBounds<S>;
+    type YBound: RangeBounds<S>;
+    type ZBound: RangeBounds<S>;
     
     fn x_bound(&self) -> Self::XBound;
     fn y_bound(&self) -> Self::YBound;
@@ -112,7 +114,7 @@ pub trait Grid3 {
     
     fn in_bounds<I>(&self, coord: I) -> bool 
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let Vector3 { x, y, z } = coord.into();
         
@@ -121,6 +123,17 @@ pub trait Grid3 {
         && self.z_bound().contains(&z)
     }
     
+    /// Borrow this grid, to apply combinators without consuming it.
+    fn view(&self) -> crate::view::GridRef<'_, Self> {
+        crate::view::GridRef::new(self)
+    }
+    
+    /// Mutably borrow this grid, to apply combinators without
+    /// consuming it.
+    fn view_mut(&mut self) -> crate::view::GridMut<'_, Self> {
+        crate::view::GridMut::new(self)
+    }
+    
     /// Element by-value mapping.
     fn map<F, T>(self, func: F) -> combinate::map::Grid3Map<Self, F, T>
     where
@@ -134,33 +147,65 @@ pub trait Grid3 {
     fn enumap<I, F, T>(self, func: F) -> combinate::enumap::Grid3EnuMap<Self, F, T, I>
     where
         Self: Sized,
-        I: From<Vector3<i32>>,
+        I: From<Vector3<S>>,
         F: Fn(I, Self::Item) -> T,
     {
         combinate::enumap::Grid3EnuMap::new(self, func)
     }
     
     /// Flattening a grid of grids with a regular stride.
-    fn flatten<I>(self, stride: I) -> combinate::flatten::Grid3Flat<Self>
+    fn flatten<I>(self, stride: I) -> combinate::flatten::Grid3Flat<Self, S>
     where
         Self: Sized,
-        Self::Item: Grid3,
-        Self::XBound: Clone + RangeBoundsTimes,
-        Self::YBound: Clone + RangeBoundsTimes,
-        Self::ZBound: Clone + RangeBoundsTimes,
-        I: Into<Vector3<i32>>,
+        Self::Item: Grid3<S>,
+        Self::XBound: Clone + RangeBoundsTimes<S>,
+        Self::YBound: Clone + RangeBoundsTimes<S>,
+        Self::ZBound: Clone + RangeBoundsTimes<S>,
+        I: Into<Vector3<S>>,
     {
         combinate::flatten::Grid3Flat::new(self, stride)
     }
     
+    /// Nearest-neighbor upscaling.
+    ///
+    /// Each element becomes a block of factor.x by factor.y by
+    /// factor.z elements, which all alias the original element.
+    fn upscale<I>(self, factor: I) -> combinate::upscale::Grid3Upscale<Self, S>
+    where
+        Self: Sized,
+        Self::XBound: RangeBoundsTimes<S>,
+        Self::YBound: RangeBoundsTimes<S>,
+        Self::ZBound: RangeBoundsTimes<S>,
+        I: Into<Vector3<S>>,
+    {
+        combinate::upscale::Grid3Upscale::new(self, factor)
+    }
+    
+    /// Downscaling by folding blocks, such as for voxel LOD.
+    ///
+    /// Each block of factor.x by factor.y by factor.z elements is
+    /// passed to the reducer as an iterator, which folds it into
+    /// one element. Blocks at the edges may be partial.
+    fn downscale<I, F, T>(self, factor: I, reducer: F) -> combinate::downscale::Grid3Downscale<Self, F, T, S>
+    where
+        Self: Sized + Grid3Get<S>,
+        Self::XBound: RangeBoundsDiv<S>,
+        Self::YBound: RangeBoundsDiv<S>,
+        Self::ZBound: RangeBoundsDiv<S>,
+        I: Into<Vector3<S>>,
+        F: Fn(combinate::downscale::Grid3Block<'_, Self, S>) -> T,
+    {
+        combinate::downscale::Grid3Downscale::new(self, factor, reducer)
+    }
+    
     /// <0, 0> in this grid becomes new_origin in resultant grid.
-    fn new_origin<I>(self, new_origin: I) -> combinate::neworigin::Grid3NewOrigin<Self>
+    fn new_origin<I>(self, new_origin: I) -> combinate::neworigin::Grid3NewOrigin<Self, S>
     where
         Self: Sized,
-        Self::XBound: RangeBoundsPlus,
-        Self::YBound: RangeBoundsPlus,
-        Self::ZBound: RangeBoundsPlus,
-        I: Into<Vector3<i32>>,
+        Self::XBound: RangeBoundsPlus<S>,
+        Self::YBound: RangeBoundsPlus<S>,
+        Self::ZBound: RangeBoundsPlus<S>,
+        I: Into<Vector3<S>>,
     {
         combinate::neworigin::Grid3NewOrigin::new(self, new_origin)
     }
@@ -171,7 +216,7 @@ pub trait Grid3 {
     fn oob_handler<I, F>(self, handler: F) -> combinate::oobhandler::Grid3OobHandler<Self, I, F>
     where
         Self: Sized,
-        I: From<Vector3<i32>>,
+        I: From<Vector3<S>>,
         F: Fn(I) -> Self::Item,
     {
         combinate::oobhandler::Grid3OobHandler::new(self, handler)
@@ -187,9 +232,9 @@ pub trait Grid3 {
         Self::XBound: Debug,
         Self::YBound: Debug,
         Self::ZBound: Debug,
-        X: RangeBounds<i32> + Clone + Debug,
-        Y: RangeBounds<i32> + Clone + Debug,
-        Z: RangeBounds<i32> + Clone + Debug,
+        X: RangeBounds<S> + Clone + Debug,
+        Y: RangeBounds<S> + Clone + Debug,
+        Z: RangeBounds<S> + Clone + Debug,
     {
         combinate::slice::Grid3Slice::new(self, new_x, new_y, new_z)
     }
@@ -203,9 +248,9 @@ pub trait Grid3 {
         Self: Sized,
         Self::XBound: Debug,
         Self::YBound: Debug,
-        X: RangeBounds<i32> + Clone + Debug,
-        Y: RangeBounds<i32> + Clone + Debug,
-        Z: RangeBounds<i32> + Clone + Debug,
+        X: RangeBounds<S> + Clone + Debug,
+        Y: RangeBounds<S> + Clone + Debug,
+        Z: RangeBounds<S> + Clone + Debug,
     {
         combinate::slice::Grid3Slice::try_new(self, new_x, new_y, new_z)
     }
@@ -214,7 +259,7 @@ pub trait Grid3 {
     /// 
     /// If the new bounds are not a subset of the current bounds,
     /// this will panic.
-    fn subview_0to(self, new_x_len: i32, new_y_len: i32, new_z_len: i32) -> combinate::slice::Grid3Slice<Self, Range0To, Range0To, Range0To>
+    fn subview_0to(self, new_x_len: S, new_y_len: S, new_z_len: S) -> combinate::slice::Grid3Slice<Self, Range0To<S>, Range0To<S>, Range0To<S>>
     where
         Self: Sized,
         Self::XBound: Debug,
@@ -232,7 +277,7 @@ pub trait Grid3 {
     /// 
     /// If the new bounds are not a subset of the current bounds,
     /// this will fail.
-    fn try_subview_0to(self, new_x_len: i32, new_y_len: i32, new_z_len: i32) -> Result<combinate::slice::Grid3Slice<Self, Range0To, Range0To, Range0To>, Self>
+    fn try_subview_0to(self, new_x_len: S, new_y_len: S, new_z_len: S) -> Result<combinate::slice::Grid3Slice<Self, Range0To<S>, Range0To<S>, Range0To<S>>, Self>
     where
         Self: Sized,
         Self::XBound: Debug,
@@ -246,6 +291,24 @@ pub trait Grid3 {
             Range0To { end: new_z_len },)
     }
     
+    /// Record the coordinates written through this grid.
+    fn tracked(self) -> combinate::tracked::Grid3Tracked<Self, S>
+    where
+        Self: Sized,
+    {
+        combinate::tracked::Grid3Tracked::new(self)
+    }
+    
+    /// Record writes to this grid, so that they can be undone.
+    ///
+    /// Up to max_transactions transactions of history are kept.
+    fn journaled(self, max_transactions: usize) -> combinate::journaled::Grid3Journaled<Self, S>
+    where
+        Self: Sized,
+    {
+        combinate::journaled::Grid3Journaled::new(self, max_transactions)
+    }
+    
     /// View of this grid which wraps around the edges.
     ///
     /// The input grid must be bounded in all directions, and the
@@ -253,56 +316,98 @@ pub trait Grid3 {
     fn wrapping(self) -> combinate::wrapping::Grid3Wrapping<Self>
     where
         Self: Sized,
-        Self::XBound: BoundRange,
-        Self::YBound: BoundRange,
-        Self::ZBound: BoundRange,
+        Self::XBound: BoundRange<S>,
+        Self::YBound: BoundRange<S>,
+        Self::ZBound: BoundRange<S>,
     {
         combinate::wrapping::Grid3Wrapping::new(self)
     }
     
+    /// View the plane at a fixed z coordinate as a Grid2.
+    ///
+    /// If z is outside the z bound, this will panic.
+    fn plane_xy(self, z: S) -> combinate::plane::Grid3PlaneXY<Self, S>
+    where
+        Self: Sized,
+    {
+        combinate::plane::Grid3PlaneXY::new(self, z)
+    }
+    
+    /// View the plane at a fixed y coordinate as a Grid2.
+    ///
+    /// Grid2 y corresponds to Grid3 z. If y is outside the y bound,
+    /// this will panic.
+    fn plane_xz(self, y: S) -> combinate::plane::Grid3PlaneXZ<Self, S>
+    where
+        Self: Sized,
+    {
+        combinate::plane::Grid3PlaneXZ::new(self, y)
+    }
+    
+    /// View the plane at a fixed x coordinate as a Grid2.
+    ///
+    /// Grid2 x corresponds to Grid3 y, and Grid2 y corresponds to
+    /// Grid3 z. If x is outside the x bound, this will panic.
+    fn plane_yz(self, x: S) -> combinate::plane::Grid3PlaneYZ<Self, S>
+    where
+        Self: Sized,
+    {
+        combinate::plane::Grid3PlaneYZ::new(self, x)
+    }
+    
+    /// View this grid as a dimension-generic GridN.
+    ///
+    /// All axes must have the same bound type.
+    fn into_gridn(self) -> crate::gridn::combinate::adapt::Grid3AsN<Self>
+    where
+        Self: Sized + Grid3<S, YBound=<Self as Grid3<S>>::XBound, ZBound=<Self as Grid3<S>>::XBound>,
+    {
+        crate::gridn::combinate::adapt::Grid3AsN::new(self)
+    }
+    
     /// Collect a grid's elements into a heap allocation.
     ///
     /// The grid must be bound from 0 to a finite limit.
-    fn collect(&self) -> backends::heap::ArrayGrid3<Self::Item>
+    fn collect(&self) -> backends::heap::ArrayGrid3<Self::Item, S>
     where
-        Self: Grid3Get,
-        Self::XBound: Into<Range0To>,
-        Self::YBound: Into<Range0To>,
-        Self::ZBound: Into<Range0To>,
+        Self: Grid3Get<S>,
+        Self::XBound: Into<Range0To<S>>,
+        Self::YBound: Into<Range0To<S>>,
+        Self::ZBound: Into<Range0To<S>>,
     {
         let x_len = self.x_bound().into().end;
         let y_len = self.y_bound().into().end;
-        let z_len = self.y_bound().into().end;
+        let z_len = self.z_bound().into().end;
         backends::heap::ArrayGrid3::new(
             x_len, y_len, z_len,
-            |coord: Vector3<i32>| self.get(coord))
+            |coord: Vector3<S>| self.get(coord))
     }
 }
 
 /// 2D grid bounded from 0 to a finite number.
-pub trait Grid3Len: Grid3<XBound=Range0To, YBound=Range0To, ZBound=Range0To> {
-    fn x_len(&self) -> i32 {
+pub trait Grid3Len<S: Scalar = i32>: Grid3<S, XBound=Range0To<S>, YBound=Range0To<S>, ZBound=Range0To<S>> {
+    fn x_len(&self) -> S {
         self.x_bound().end
     }
     
-    fn y_len(&self) -> i32 {
+    fn y_len(&self) -> S {
         self.y_bound().end
     }
     
-    fn z_len(&self) -> i32 {
+    fn z_len(&self) -> S {
         self.z_bound().end
     }
 }
 
 /// 2D grid read by value.
-pub trait Grid3Get: Grid3 {
+pub trait Grid3Get<S: Scalar = i32>: Grid3<S> {
     fn get<I>(&self, coord: I) -> Self::Item
     where
-        I: Into<Vector3<i32>>;
+        I: Into<Vector3<S>>;
         
     fn try_get<I>(&self, coord: I) -> Option<Self::Item>
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
@@ -314,19 +419,19 @@ pub trait Grid3Get: Grid3 {
 }
 
 /// 2D grid write by value.
-pub trait Grid3Set: Grid3 {
+pub trait Grid3Set<S: Scalar = i32>: Grid3<S> {
     fn set<I>(&mut self, coord: I, elem: Self::Item)
     where
-        I: Into<Vector3<i32>>;
+        I: Into<Vector3<S>>;
         
     fn try_set<I>(&mut self, coord: I, elem: Self::Item) -> Result<(), Self::Item> 
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
             self.set(coord, elem);
-            Ok({})
+            Ok(())
         } else {
             Err(elem)
         }
@@ -334,14 +439,14 @@ pub trait Grid3Set: Grid3 {
 }
 
 /// 2D grid read by reference.
-pub trait Grid3Ref: Grid3 {
+pub trait Grid3Ref<S: Scalar = i32>: Grid3<S> {
     fn idx<I>(&self, coord: I) -> &Self::Item
     where
-        I: Into<Vector3<i32>>;
+        I: Into<Vector3<S>>;
     
     fn try_idx<I>(&self, coord: I) -> Option<&Self::Item>
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
@@ -353,14 +458,14 @@ pub trait Grid3Ref: Grid3 {
 }
 
 /// 2D grid write by reference.
-pub trait Grid3Mut: Grid3 {
+pub trait Grid3Mut<S: Scalar = i32>: Grid3<S> {
     fn midx<I>(&mut self, coord: I) -> &mut Self::Item
     where
-        I: Into<Vector3<i32>>;
+        I: Into<Vector3<S>>;
     
     fn try_midx<I>(&mut self, coord: I) -> Option<&mut Self::Item>
     where
-        I: Into<Vector3<i32>>
+        I: Into<Vector3<S>>
     {
         let coord = coord.into();
         if self.in_bounds(coord) {
@@ -0,0 +1,121 @@
+//! Heap-allocated array implementation of GridN.
+
+use crate::{
+    range::Range0To,
+    scalar::Scalar,
+    gridn::*,
+};
+
+/// Heap-allocated array implementation of GridN.
+///
+/// Elements are stored with the first axis varying fastest.
+pub struct ArrayGridN<T, const D: usize, S = i32> {
+    alloc: Box<[T]>,
+    lens: [S; D],
+}
+
+/// 1D heap-allocated grid, such as a time series.
+pub type ArrayGrid1<T, S = i32> = ArrayGridN<T, 1, S>;
+
+/// 4D heap-allocated grid, such as a spacetime volume.
+pub type ArrayGrid4<T, S = i32> = ArrayGridN<T, 4, S>;
+
+impl<T, S: Scalar, const D: usize> ArrayGridN<T, D, S> {
+    pub fn new<I, F>(lens: [S; D], mut startval: F) -> Self
+    where
+        I: From<[S; D]>,
+        F: FnMut(I) -> T
+    {
+        assert!(lens.iter().all(|&len| len >= S::ZERO));
+        
+        let len = lens.iter().map(|len| len.to_usize()).product();
+        let mut v: Vec<T> = Vec::with_capacity(len);
+        for i in 0..len {
+            let mut rem = i;
+            let coord = lens.map(|len| {
+                let len = len.to_usize();
+                let axis = rem % len;
+                rem /= len;
+                S::from_usize(axis)
+            });
+            v.push(startval(I::from(coord)));
+        }
+        ArrayGridN {
+            alloc: v.into_boxed_slice(),
+            lens,
+        }
+    }
+    
+    pub fn broadcast(lens: [S; D], startval: T) -> Self
+    where
+        T: Clone 
+    {
+        Self::new(lens, |_: [S; D]| startval.clone())
+    }
+    
+    fn inner_index(&self, coord: [S; D]) -> Option<usize> {
+        let mut index = 0;
+        let mut stride = 1;
+        for (&i, &len) in coord.iter().zip(self.lens.iter()) {
+            if i < S::ZERO || i >= len {
+                return None;
+            }
+            index += i.to_usize() * stride;
+            stride *= len.to_usize();
+        }
+        Some(index)
+    }
+}
+
+impl<T, S: Scalar, const D: usize> GridN<D, S> for ArrayGridN<T, D, S> {
+    type Item = T;
+    type Bound = Range0To<S>;
+    
+    fn bounds(&self) -> [Range0To<S>; D] {
+        self.lens.map(|end| Range0To { end })
+    }
+}
+
+impl<T, S: Scalar, const D: usize> GridNLen<D, S> for ArrayGridN<T, D, S> {}
+
+impl<T, S: Scalar, const D: usize> GridNRef<D, S> for ArrayGridN<T, D, S> {
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<[S; D]>
+    {
+        let coord = coord.into();
+        let option = self
+            .inner_index(coord)
+            .map(|i| &self.alloc[i]);
+        match option {
+            Some(o) => o,
+            None => panic!("invalid index {:?}", coord),
+        }
+    }
+}
+
+impl<T, S: Scalar, const D: usize> GridNMut<D, S> for ArrayGridN<T, D, S> {
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<[S; D]>
+    {
+        let coord = coord.into();
+        let option = self
+            .inner_index(coord)
+            .map(move |i| &mut self.alloc[i]);
+        match option {
+            Some(o) => o,
+            None => panic!("invalid index {:?}", coord),
+        }
+    }
+}
+
+impl<T: Clone, S: Scalar, const D: usize> GridNGet<D, S> for ArrayGridN<T, D, S> {
+    fn get<I: Into<[S; D]>>(&self, coord: I) -> Self::Item 
+    { self.idx(coord).clone() }
+}
+
+impl<T, S: Scalar, const D: usize> GridNSet<D, S> for ArrayGridN<T, D, S> {
+    fn set<I: Into<[S; D]>>(&mut self, coord: I, elem: Self::Item) 
+    { *self.midx(coord) = elem; }
+}
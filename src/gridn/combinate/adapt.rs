@@ -0,0 +1,397 @@
+//! Adapters between GridN and Grid2/Grid3.
+//!
+//! Grid2 and Grid3 are not blanket-implemented for GridN (or 
+//! vice versa), since that would conflict with the existing
+//! implementations, so these wrappers bridge the two.
+
+use crate::{
+    scalar::Scalar,
+    gridn::*,
+    grid2::{Grid2, Grid2Len, Grid2Get, Grid2Set, Grid2Ref, Grid2Mut},
+    grid3::{Grid3, Grid3Len, Grid3Get, Grid3Set, Grid3Ref, Grid3Mut},
+};
+use mint::{Vector2, Vector3};
+
+/// View of a Grid2 as a GridN<2>.
+///
+/// The x and y bounds must be the same type.
+pub struct Grid2AsN<G> {
+    inner: G,
+}
+
+impl<G> Grid2AsN<G> {
+    pub fn new(inner: G) -> Self {
+        Grid2AsN { inner }
+    }
+    
+    pub fn into_inner(self) -> G {
+        self.inner
+    }
+}
+
+fn vec2<S: Scalar>(coord: impl Into<[S; 2]>) -> Vector2<S> {
+    Vector2::from(coord.into())
+}
+
+impl<G, S> GridN<2, S> for Grid2AsN<G>
+where
+    G: Grid2<S, YBound=<G as Grid2<S>>::XBound>,
+    S: Scalar,
+{
+    type Item = <G as Grid2<S>>::Item;
+    type Bound = <G as Grid2<S>>::XBound;
+    
+    fn bounds(&self) -> [Self::Bound; 2] {
+        [self.inner.x_bound(), self.inner.y_bound()]
+    }
+}
+
+impl<G, S> GridNLen<2, S> for Grid2AsN<G>
+where
+    G: Grid2Len<S>,
+    S: Scalar,
+{}
+
+impl<G, S> GridNGet<2, S> for Grid2AsN<G>
+where
+    G: Grid2Get<S, YBound=<G as Grid2<S>>::XBound>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<[S; 2]>
+    {
+        self.inner.get(vec2(coord))
+    }
+}
+
+impl<G, S> GridNSet<2, S> for Grid2AsN<G>
+where
+    G: Grid2Set<S, YBound=<G as Grid2<S>>::XBound>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<[S; 2]>
+    {
+        self.inner.set(vec2(coord), elem)
+    }
+}
+
+impl<G, S> GridNRef<2, S> for Grid2AsN<G>
+where
+    G: Grid2Ref<S, YBound=<G as Grid2<S>>::XBound>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<[S; 2]>
+    {
+        self.inner.idx(vec2(coord))
+    }
+}
+
+impl<G, S> GridNMut<2, S> for Grid2AsN<G>
+where
+    G: Grid2Mut<S, YBound=<G as Grid2<S>>::XBound>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<[S; 2]>
+    {
+        self.inner.midx(vec2(coord))
+    }
+}
+
+/// View of a Grid3 as a GridN<3>.
+///
+/// The x, y and z bounds must be the same type.
+pub struct Grid3AsN<G> {
+    inner: G,
+}
+
+impl<G> Grid3AsN<G> {
+    pub fn new(inner: G) -> Self {
+        Grid3AsN { inner }
+    }
+    
+    pub fn into_inner(self) -> G {
+        self.inner
+    }
+}
+
+fn vec3<S: Scalar>(coord: impl Into<[S; 3]>) -> Vector3<S> {
+    Vector3::from(coord.into())
+}
+
+impl<G, S> GridN<3, S> for Grid3AsN<G>
+where
+    G: Grid3<S, YBound=<G as Grid3<S>>::XBound, ZBound=<G as Grid3<S>>::XBound>,
+    S: Scalar,
+{
+    type Item = <G as Grid3<S>>::Item;
+    type Bound = <G as Grid3<S>>::XBound;
+    
+    fn bounds(&self) -> [Self::Bound; 3] {
+        [self.inner.x_bound(), self.inner.y_bound(), self.inner.z_bound()]
+    }
+}
+
+impl<G, S> GridNLen<3, S> for Grid3AsN<G>
+where
+    G: Grid3Len<S>,
+    S: Scalar,
+{}
+
+impl<G, S> GridNGet<3, S> for Grid3AsN<G>
+where
+    G: Grid3Get<S, YBound=<G as Grid3<S>>::XBound, ZBound=<G as Grid3<S>>::XBound>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<[S; 3]>
+    {
+        self.inner.get(vec3(coord))
+    }
+}
+
+impl<G, S> GridNSet<3, S> for Grid3AsN<G>
+where
+    G: Grid3Set<S, YBound=<G as Grid3<S>>::XBound, ZBound=<G as Grid3<S>>::XBound>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<[S; 3]>
+    {
+        self.inner.set(vec3(coord), elem)
+    }
+}
+
+impl<G, S> GridNRef<3, S> for Grid3AsN<G>
+where
+    G: Grid3Ref<S, YBound=<G as Grid3<S>>::XBound, ZBound=<G as Grid3<S>>::XBound>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<[S; 3]>
+    {
+        self.inner.idx(vec3(coord))
+    }
+}
+
+impl<G, S> GridNMut<3, S> for Grid3AsN<G>
+where
+    G: Grid3Mut<S, YBound=<G as Grid3<S>>::XBound, ZBound=<G as Grid3<S>>::XBound>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<[S; 3]>
+    {
+        self.inner.midx(vec3(coord))
+    }
+}
+
+/// View of a GridN<2> as a Grid2.
+pub struct GridNAs2<G> {
+    inner: G,
+}
+
+impl<G> GridNAs2<G> {
+    pub fn new(inner: G) -> Self {
+        GridNAs2 { inner }
+    }
+    
+    pub fn into_inner(self) -> G {
+        self.inner
+    }
+}
+
+fn arr2<S: Scalar>(coord: impl Into<Vector2<S>>) -> [S; 2] {
+    coord.into().into()
+}
+
+impl<G, S> Grid2<S> for GridNAs2<G>
+where
+    G: GridN<2, S>,
+    S: Scalar,
+{
+    type Item = <G as GridN<2, S>>::Item;
+    type XBound = <G as GridN<2, S>>::Bound;
+    type YBound = <G as GridN<2, S>>::Bound;
+    
+    fn x_bound(&self) -> Self::XBound {
+        let [x, _] = self.inner.bounds();
+        x
+    }
+    
+    fn y_bound(&self) -> Self::YBound {
+        let [_, y] = self.inner.bounds();
+        y
+    }
+}
+
+impl<G, S> Grid2Len<S> for GridNAs2<G>
+where
+    G: GridNLen<2, S>,
+    S: Scalar,
+{}
+
+impl<G, S> Grid2Get<S> for GridNAs2<G>
+where
+    G: GridNGet<2, S>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.get(arr2(coord))
+    }
+}
+
+impl<G, S> Grid2Set<S> for GridNAs2<G>
+where
+    G: GridNSet<2, S>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.set(arr2(coord), elem)
+    }
+}
+
+impl<G, S> Grid2Ref<S> for GridNAs2<G>
+where
+    G: GridNRef<2, S>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.idx(arr2(coord))
+    }
+}
+
+impl<G, S> Grid2Mut<S> for GridNAs2<G>
+where
+    G: GridNMut<2, S>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.midx(arr2(coord))
+    }
+}
+
+/// View of a GridN<3> as a Grid3.
+pub struct GridNAs3<G> {
+    inner: G,
+}
+
+impl<G> GridNAs3<G> {
+    pub fn new(inner: G) -> Self {
+        GridNAs3 { inner }
+    }
+    
+    pub fn into_inner(self) -> G {
+        self.inner
+    }
+}
+
+fn arr3<S: Scalar>(coord: impl Into<Vector3<S>>) -> [S; 3] {
+    coord.into().into()
+}
+
+impl<G, S> Grid3<S> for GridNAs3<G>
+where
+    G: GridN<3, S>,
+    S: Scalar,
+{
+    type Item = <G as GridN<3, S>>::Item;
+    type XBound = <G as GridN<3, S>>::Bound;
+    type YBound = <G as GridN<3, S>>::Bound;
+    type ZBound = <G as GridN<3, S>>::Bound;
+    
+    fn x_bound(&self) -> Self::XBound {
+        let [x, _, _] = self.inner.bounds();
+        x
+    }
+    
+    fn y_bound(&self) -> Self::YBound {
+        let [_, y, _] = self.inner.bounds();
+        y
+    }
+    
+    fn z_bound(&self) -> Self::ZBound {
+        let [_, _, z] = self.inner.bounds();
+        z
+    }
+}
+
+impl<G, S> Grid3Len<S> for GridNAs3<G>
+where
+    G: GridNLen<3, S>,
+    S: Scalar,
+{}
+
+impl<G, S> Grid3Get<S> for GridNAs3<G>
+where
+    G: GridNGet<3, S>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        self.inner.get(arr3(coord))
+    }
+}
+
+impl<G, S> Grid3Set<S> for GridNAs3<G>
+where
+    G: GridNSet<3, S>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector3<S>>
+    {
+        self.inner.set(arr3(coord), elem)
+    }
+}
+
+impl<G, S> Grid3Ref<S> for GridNAs3<G>
+where
+    G: GridNRef<3, S>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        self.inner.idx(arr3(coord))
+    }
+}
+
+impl<G, S> Grid3Mut<S> for GridNAs3<G>
+where
+    G: GridNMut<3, S>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        self.inner.midx(arr3(coord))
+    }
+}
@@ -0,0 +1,85 @@
+pub mod adapt;
+
+use super::*;
+use std::{
+    ops::{Deref, DerefMut},
+};
+
+// ==== elevate pointer types ====
+
+impl<T, S, const D: usize> GridN<D, S> for T
+where
+    T: Deref,
+    <T as Deref>::Target: GridN<D, S>,
+    S: Scalar,
+{
+    type Item = <<T as Deref>::Target as GridN<D, S>>::Item;
+    type Bound = <<T as Deref>::Target as GridN<D, S>>::Bound;
+    
+    fn bounds(&self) -> [Self::Bound; D] {
+        T::deref(self).bounds()
+    }
+}
+
+impl<T, S, const D: usize> GridNLen<D, S> for T
+where
+    T: Deref,
+    <T as Deref>::Target: GridNLen<D, S>,
+    S: Scalar,
+{}
+
+impl<T, S, const D: usize> GridNGet<D, S> for T
+where
+    T: Deref,
+    <T as Deref>::Target: GridNGet<D, S>,
+    S: Scalar,
+{
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<[S; D]>
+    {
+        T::deref(self).get(coord)
+    }
+}
+
+impl<T, S, const D: usize> GridNRef<D, S> for T
+where
+    T: Deref,
+    <T as Deref>::Target: GridNRef<D, S>,
+    S: Scalar,
+{
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<[S; D]>
+    {
+        T::deref(self).idx(coord)
+    }
+}
+
+impl<T, S, const D: usize> GridNSet<D, S> for T
+where
+    T: Deref + DerefMut,
+    <T as Deref>::Target: GridNSet<D, S>,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<[S; D]>
+    {
+        T::deref_mut(self).set(coord, elem)
+    }
+}
+
+impl<T, S, const D: usize> GridNMut<D, S> for T
+where
+    T: Deref + DerefMut,
+    <T as Deref>::Target: GridNMut<D, S>,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<[S; D]>
+    {
+        T::deref_mut(self).midx(coord)
+    }
+}
@@ -0,0 +1,178 @@
+//! Dimension-generic data grid.
+//!
+//! `GridN<D>` is a const-generic interface over `Grid2` and
+//! `Grid3`, for code which is written once for any dimensionality.
+//! Coordinates are `[S; D]` arrays, and every axis shares a single
+//! bound type, so bounds are `[Self::Bound; D]`.
+//!
+//! Grid2 and Grid3 types can be viewed as GridN through
+//! `Grid2::into_gridn` and `Grid3::into_gridn`, and a GridN of
+//! the matching dimension can be viewed as Grid2 or Grid3 through
+//! `GridN::into_grid2` and `GridN::into_grid3`.
+//!
+//! `backends::heap::ArrayGridN` stores any dimensionality, with the
+//! `ArrayGrid1` and `ArrayGrid4` aliases for time series and
+//! spacetime volumes. Other backends and combinators live on Grid2
+//! and Grid3 only.
+
+/// Combinators.
+pub mod combinate;
+
+/// Implementations.
+pub mod backends;
+
+use crate::{
+    range::Range0To,
+    scalar::Scalar,
+};
+use std::ops::RangeBounds;
+
+/// Allocate a grid on the heap.
+pub fn alloc<T, S, const D: usize>(lens: [S; D], startval: T) -> backends::heap::ArrayGridN<T, D, S>
+where
+    T: Clone,
+    S: Scalar,
+{
+    backends::heap::ArrayGridN::broadcast(lens, startval)
+}
+
+/// Allocate a grid on the heap, populate with a function.
+pub fn alloc_gen<I, T, F, S, const D: usize>(lens: [S; D], generator: F) -> backends::heap::ArrayGridN<T, D, S>
+where
+    I: From<[S; D]>,
+    F: FnMut(I) -> T,
+    S: Scalar,
+{
+    backends::heap::ArrayGridN::new(lens, generator)
+}
+
+/// Top-level trait for N-dimensional grids.
+pub trait GridN<const D: usize, S: Scalar = i32> {
+    type Item;
+    type Bound: RangeBounds<S>;
+    
+    fn bounds(&self) -> [Self::Bound; D];
+    
+    fn in_bounds<I>(&self, coord: I) -> bool
+    where
+        I: Into<[S; D]>
+    {
+        let coord = coord.into();
+        
+        self.bounds().iter()
+            .zip(coord.iter())
+            .all(|(bound, i)| bound.contains(i))
+    }
+    
+    /// Borrow this grid, to apply combinators without consuming it.
+    fn view(&self) -> crate::view::GridRef<'_, Self> {
+        crate::view::GridRef::new(self)
+    }
+    
+    /// Mutably borrow this grid, to apply combinators without
+    /// consuming it.
+    fn view_mut(&mut self) -> crate::view::GridMut<'_, Self> {
+        crate::view::GridMut::new(self)
+    }
+    
+    /// View this 2D grid as a Grid2.
+    fn into_grid2(self) -> combinate::adapt::GridNAs2<Self>
+    where
+        Self: Sized + GridN<2, S>,
+    {
+        combinate::adapt::GridNAs2::new(self)
+    }
+    
+    /// View this 3D grid as a Grid3.
+    fn into_grid3(self) -> combinate::adapt::GridNAs3<Self>
+    where
+        Self: Sized + GridN<3, S>,
+    {
+        combinate::adapt::GridNAs3::new(self)
+    }
+
+}
+
+/// N-dimensional grid bounded from 0 to a finite number.
+pub trait GridNLen<const D: usize, S: Scalar = i32>: GridN<D, S, Bound=Range0To<S>> {
+    fn lens(&self) -> [S; D] {
+        self.bounds().map(|bound| bound.end)
+    }
+}
+
+/// N-dimensional grid read by value.
+pub trait GridNGet<const D: usize, S: Scalar = i32>: GridN<D, S> {
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<[S; D]>;
+    
+    fn try_get<I>(&self, coord: I) -> Option<Self::Item>
+    where
+        I: Into<[S; D]>
+    {
+        let coord = coord.into();
+        if self.in_bounds(coord) {
+            Some(self.get(coord))
+        } else {
+            None
+        }
+    }
+}
+
+/// N-dimensional grid write by value.
+pub trait GridNSet<const D: usize, S: Scalar = i32>: GridN<D, S> {
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<[S; D]>;
+    
+    fn try_set<I>(&mut self, coord: I, elem: Self::Item) -> Result<(), Self::Item>
+    where
+        I: Into<[S; D]>
+    {
+        let coord = coord.into();
+        if self.in_bounds(coord) {
+            self.set(coord, elem);
+            Ok(())
+        } else {
+            Err(elem)
+        }
+    }
+}
+
+/// N-dimensional grid read by reference.
+pub trait GridNRef<const D: usize, S: Scalar = i32>: GridN<D, S> {
+    fn idx<I>(&self, coord: I) -> &Self::Item
+    where
+        I: Into<[S; D]>;
+    
+    fn try_idx<I>(&self, coord: I) -> Option<&Self::Item>
+    where
+        I: Into<[S; D]>
+    {
+        let coord = coord.into();
+        if self.in_bounds(coord) {
+            Some(self.idx(coord))
+        } else {
+            None
+        }
+    }
+}
+
+/// N-dimensional grid write by reference.
+pub trait GridNMut<const D: usize, S: Scalar = i32>: GridN<D, S> {
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<[S; D]>;
+    
+    fn try_midx<I>(&mut self, coord: I) -> Option<&mut Self::Item>
+    where
+        I: Into<[S; D]>
+    {
+        let coord = coord.into();
+        if self.in_bounds(coord) {
+            Some(self.midx(coord))
+        } else {
+            None
+        }
+    }
+}
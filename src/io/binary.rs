@@ -0,0 +1,629 @@
+//! Compact binary format.
+//!
+//! A file is a header followed by the grid's elements in x-major
+//! order, that is, x varies fastest, then y, then z. All integers
+//! are little-endian.
+//!
+//! The header is:
+//!
+//! - the magic bytes `b"GRID"`
+//! - the format version, as a u16
+//! - the dimensionality, as a u8
+//! - the compression, as a u8
+//! - the element size in bytes, as a u32
+//! - for each axis, the lower bound as an i64 and the length as a u64
+//! - since version 2, zero padding up to a multiple of 16 bytes
+//!
+//! The padding keeps uncompressed elements aligned, so that files
+//! can be memory-mapped. Version 1 files, which have no padding, can
+//! still be read.
+//!
+//! Uncompressed elements are stored back to back. Run-length
+//! encoded elements are stored as runs of a u32 count followed by
+//! one element. LZ compressed elements are stored in blocks of up
+//! to 64 KiB of element bytes, each as a u32 uncompressed length,
+//! a u32 compressed length, and the compressed bytes.
+//!
+//! Writers stream elements out of any bounded grid, and readers
+//! fill a heap-allocated grid, which begins at the origin.
+
+use crate::{
+    scalar::Scalar,
+    range::BoundRange,
+    grid2::{
+        Grid2Get,
+        backends::heap::ArrayGrid2,
+    },
+    grid3::{
+        Grid3Get,
+        backends::heap::ArrayGrid3,
+    },
+    io::lz,
+};
+use mint::{Vector2, Vector3};
+use std::{
+    io::{self, Read, Write},
+    convert::TryFrom,
+    ops::Range,
+    fmt::{self, Display, Formatter},
+    error,
+};
+
+/// Bytes which begin every file.
+pub const MAGIC: [u8; 4] = *b"GRID";
+
+/// Format version written by this module.
+pub const VERSION: u16 = 2;
+
+/// Alignment of the end of the header.
+const HEADER_ALIGN: usize = 16;
+
+/// Maximum uncompressed size of an LZ block.
+const BLOCK_SIZE: usize = 1 << 16;
+
+/// Element type with a fixed-size binary representation.
+pub trait Element: Sized {
+    /// Number of bytes in the representation.
+    const SIZE: usize;
+    
+    /// Write the representation into a buffer of `SIZE` bytes.
+    fn write_bytes(&self, out: &mut [u8]);
+    
+    /// Read the representation from a buffer of `SIZE` bytes.
+    fn read_bytes(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_element {
+    ($($t:ty),*)=>{$(
+        impl Element for $t {
+            const SIZE: usize = std::mem::size_of::<$t>();
+            
+            fn write_bytes(&self, out: &mut [u8]) {
+                out.copy_from_slice(&self.to_le_bytes());
+            }
+            
+            fn read_bytes(bytes: &[u8]) -> Self {
+                let mut array = [0; std::mem::size_of::<$t>()];
+                array.copy_from_slice(bytes);
+                <$t>::from_le_bytes(array)
+            }
+        }
+    )*};
+}
+
+impl_element!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+impl Element for bool {
+    const SIZE: usize = 1;
+    
+    fn write_bytes(&self, out: &mut [u8]) {
+        out[0] = *self as u8;
+    }
+    
+    fn read_bytes(bytes: &[u8]) -> Self {
+        bytes[0] != 0
+    }
+}
+
+/// Compression of a file's elements.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Compression {
+    None,
+    /// Runs of equal elements.
+    Rle,
+    /// LZ77 blocks.
+    Lz,
+}
+
+impl Compression {
+    fn to_byte(self) -> u8 {
+        match self {
+            Compression::None => 0,
+            Compression::Rle => 1,
+            Compression::Lz => 2,
+        }
+    }
+    
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Compression::None),
+            1 => Some(Compression::Rle),
+            2 => Some(Compression::Lz),
+            _ => None,
+        }
+    }
+}
+
+/// File header.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Header {
+    pub version: u16,
+    pub compression: Compression,
+    pub element_size: u32,
+    /// Bounds of each axis of the grid which was written.
+    pub bounds: Vec<Range<i64>>,
+}
+
+/// Error reading a file.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The file ended early.
+    Truncated,
+    /// The file does not begin with `MAGIC`.
+    BadMagic([u8; 4]),
+    UnsupportedVersion(u16),
+    WrongDimensions {
+        expected: usize,
+        found: usize,
+    },
+    WrongElementSize {
+        expected: usize,
+        found: usize,
+    },
+    UnknownCompression(u8),
+    /// The grid's bounds do not fit in memory or the coordinate type.
+    TooLarge,
+    /// The compressed data is invalid.
+    Corrupt(&'static str),
+    /// The file length does not match its header.
+    WrongFileLength {
+        expected: u64,
+        found: u64,
+    },
+    /// The file is valid but cannot be memory-mapped.
+    Unmappable(&'static str),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "io error: {}", e),
+            Error::Truncated => write!(f, "file is truncated"),
+            Error::BadMagic(magic) => write!(f, "bad magic bytes {:?}", magic),
+            Error::UnsupportedVersion(version) => write!(f, "unsupported version {} (expected at most {})", version, VERSION),
+            Error::WrongDimensions { expected, found } => write!(f, "expected {}D grid, found {}D grid", expected, found),
+            Error::WrongElementSize { expected, found } => write!(f, "expected {} byte elements, found {} byte elements", expected, found),
+            Error::UnknownCompression(byte) => write!(f, "unknown compression {}", byte),
+            Error::TooLarge => write!(f, "grid is too large"),
+            Error::Corrupt(reason) => write!(f, "corrupt data: {}", reason),
+            Error::WrongFileLength { expected, found } => write!(f, "expected {} byte file, found {} byte file", expected, found),
+            Error::Unmappable(reason) => write!(f, "cannot map file: {}", reason),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            Error::Truncated
+        } else {
+            Error::Io(e)
+        }
+    }
+}
+
+fn read_array<R: Read, const N: usize>(input: &mut R) -> Result<[u8; N], Error> {
+    let mut array = [0; N];
+    input.read_exact(&mut array)?;
+    Ok(array)
+}
+
+/// Write a file header.
+pub fn write_header<W: Write>(mut out: W, header: &Header) -> io::Result<()> {
+    out.write_all(&MAGIC)?;
+    out.write_all(&header.version.to_le_bytes())?;
+    out.write_all(&[header.bounds.len() as u8, header.compression.to_byte()])?;
+    out.write_all(&header.element_size.to_le_bytes())?;
+    for bound in &header.bounds {
+        out.write_all(&bound.start.to_le_bytes())?;
+        out.write_all(&((bound.end - bound.start) as u64).to_le_bytes())?;
+    }
+    out.write_all(&[0; HEADER_ALIGN][..padding(header.version, header.bounds.len())])?;
+    Ok(())
+}
+
+fn unpadded_header_len(dims: usize) -> usize {
+    12 + 16 * dims
+}
+
+/// Length in bytes of the header of a file of the current version
+/// with some dimensionality.
+pub fn header_len(dims: usize) -> usize {
+    unpadded_header_len(dims).div_ceil(HEADER_ALIGN) * HEADER_ALIGN
+}
+
+/// Length in bytes of the padding at the end of a header.
+fn padding(version: u16, dims: usize) -> usize {
+    if version < 2 {
+        0
+    } else {
+        header_len(dims) - unpadded_header_len(dims)
+    }
+}
+
+/// Read a file header.
+///
+/// This fails if the version is not supported. Older versions are
+/// supported, and are returned in the header.
+pub fn read_header<R: Read>(mut input: R) -> Result<Header, Error> {
+    let magic = read_array(&mut input)?;
+    if magic != MAGIC {
+        return Err(Error::BadMagic(magic));
+    }
+    let version = u16::from_le_bytes(read_array(&mut input)?);
+    if version == 0 || version > VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+    let [dims, compression] = read_array(&mut input)?;
+    let compression = Compression::from_byte(compression)
+        .ok_or(Error::UnknownCompression(compression))?;
+    let element_size = u32::from_le_bytes(read_array(&mut input)?);
+    let mut bounds = Vec::with_capacity(dims as usize);
+    for _ in 0..dims {
+        let start = i64::from_le_bytes(read_array(&mut input)?);
+        let len = u64::from_le_bytes(read_array(&mut input)?);
+        let end = i64::try_from(len).ok()
+            .and_then(|len| start.checked_add(len))
+            .ok_or(Error::TooLarge)?;
+        bounds.push(start..end);
+    }
+    input.read_exact(&mut [0; HEADER_ALIGN][..padding(version, dims as usize)])?;
+    Ok(Header {
+        version,
+        compression,
+        element_size,
+        bounds,
+    })
+}
+
+/// Streaming element compressor.
+struct Encoder<W> {
+    out: W,
+    compression: Compression,
+    /// Current run element, and its count.
+    run: Option<(Vec<u8>, u32)>,
+    /// Current LZ block.
+    block: Vec<u8>,
+    /// Bytes per element.
+    size: usize,
+}
+
+impl<W: Write> Encoder<W> {
+    fn new(out: W, compression: Compression, size: usize) -> Self {
+        Encoder {
+            out,
+            compression,
+            run: None,
+            block: Vec::new(),
+            size,
+        }
+    }
+    
+    fn push(&mut self, elem: &[u8]) -> io::Result<()> {
+        match self.compression {
+            Compression::None => self.out.write_all(elem),
+            Compression::Rle => {
+                if let Some((ref run, ref mut count)) = self.run {
+                    if run == elem && *count < u32::MAX {
+                        *count += 1;
+                        return Ok(());
+                    }
+                }
+                self.flush_run()?;
+                self.run = Some((elem.to_vec(), 1));
+                Ok(())
+            }
+            Compression::Lz => {
+                self.block.extend_from_slice(elem);
+                // blocks always hold whole elements
+                if self.block.len() + self.size > BLOCK_SIZE {
+                    self.flush_block()?;
+                }
+                Ok(())
+            }
+        }
+    }
+    
+    fn flush_run(&mut self) -> io::Result<()> {
+        if let Some((run, count)) = self.run.take() {
+            self.out.write_all(&count.to_le_bytes())?;
+            self.out.write_all(&run)?;
+        }
+        Ok(())
+    }
+    
+    fn flush_block(&mut self) -> io::Result<()> {
+        if !self.block.is_empty() {
+            let compressed = lz::compress(&self.block);
+            self.out.write_all(&(self.block.len() as u32).to_le_bytes())?;
+            self.out.write_all(&(compressed.len() as u32).to_le_bytes())?;
+            self.out.write_all(&compressed)?;
+            self.block.clear();
+        }
+        Ok(())
+    }
+    
+    fn finish(mut self) -> io::Result<()> {
+        self.flush_run()?;
+        self.flush_block()?;
+        self.out.flush()
+    }
+}
+
+/// Streaming element decompressor.
+struct Decoder<R> {
+    input: R,
+    compression: Compression,
+    /// Current run element, and how many times it remains.
+    run: (Vec<u8>, u32),
+    /// Current LZ block, and read position.
+    block: (Vec<u8>, usize),
+    /// Bytes per element.
+    size: usize,
+    /// Elements which the header declares, but have not been read.
+    remaining: usize,
+}
+
+impl<R: Read> Decoder<R> {
+    fn new(input: R, compression: Compression, size: usize, total: usize) -> Self {
+        Decoder {
+            input,
+            compression,
+            run: (vec![0; size], 0),
+            block: (Vec::new(), 0),
+            size,
+            remaining: total,
+        }
+    }
+    
+    fn next(&mut self, elem: &mut [u8]) -> Result<(), Error> {
+        match self.compression {
+            Compression::None => {
+                self.input.read_exact(elem)?;
+            }
+            Compression::Rle => {
+                if self.run.1 == 0 {
+                    let count = u32::from_le_bytes(read_array(&mut self.input)?);
+                    if count == 0 {
+                        return Err(Error::Corrupt("empty run"));
+                    }
+                    if count as usize > self.remaining {
+                        return Err(Error::Corrupt("run is longer than the grid"));
+                    }
+                    self.input.read_exact(&mut self.run.0)?;
+                    self.run.1 = count;
+                }
+                elem.copy_from_slice(&self.run.0);
+                self.run.1 -= 1;
+            }
+            Compression::Lz => {
+                if self.block.1 == self.block.0.len() {
+                    let len = u32::from_le_bytes(read_array(&mut self.input)?) as usize;
+                    let compressed_len = u32::from_le_bytes(read_array(&mut self.input)?) as usize;
+                    if len == 0 || len > BLOCK_SIZE || !len.is_multiple_of(self.size) {
+                        return Err(Error::Corrupt("invalid block length"));
+                    }
+                    if len / self.size > self.remaining {
+                        return Err(Error::Corrupt("block is longer than the grid"));
+                    }
+                    if compressed_len > 2 * BLOCK_SIZE {
+                        return Err(Error::Corrupt("invalid compressed block length"));
+                    }
+                    let mut compressed = vec![0; compressed_len];
+                    self.input.read_exact(&mut compressed)?;
+                    let block = lz::decompress(&compressed, len)
+                        .ok_or(Error::Corrupt("invalid compressed block"))?;
+                    self.block = (block, 0);
+                }
+                let (ref block, ref mut pos) = self.block;
+                elem.copy_from_slice(&block[*pos..*pos + self.size]);
+                *pos += self.size;
+            }
+        }
+        self.remaining -= 1;
+        Ok(())
+    }
+}
+
+fn header_bounds<B, S>(bound: &B) -> Range<i64>
+where
+    B: BoundRange<S>,
+    S: Scalar,
+{
+    bound.lower_inclusive().to_i64()..bound.upper_exclusive().to_i64()
+}
+
+/// Check a header against the element type and dimensionality,
+/// returning the length of each axis.
+fn check_header<T, S>(header: &Header, dims: usize) -> Result<Vec<S>, Error>
+where
+    T: Element,
+    S: Scalar,
+{
+    if header.bounds.len() != dims {
+        return Err(Error::WrongDimensions {
+            expected: dims,
+            found: header.bounds.len(),
+        });
+    }
+    if header.element_size as usize != T::SIZE {
+        return Err(Error::WrongElementSize {
+            expected: T::SIZE,
+            found: header.element_size as usize,
+        });
+    }
+    if T::SIZE == 0 && header.compression == Compression::Lz {
+        return Err(Error::Corrupt("LZ compression of empty elements"));
+    }
+    let mut total = 1usize;
+    header.bounds.iter()
+        .map(|bound| {
+            let len = bound.end - bound.start;
+            let n = usize::try_from(len).map_err(|_| Error::TooLarge)?;
+            total = total.checked_mul(n).ok_or(Error::TooLarge)?;
+            let s = S::from_i64(len);
+            if s.to_i64() != len {
+                return Err(Error::TooLarge);
+            }
+            Ok(s)
+        })
+        .collect()
+}
+
+/// Read the elements of a file, after its header.
+///
+/// The header's lengths are untrusted, so the buffer grows as
+/// elements are actually read, rather than being allocated up front,
+/// and runs or blocks which would decode past the declared number
+/// of elements are rejected before they are expanded.
+fn read_elements<T, S, R>(header: &Header, input: R, lens: &[S]) -> Result<Vec<T>, Error>
+where
+    T: Element,
+    S: Scalar,
+    R: Read,
+{
+    let total: usize = lens.iter().map(|len| len.to_usize()).product();
+    let mut decoder = Decoder::new(input, header.compression, T::SIZE, total);
+    let mut buf = vec![0; T::SIZE];
+    let mut elems = Vec::new();
+    for _ in 0..total {
+        decoder.next(&mut buf)?;
+        elems.try_reserve(1).map_err(|_| Error::TooLarge)?;
+        elems.push(T::read_bytes(&buf));
+    }
+    Ok(elems)
+}
+
+/// Write a bounded Grid2.
+pub fn write_grid2<G, W, S>(grid: &G, mut out: W, compression: Compression) -> io::Result<()>
+where
+    G: Grid2Get<S>,
+    G::Item: Element,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    W: Write,
+    S: Scalar,
+{
+    let x_bound = grid.x_bound();
+    let y_bound = grid.y_bound();
+    write_header(&mut out, &Header {
+        version: VERSION,
+        compression,
+        element_size: G::Item::SIZE as u32,
+        bounds: vec![header_bounds(&x_bound), header_bounds(&y_bound)],
+    })?;
+    
+    let mut encoder = Encoder::new(out, compression, G::Item::SIZE);
+    let mut buf = vec![0; G::Item::SIZE];
+    let mut y = y_bound.lower_inclusive();
+    while y < y_bound.upper_exclusive() {
+        let mut x = x_bound.lower_inclusive();
+        while x < x_bound.upper_exclusive() {
+            grid.get(Vector2 { x, y }).write_bytes(&mut buf);
+            encoder.push(&buf)?;
+            x = x + S::ONE;
+        }
+        y = y + S::ONE;
+    }
+    encoder.finish()
+}
+
+/// Write a bounded Grid3.
+pub fn write_grid3<G, W, S>(grid: &G, mut out: W, compression: Compression) -> io::Result<()>
+where
+    G: Grid3Get<S>,
+    G::Item: Element,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    W: Write,
+    S: Scalar,
+{
+    let x_bound = grid.x_bound();
+    let y_bound = grid.y_bound();
+    let z_bound = grid.z_bound();
+    write_header(&mut out, &Header {
+        version: VERSION,
+        compression,
+        element_size: G::Item::SIZE as u32,
+        bounds: vec![
+            header_bounds(&x_bound),
+            header_bounds(&y_bound),
+            header_bounds(&z_bound),
+        ],
+    })?;
+    
+    let mut encoder = Encoder::new(out, compression, G::Item::SIZE);
+    let mut buf = vec![0; G::Item::SIZE];
+    let mut z = z_bound.lower_inclusive();
+    while z < z_bound.upper_exclusive() {
+        let mut y = y_bound.lower_inclusive();
+        while y < y_bound.upper_exclusive() {
+            let mut x = x_bound.lower_inclusive();
+            while x < x_bound.upper_exclusive() {
+                grid.get(Vector3 { x, y, z }).write_bytes(&mut buf);
+                encoder.push(&buf)?;
+                x = x + S::ONE;
+            }
+            y = y + S::ONE;
+        }
+        z = z + S::ONE;
+    }
+    encoder.finish()
+}
+
+/// Read a Grid2 file.
+pub fn read_grid2<T, S, R>(mut input: R) -> Result<ArrayGrid2<T, S>, Error>
+where
+    T: Element,
+    S: Scalar,
+    R: Read,
+{
+    let header = read_header(&mut input)?;
+    read_grid2_body(&header, input)
+}
+
+/// Read the elements of a Grid2 file, after its header.
+pub fn read_grid2_body<T, S, R>(header: &Header, input: R) -> Result<ArrayGrid2<T, S>, Error>
+where
+    T: Element,
+    S: Scalar,
+    R: Read,
+{
+    let lens = check_header::<T, S>(header, 2)?;
+    let mut elems = read_elements(header, input, &lens)?.into_iter();
+    Ok(ArrayGrid2::new(lens[0], lens[1], |_: Vector2<S>| elems.next().unwrap()))
+}
+
+/// Read a Grid3 file.
+pub fn read_grid3<T, S, R>(mut input: R) -> Result<ArrayGrid3<T, S>, Error>
+where
+    T: Element,
+    S: Scalar,
+    R: Read,
+{
+    let header = read_header(&mut input)?;
+    read_grid3_body(&header, input)
+}
+
+/// Read the elements of a Grid3 file, after its header.
+pub fn read_grid3_body<T, S, R>(header: &Header, input: R) -> Result<ArrayGrid3<T, S>, Error>
+where
+    T: Element,
+    S: Scalar,
+    R: Read,
+{
+    let lens = check_header::<T, S>(header, 3)?;
+    let mut elems = read_elements(header, input, &lens)?.into_iter();
+    Ok(ArrayGrid3::new(lens[0], lens[1], lens[2], |_: Vector3<S>| elems.next().unwrap()))
+}
@@ -0,0 +1,114 @@
+//! Minimal LZ77 block compression.
+//!
+//! A compressed block is a sequence of tokens. A literal token is a
+//! 0 byte, a varint length, and that many bytes. A match token is a
+//! 1 byte, a varint length, and a varint offset back into the
+//! already-decompressed output.
+
+const MIN_MATCH: usize = 4;
+const HASH_BITS: u32 = 12;
+
+fn write_varint(out: &mut Vec<u8>, mut n: usize) {
+    while n >= 0x80 {
+        out.push((n as u8 & 0x7f) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn read_varint(input: &[u8], pos: &mut usize) -> Option<usize> {
+    let mut n = 0usize;
+    let mut shift = 0;
+    loop {
+        let byte = *input.get(*pos)?;
+        *pos += 1;
+        if shift >= usize::BITS {
+            return None;
+        }
+        n |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return Some(n);
+        }
+        shift += 7;
+    }
+}
+
+fn hash(bytes: &[u8]) -> usize {
+    let n = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    (n.wrapping_mul(2654435761) >> (32 - HASH_BITS)) as usize
+}
+
+fn flush_literals(out: &mut Vec<u8>, literals: &[u8]) {
+    if !literals.is_empty() {
+        out.push(0);
+        write_varint(out, literals.len());
+        out.extend_from_slice(literals);
+    }
+}
+
+/// Compress a block.
+pub(crate) fn compress(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut table = vec![usize::MAX; 1 << HASH_BITS];
+    let mut literal_start = 0;
+    let mut i = 0;
+    while i + MIN_MATCH <= input.len() {
+        let h = hash(&input[i..]);
+        let candidate = table[h];
+        table[h] = i;
+        if candidate != usize::MAX && input[candidate..candidate + MIN_MATCH] == input[i..i + MIN_MATCH] {
+            let mut len = MIN_MATCH;
+            while i + len < input.len() && input[candidate + len] == input[i + len] {
+                len += 1;
+            }
+            flush_literals(&mut out, &input[literal_start..i]);
+            out.push(1);
+            write_varint(&mut out, len);
+            write_varint(&mut out, i - candidate);
+            i += len;
+            literal_start = i;
+        } else {
+            i += 1;
+        }
+    }
+    flush_literals(&mut out, &input[literal_start..]);
+    out
+}
+
+/// Decompress a block, which must decompress to exactly len bytes.
+pub(crate) fn decompress(input: &[u8], len: usize) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(len);
+    let mut pos = 0;
+    while pos < input.len() {
+        let tag = input[pos];
+        pos += 1;
+        let n = read_varint(input, &mut pos)?;
+        if n > len - out.len() {
+            return None;
+        }
+        match tag {
+            0 => {
+                let literals = input.get(pos..pos.checked_add(n)?)?;
+                out.extend_from_slice(literals);
+                pos += n;
+            }
+            1 => {
+                let offset = read_varint(input, &mut pos)?;
+                if offset == 0 || offset > out.len() {
+                    return None;
+                }
+                // matches may overlap their own output
+                let start = out.len() - offset;
+                for j in 0..n {
+                    out.push(out[start + j]);
+                }
+            }
+            _ => return None,
+        }
+    }
+    if out.len() == len {
+        Some(out)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,9 @@
+//! Reading and writing grids.
+
+/// Compact binary format.
+pub mod binary;
+
+/// MagicaVoxel files.
+pub mod vox;
+
+mod lz;
@@ -0,0 +1,343 @@
+//! MagicaVoxel `.vox` files.
+//!
+//! Models are read into grids of palette indices, where 0 is an
+//! empty voxel. Coordinates are used as-is, so z is up.
+//!
+//! Only the SIZE, XYZI and RGBA chunks are interpreted. Other
+//! chunks, such as the scene graph and materials, are skipped when
+//! reading and not written.
+
+use crate::{
+    scalar::Scalar,
+    range::BoundRange,
+    grid3::{
+        Grid3Get,
+        Grid3Set,
+        backends::heap::ArrayGrid3,
+    },
+};
+use mint::Vector3;
+use std::{
+    io::{self, Read, Write},
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    error,
+};
+
+/// Bytes which begin every file.
+pub const MAGIC: [u8; 4] = *b"VOX ";
+
+/// File versions which can be read.
+pub const SUPPORTED_VERSIONS: [i32; 2] = [150, 200];
+
+/// Version written by this module.
+pub const VERSION: i32 = 150;
+
+/// Maximum length of a model along any axis.
+pub const MAX_LEN: i32 = 256;
+
+/// RGBA colors, indexed by palette index.
+///
+/// Index 0 is the empty voxel, and its color is unused.
+pub type Palette = [[u8; 4]; 256];
+
+/// Contents of a file.
+pub struct Vox {
+    pub models: Vec<ArrayGrid3<u8>>,
+    pub palette: Palette,
+}
+
+/// Error reading or writing a file.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The file ended early.
+    Truncated,
+    /// The file does not begin with `MAGIC`.
+    BadMagic([u8; 4]),
+    UnsupportedVersion(i32),
+    /// A model is larger than `MAX_LEN` along some axis.
+    TooLarge,
+    /// The chunk structure is invalid.
+    Corrupt(&'static str),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "io error: {}", e),
+            Error::Truncated => write!(f, "file is truncated"),
+            Error::BadMagic(magic) => write!(f, "bad magic bytes {:?}", magic),
+            Error::UnsupportedVersion(version) => write!(f, "unsupported version {}", version),
+            Error::TooLarge => write!(f, "model is larger than {} voxels along an axis", MAX_LEN),
+            Error::Corrupt(reason) => write!(f, "corrupt file: {}", reason),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            Error::Truncated
+        } else {
+            Error::Io(e)
+        }
+    }
+}
+
+/// Palette used by files without an RGBA chunk.
+///
+/// This is a 6×6×6 color cube, followed by red, green, blue and
+/// gray ramps.
+pub fn default_palette() -> Palette {
+    const CUBE: [u8; 6] = [0xff, 0xcc, 0x99, 0x66, 0x33, 0x00];
+    const RAMP: [u8; 10] = [0xee, 0xdd, 0xbb, 0xaa, 0x88, 0x77, 0x55, 0x44, 0x22, 0x11];
+    
+    let mut palette = [[0; 4]; 256];
+    let mut i = 1;
+    for &r in &CUBE {
+        for &g in &CUBE {
+            for &b in &CUBE {
+                if (r, g, b) != (0, 0, 0) {
+                    palette[i] = [r, g, b, 0xff];
+                    i += 1;
+                }
+            }
+        }
+    }
+    for channel in 0..4 {
+        for &v in &RAMP {
+            palette[i] = match channel {
+                0 => [v, 0, 0, 0xff],
+                1 => [0, v, 0, 0xff],
+                2 => [0, 0, v, 0xff],
+                _ => [v, v, v, 0xff],
+            };
+            i += 1;
+        }
+    }
+    palette
+}
+
+/// Cursor over the bytes of a file.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if n > self.bytes.len() {
+            return Err(Error::Truncated);
+        }
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(taken)
+    }
+    
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+    
+    fn i32(&mut self) -> Result<i32, Error> {
+        self.array().map(i32::from_le_bytes)
+    }
+    
+    fn len(&mut self) -> Result<usize, Error> {
+        usize::try_from(self.i32()?).map_err(|_| Error::Corrupt("negative length"))
+    }
+    
+    /// Read a chunk's id, content and children.
+    fn chunk(&mut self) -> Result<([u8; 4], Cursor<'a>, Cursor<'a>), Error> {
+        let id = self.array()?;
+        let content_len = self.len()?;
+        let children_len = self.len()?;
+        let content = Cursor { bytes: self.take(content_len)? };
+        let children = Cursor { bytes: self.take(children_len)? };
+        Ok((id, content, children))
+    }
+}
+
+/// Read a file.
+pub fn read<R: Read>(mut input: R) -> Result<Vox, Error> {
+    let mut bytes = Vec::new();
+    input.read_to_end(&mut bytes)?;
+    let mut cursor = Cursor { bytes: &bytes };
+    
+    let magic = cursor.array()?;
+    if magic != MAGIC {
+        return Err(Error::BadMagic(magic));
+    }
+    let version = cursor.i32()?;
+    if !SUPPORTED_VERSIONS.contains(&version) {
+        return Err(Error::UnsupportedVersion(version));
+    }
+    let (id, _, mut children) = cursor.chunk()?;
+    if &id != b"MAIN" {
+        return Err(Error::Corrupt("first chunk is not MAIN"));
+    }
+    
+    let mut vox = Vox {
+        models: Vec::new(),
+        palette: default_palette(),
+    };
+    let mut size = None;
+    while !children.bytes.is_empty() {
+        let (id, mut content, _) = children.chunk()?;
+        match &id {
+            b"SIZE" => {
+                let x = content.i32()?;
+                let y = content.i32()?;
+                let z = content.i32()?;
+                if [x, y, z].iter().any(|&n| n < 0) {
+                    return Err(Error::Corrupt("negative model size"));
+                }
+                if [x, y, z].iter().any(|&n| n > MAX_LEN) {
+                    return Err(Error::TooLarge);
+                }
+                size = Some([x, y, z]);
+            }
+            b"XYZI" => {
+                let [x_len, y_len, z_len] = size.take()
+                    .ok_or(Error::Corrupt("XYZI chunk without SIZE chunk"))?;
+                let mut model = ArrayGrid3::broadcast(x_len, y_len, z_len, 0);
+                let count = content.len()?;
+                for _ in 0..count {
+                    let [x, y, z, i] = content.array()?;
+                    let coord = Vector3 {
+                        x: x as i32,
+                        y: y as i32,
+                        z: z as i32,
+                    };
+                    model.try_set(coord, i)
+                        .map_err(|_| Error::Corrupt("voxel outside model"))?;
+                }
+                vox.models.push(model);
+            }
+            b"RGBA" => {
+                // entry i colors palette index i + 1
+                for i in 1..256 {
+                    vox.palette[i] = content.array()?;
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(vox)
+}
+
+/// Write a chunk header.
+fn write_chunk_header<W: Write>(out: &mut W, id: &[u8; 4], content_len: usize, children_len: usize) -> Result<(), Error> {
+    let content_len = i32::try_from(content_len).map_err(|_| Error::TooLarge)?;
+    let children_len = i32::try_from(children_len).map_err(|_| Error::TooLarge)?;
+    out.write_all(id)?;
+    out.write_all(&content_len.to_le_bytes())?;
+    out.write_all(&children_len.to_le_bytes())?;
+    Ok(())
+}
+
+/// Write a file.
+///
+/// Each model must be bounded and at most `MAX_LEN` long along each
+/// axis. Coordinates are written relative to the model's lower
+/// bounds, and elements of 0 are empty.
+pub fn write<G, W, S>(mut out: W, models: &[G], palette: &Palette) -> Result<(), Error>
+where
+    G: Grid3Get<S, Item=u8>,
+    G::XBound: BoundRange<S>,
+    G::YBound: BoundRange<S>,
+    G::ZBound: BoundRange<S>,
+    W: Write,
+    S: Scalar,
+{
+    // the voxel counts must be known before writing
+    let mut sizes = Vec::with_capacity(models.len());
+    let mut voxels = Vec::with_capacity(models.len());
+    for model in models {
+        let x_bound = model.x_bound();
+        let y_bound = model.y_bound();
+        let z_bound = model.z_bound();
+        let lower = [
+            x_bound.lower_inclusive(),
+            y_bound.lower_inclusive(),
+            z_bound.lower_inclusive(),
+        ];
+        let upper = [
+            x_bound.upper_exclusive(),
+            y_bound.upper_exclusive(),
+            z_bound.upper_exclusive(),
+        ];
+        let mut size = [0; 3];
+        for axis in 0..3 {
+            let len = (upper[axis] - lower[axis]).to_i64();
+            if len > MAX_LEN as i64 {
+                return Err(Error::TooLarge);
+            }
+            size[axis] = len as i32;
+        }
+        sizes.push(size);
+        
+        let mut model_voxels = Vec::new();
+        for z in 0..size[2] {
+            for y in 0..size[1] {
+                for x in 0..size[0] {
+                    let i = model.get(Vector3 {
+                        x: lower[0] + S::from_i64(x as i64),
+                        y: lower[1] + S::from_i64(y as i64),
+                        z: lower[2] + S::from_i64(z as i64),
+                    });
+                    if i != 0 {
+                        model_voxels.push([x as u8, y as u8, z as u8, i]);
+                    }
+                }
+            }
+        }
+        voxels.push(model_voxels);
+    }
+    
+    let chunk_len = |content_len: usize| 12 + content_len;
+    let pack = models.len() != 1;
+    let mut children_len = chunk_len(4 * 256);
+    if pack {
+        children_len += chunk_len(4);
+    }
+    for model_voxels in &voxels {
+        children_len += chunk_len(12) + chunk_len(4 + 4 * model_voxels.len());
+    }
+    
+    out.write_all(&MAGIC)?;
+    out.write_all(&VERSION.to_le_bytes())?;
+    write_chunk_header(&mut out, b"MAIN", 0, children_len)?;
+    if pack {
+        write_chunk_header(&mut out, b"PACK", 4, 0)?;
+        out.write_all(&(models.len() as i32).to_le_bytes())?;
+    }
+    for (size, model_voxels) in sizes.iter().zip(&voxels) {
+        write_chunk_header(&mut out, b"SIZE", 12, 0)?;
+        for n in size {
+            out.write_all(&n.to_le_bytes())?;
+        }
+        write_chunk_header(&mut out, b"XYZI", 4 + 4 * model_voxels.len(), 0)?;
+        out.write_all(&(model_voxels.len() as i32).to_le_bytes())?;
+        for voxel in model_voxels {
+            out.write_all(voxel)?;
+        }
+    }
+    write_chunk_header(&mut out, b"RGBA", 4 * 256, 0)?;
+    for color in &palette[1..] {
+        out.write_all(color)?;
+    }
+    out.write_all(&[0; 4])?;
+    out.flush()?;
+    Ok(())
+}
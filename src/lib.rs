@@ -1,8 +1,29 @@
+#![allow(clippy::type_complexity)]
 
 extern crate mint;
+#[cfg(feature = "mmap")]
+extern crate memmap2;
+
+pub mod scalar;
 
 pub mod range;
 
 pub mod grid2;
 
 pub mod grid3;
+
+pub mod gridn;
+
+pub mod view;
+
+pub mod sample;
+
+pub mod algo;
+
+pub mod noise;
+
+pub mod diff;
+
+pub mod io;
+
+pub mod concurrent;
@@ -0,0 +1,586 @@
+//! Procedural noise grids.
+//!
+//! Every noise type here is a seeded, deterministic function of
+//! position, which implements the `Noise` trait for sampling at
+//! continuous `f64` positions. `Noise::grid2` and `Noise::grid3`
+//! view a noise as an unbounded `Grid2` or `Grid3` of `f32` for
+//! sampling at integer coordinates. Noise types do not implement
+//! the grid traits themselves, as a type implementing both `Grid2`
+//! and `Grid3` would make every combinator call on it ambiguous,
+//! so one of these adapters is required to use noise as a grid.
+//!
+//! Positions are multiplied by the noise's frequency before
+//! sampling, so features are roughly `1 / frequency` elements
+//! wide. The default frequency is `1 / 16`.
+//!
+//! Positions are split into an i64 lattice cell and an offset
+//! within it before any `f32` arithmetic, so integer coordinates
+//! up to 2^53 in magnitude, where `f64` is still exact, sample
+//! distinct noise.
+
+use crate::{
+    scalar::Scalar,
+    grid2::{Grid2, Grid2Get},
+    grid3::{Grid3, Grid3Get},
+};
+use mint::{Vector2, Vector3};
+use std::{
+    ops::RangeFull,
+    marker::PhantomData,
+};
+
+const DEFAULT_FREQUENCY: f64 = 1.0 / 16.0;
+
+/// Continuous noise function.
+pub trait Noise {
+    fn noise2(&self, x: f64, y: f64) -> f32;
+    
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f32;
+    
+    /// View this noise as an unbounded Grid2.
+    fn grid2<S: Scalar>(self) -> NoiseGrid2<Self, S>
+    where
+        Self: Sized,
+    {
+        NoiseGrid2::new(self)
+    }
+    
+    /// View this noise as an unbounded Grid3.
+    fn grid3<S: Scalar>(self) -> NoiseGrid3<Self, S>
+    where
+        Self: Sized,
+    {
+        NoiseGrid3::new(self)
+    }
+}
+
+impl<N: Noise + ?Sized> Noise for &N {
+    fn noise2(&self, x: f64, y: f64) -> f32 { N::noise2(self, x, y) }
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f32 { N::noise3(self, x, y, z) }
+}
+
+/// Fold a lattice coordinate into 32 bits.
+fn fold(i: i64) -> u32 {
+    i as u32 ^ (i >> 32) as u32
+}
+
+/// Hash a lattice point to 32 pseudo-random bits.
+///
+/// Lattice coordinates are i64, and are offset with wrapping
+/// arithmetic, so that neighbouring cells of any lattice cell can
+/// be hashed without overflow.
+fn hash(seed: u32, x: i64, y: i64, z: i64) -> u32 {
+    let mut h = seed
+        ^ fold(x).wrapping_mul(0x27d4_eb2d)
+        ^ fold(y).wrapping_mul(0x1656_67b1)
+        ^ fold(z).wrapping_mul(0x9e37_79b9);
+    h = (h ^ (h >> 15)).wrapping_mul(0x85eb_ca6b);
+    h = (h ^ (h >> 13)).wrapping_mul(0xc2b2_ae35);
+    h ^ (h >> 16)
+}
+
+/// Hash a lattice point to a value in [-1, 1].
+fn hash_unit(seed: u32, x: i64, y: i64, z: i64) -> f32 {
+    hash(seed, x, y, z) as f32 / u32::MAX as f32 * 2.0 - 1.0
+}
+
+/// Split a position into its lattice cell and the offset within it.
+fn split(x: f64) -> (i64, f32) {
+    let floor = x.floor();
+    (floor as i64, (x - floor) as f32)
+}
+
+/// Quintic fade curve, with zero first and second derivatives
+/// at 0 and 1.
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Dot product of a hashed 2D gradient with an offset.
+fn grad2(h: u32, x: f32, y: f32) -> f32 {
+    match h & 7 {
+        0 => x + y,
+        1 => x - y,
+        2 => -x + y,
+        3 => -x - y,
+        4 => x,
+        5 => -x,
+        6 => y,
+        _ => -y,
+    }
+}
+
+/// Dot product of a hashed 3D gradient, from the edges of a
+/// cube, with an offset.
+fn grad3(h: u32, x: f32, y: f32, z: f32) -> f32 {
+    match h % 12 {
+        0 => x + y,
+        1 => -x + y,
+        2 => x - y,
+        3 => -x - y,
+        4 => x + z,
+        5 => -x + z,
+        6 => x - z,
+        7 => -x - z,
+        8 => y + z,
+        9 => -y + z,
+        10 => y - z,
+        _ => -y - z,
+    }
+}
+
+macro_rules! base_noise {
+    ($name:ident) => {
+        impl $name {
+            pub fn new(seed: u32) -> Self {
+                $name {
+                    seed,
+                    frequency: DEFAULT_FREQUENCY,
+                }
+            }
+            
+            /// Set the frequency.
+            pub fn frequency(mut self, frequency: f64) -> Self {
+                self.frequency = frequency;
+                self
+            }
+        }
+    };
+}
+
+/// Noise sampled at the integer coordinates of a Grid2.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NoiseGrid2<N, S = i32> {
+    noise: N,
+    p: PhantomData<S>,
+}
+
+impl<N, S> NoiseGrid2<N, S> {
+    pub fn new(noise: N) -> Self {
+        NoiseGrid2 {
+            noise,
+            p: PhantomData,
+        }
+    }
+    
+    pub fn into_inner(self) -> N {
+        self.noise
+    }
+}
+
+impl<N: Noise, S: Scalar> Grid2<S> for NoiseGrid2<N, S> {
+    type Item = f32;
+    type XBound = RangeFull;
+    type YBound = RangeFull;
+    
+    fn x_bound(&self) -> RangeFull { RangeFull }
+    fn y_bound(&self) -> RangeFull { RangeFull }
+}
+
+impl<N: Noise, S: Scalar> Grid2Get<S> for NoiseGrid2<N, S> {
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        let Vector2 { x, y } = coord.into();
+        self.noise.noise2(x.to_i64() as f64, y.to_i64() as f64)
+    }
+}
+
+/// Noise sampled at the integer coordinates of a Grid3.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NoiseGrid3<N, S = i32> {
+    noise: N,
+    p: PhantomData<S>,
+}
+
+impl<N, S> NoiseGrid3<N, S> {
+    pub fn new(noise: N) -> Self {
+        NoiseGrid3 {
+            noise,
+            p: PhantomData,
+        }
+    }
+    
+    pub fn into_inner(self) -> N {
+        self.noise
+    }
+}
+
+impl<N: Noise, S: Scalar> Grid3<S> for NoiseGrid3<N, S> {
+    type Item = f32;
+    type XBound = RangeFull;
+    type YBound = RangeFull;
+    type ZBound = RangeFull;
+    
+    fn x_bound(&self) -> RangeFull { RangeFull }
+    fn y_bound(&self) -> RangeFull { RangeFull }
+    fn z_bound(&self) -> RangeFull { RangeFull }
+}
+
+impl<N: Noise, S: Scalar> Grid3Get<S> for NoiseGrid3<N, S> {
+    fn get<I>(&self, coord: I) -> Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        let Vector3 { x, y, z } = coord.into();
+        self.noise.noise3(x.to_i64() as f64, y.to_i64() as f64, z.to_i64() as f64)
+    }
+}
+
+/// Value noise, which interpolates random values at lattice points.
+///
+/// Output is in [-1, 1].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Value {
+    seed: u32,
+    frequency: f64,
+}
+
+base_noise!(Value);
+
+impl Noise for Value {
+    fn noise2(&self, x: f64, y: f64) -> f32 {
+        let ((x0, fx), (y0, fy)) = (split(x * self.frequency), split(y * self.frequency));
+        let (tx, ty) = (fade(fx), fade(fy));
+        let v = |i: i64, j: i64| hash_unit(self.seed, x0.wrapping_add(i), y0.wrapping_add(j), 0);
+        lerp(
+            lerp(v(0, 0), v(1, 0), tx),
+            lerp(v(0, 1), v(1, 1), tx),
+            ty)
+    }
+    
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f32 {
+        let (x0, fx) = split(x * self.frequency);
+        let (y0, fy) = split(y * self.frequency);
+        let (z0, fz) = split(z * self.frequency);
+        let (tx, ty, tz) = (fade(fx), fade(fy), fade(fz));
+        let v = |i: i64, j: i64, k: i64| hash_unit(
+            self.seed,
+            x0.wrapping_add(i),
+            y0.wrapping_add(j),
+            z0.wrapping_add(k));
+        let plane = |k: i64| lerp(
+            lerp(v(0, 0, k), v(1, 0, k), tx),
+            lerp(v(0, 1, k), v(1, 1, k), tx),
+            ty);
+        lerp(plane(0), plane(1), tz)
+    }
+}
+
+/// Perlin gradient noise.
+///
+/// Output is roughly in [-1, 1], and is 0 at lattice points.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Perlin {
+    seed: u32,
+    frequency: f64,
+}
+
+base_noise!(Perlin);
+
+impl Noise for Perlin {
+    fn noise2(&self, x: f64, y: f64) -> f32 {
+        let ((x0, fx), (y0, fy)) = (split(x * self.frequency), split(y * self.frequency));
+        let (tx, ty) = (fade(fx), fade(fy));
+        let g = |i: i64, j: i64| grad2(
+            hash(self.seed, x0.wrapping_add(i), y0.wrapping_add(j), 0),
+            fx - i as f32,
+            fy - j as f32);
+        lerp(
+            lerp(g(0, 0), g(1, 0), tx),
+            lerp(g(0, 1), g(1, 1), tx),
+            ty)
+    }
+    
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f32 {
+        let (x0, fx) = split(x * self.frequency);
+        let (y0, fy) = split(y * self.frequency);
+        let (z0, fz) = split(z * self.frequency);
+        let (tx, ty, tz) = (fade(fx), fade(fy), fade(fz));
+        let g = |i: i64, j: i64, k: i64| grad3(
+            hash(self.seed, x0.wrapping_add(i), y0.wrapping_add(j), z0.wrapping_add(k)),
+            fx - i as f32,
+            fy - j as f32,
+            fz - k as f32);
+        let plane = |k: i64| lerp(
+            lerp(g(0, 0, k), g(1, 0, k), tx),
+            lerp(g(0, 1, k), g(1, 1, k), tx),
+            ty);
+        lerp(plane(0), plane(1), tz)
+    }
+}
+
+/// Simplex gradient noise, which has fewer directional artifacts
+/// than Perlin noise.
+///
+/// Output is roughly in [-1, 1].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Simplex {
+    seed: u32,
+    frequency: f64,
+}
+
+base_noise!(Simplex);
+
+impl Noise for Simplex {
+    fn noise2(&self, x: f64, y: f64) -> f32 {
+        const F2: f64 = 0.366_025_403_784_438_6; // (sqrt(3) - 1) / 2
+        const G2: f64 = 0.211_324_865_405_187_1; // (3 - sqrt(3)) / 6
+        
+        let (x, y) = (x * self.frequency, y * self.frequency);
+        
+        // skew into the simplex lattice to find the containing cell,
+        // and unskew its offset within that cell
+        let s = (x + y) * F2;
+        let g2 = G2 as f32;
+        let ((i, sx), (j, sy)) = (split(x + s), split(y + s));
+        let t = (sx + sy) * g2;
+        let (x0, y0) = (sx - t, sy - t);
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+        
+        let corners = [
+            (0, 0, x0, y0),
+            (i1, j1, x0 - i1 as f32 + g2, y0 - j1 as f32 + g2),
+            (1, 1, x0 - 1.0 + 2.0 * g2, y0 - 1.0 + 2.0 * g2),
+        ];
+        let mut sum = 0.0;
+        for &(di, dj, cx, cy) in &corners {
+            let falloff = 0.5 - cx * cx - cy * cy;
+            if falloff > 0.0 {
+                let falloff = falloff * falloff;
+                sum += falloff * falloff * grad2(hash(self.seed, i.wrapping_add(di), j.wrapping_add(dj), 0), cx, cy);
+            }
+        }
+        70.0 * sum
+    }
+    
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f32 {
+        const F3: f64 = 1.0 / 3.0;
+        const G3: f32 = 1.0 / 6.0;
+        
+        let (x, y, z) = (x * self.frequency, y * self.frequency, z * self.frequency);
+        
+        let s = (x + y + z) * F3;
+        let ((i, sx), (j, sy), (k, sz)) = (split(x + s), split(y + s), split(z + s));
+        let t = (sx + sy + sz) * G3;
+        let (x0, y0, z0) = (sx - t, sy - t, sz - t);
+        
+        // order the axes by offset to find the containing simplex
+        let (first, second) = if x0 >= y0 {
+            if y0 >= z0 {
+                ((1, 0, 0), (1, 1, 0))
+            } else if x0 >= z0 {
+                ((1, 0, 0), (1, 0, 1))
+            } else {
+                ((0, 0, 1), (1, 0, 1))
+            }
+        } else if y0 < z0 {
+            ((0, 0, 1), (0, 1, 1))
+        } else if x0 < z0 {
+            ((0, 1, 0), (0, 1, 1))
+        } else {
+            ((0, 1, 0), (1, 1, 0))
+        };
+        
+        let mut sum = 0.0;
+        for (n, &(di, dj, dk)) in [(0, 0, 0), first, second, (1, 1, 1)].iter().enumerate() {
+            let cx = x0 - di as f32 + n as f32 * G3;
+            let cy = y0 - dj as f32 + n as f32 * G3;
+            let cz = z0 - dk as f32 + n as f32 * G3;
+            let falloff = 0.6 - cx * cx - cy * cy - cz * cz;
+            if falloff > 0.0 {
+                let falloff = falloff * falloff;
+                let h = hash(self.seed, i.wrapping_add(di), j.wrapping_add(dj), k.wrapping_add(dk));
+                sum += falloff * falloff * grad3(h, cx, cy, cz);
+            }
+        }
+        32.0 * sum
+    }
+}
+
+/// Worley (cellular) noise.
+///
+/// Each lattice cell contains one randomly placed feature point,
+/// and the output is the distance to the nearest feature point,
+/// in cells, which is in [0, 1.5] in 2D and [0, 1.8] in 3D.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Worley {
+    seed: u32,
+    frequency: f64,
+}
+
+base_noise!(Worley);
+
+impl Worley {
+    /// Position of a cell's feature point within the cell, on
+    /// one axis.
+    fn feature(&self, axis: u32, i: i64, j: i64, k: i64) -> f32 {
+        let h = hash(self.seed.wrapping_add(axis.wrapping_mul(0x68e3_1da4)), i, j, k);
+        h as f32 / u32::MAX as f32
+    }
+}
+
+impl Noise for Worley {
+    fn noise2(&self, x: f64, y: f64) -> f32 {
+        let ((x0, fx), (y0, fy)) = (split(x * self.frequency), split(y * self.frequency));
+        let mut nearest = f32::INFINITY;
+        for dj in -1..=1 {
+            let j = y0.wrapping_add(dj);
+            for di in -1..=1 {
+                let i = x0.wrapping_add(di);
+                let dx = di as f32 + self.feature(0, i, j, 0) - fx;
+                let dy = dj as f32 + self.feature(1, i, j, 0) - fy;
+                nearest = nearest.min(dx * dx + dy * dy);
+            }
+        }
+        nearest.sqrt()
+    }
+    
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f32 {
+        let (x0, fx) = split(x * self.frequency);
+        let (y0, fy) = split(y * self.frequency);
+        let (z0, fz) = split(z * self.frequency);
+        let mut nearest = f32::INFINITY;
+        for dk in -1..=1 {
+            let k = z0.wrapping_add(dk);
+            for dj in -1..=1 {
+                let j = y0.wrapping_add(dj);
+                for di in -1..=1 {
+                    let i = x0.wrapping_add(di);
+                    let dx = di as f32 + self.feature(0, i, j, k) - fx;
+                    let dy = dj as f32 + self.feature(1, i, j, k) - fy;
+                    let dz = dk as f32 + self.feature(2, i, j, k) - fz;
+                    nearest = nearest.min(dx * dx + dy * dy + dz * dz);
+                }
+            }
+        }
+        nearest.sqrt()
+    }
+}
+
+/// Offset applied to each successive octave, so that octaves do
+/// not line up at the origin.
+const OCTAVE_OFFSET: f64 = 71.37;
+
+macro_rules! octave_noise {
+    ($name:ident) => {
+        impl<N: Noise> $name<N> {
+            /// Layer octaves of a noise, beginning at its frequency.
+            ///
+            /// By default, this has 4 octaves, a lacunarity of 2,
+            /// and a gain of 0.5.
+            pub fn new(noise: N) -> Self {
+                $name {
+                    noise,
+                    octaves: 4,
+                    lacunarity: 2.0,
+                    gain: 0.5,
+                }
+            }
+            
+            /// Set the number of octaves.
+            pub fn octaves(mut self, octaves: u32) -> Self {
+                self.octaves = octaves;
+                self
+            }
+            
+            /// Set the frequency multiplier between octaves.
+            pub fn lacunarity(mut self, lacunarity: f64) -> Self {
+                self.lacunarity = lacunarity;
+                self
+            }
+            
+            /// Set the amplitude multiplier between octaves.
+            pub fn gain(mut self, gain: f32) -> Self {
+                self.gain = gain;
+                self
+            }
+            
+            /// Weighted average of a function of each octave.
+            fn layer<F>(&self, octave: F) -> f32
+            where
+                F: Fn(f64, f64) -> f32,
+            {
+                let mut sum = 0.0;
+                let mut total = 0.0;
+                let mut scale = 1.0;
+                let mut amplitude = 1.0;
+                for i in 0..self.octaves {
+                    sum += amplitude * octave(scale, i as f64 * OCTAVE_OFFSET);
+                    total += amplitude;
+                    scale *= self.lacunarity;
+                    amplitude *= self.gain;
+                }
+                if total > 0.0 { sum / total } else { 0.0 }
+            }
+        }
+    };
+}
+
+/// Fractal Brownian motion, which sums octaves of a noise at
+/// increasing frequency and decreasing amplitude.
+///
+/// Output is in the range of the layered noise.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Fbm<N> {
+    noise: N,
+    octaves: u32,
+    lacunarity: f64,
+    gain: f32,
+}
+
+octave_noise!(Fbm);
+
+impl<N: Noise> Noise for Fbm<N> {
+    fn noise2(&self, x: f64, y: f64) -> f32 {
+        self.layer(|scale, offset| self.noise.noise2(
+            x * scale + offset,
+            y * scale + offset))
+    }
+    
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f32 {
+        self.layer(|scale, offset| self.noise.noise3(
+            x * scale + offset,
+            y * scale + offset,
+            z * scale + offset))
+    }
+}
+
+/// Ridged multifractal, which sums octaves of `(1 - |n|)²`, forming
+/// sharp ridges where the noise crosses zero.
+///
+/// Output is in [0, 1] for noise in [-1, 1].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ridged<N> {
+    noise: N,
+    octaves: u32,
+    lacunarity: f64,
+    gain: f32,
+}
+
+octave_noise!(Ridged);
+
+fn ridge(n: f32) -> f32 {
+    let r = 1.0 - n.abs();
+    r * r
+}
+
+impl<N: Noise> Noise for Ridged<N> {
+    fn noise2(&self, x: f64, y: f64) -> f32 {
+        self.layer(|scale, offset| ridge(self.noise.noise2(
+            x * scale + offset,
+            y * scale + offset)))
+    }
+    
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f32 {
+        self.layer(|scale, offset| ridge(self.noise.noise3(
+            x * scale + offset,
+            y * scale + offset,
+            z * scale + offset)))
+    }
+}
@@ -1,5 +1,6 @@
 //! Extensions to integer ranges.
 
+use crate::scalar::Scalar;
 use std::ops::{
     RangeBounds,
     Range,
@@ -13,36 +14,38 @@ use std::ops::{
 
 /// Range from 0 to N.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct Range0To {
-    pub end: i32,
+pub struct Range0To<S = i32> {
+    pub end: S,
 }
 
-impl RangeBounds<i32> for Range0To {
-    fn start_bound(&self) -> Bound<&i32> {
-        Bound::Included(&0)
+impl<S: Scalar> RangeBounds<S> for Range0To<S> {
+    fn start_bound(&self) -> Bound<&S> {
+        Bound::Included(S::zero_ref())
     }
     
-    fn end_bound(&self) -> Bound<&i32> {
+    fn end_bound(&self) -> Bound<&S> {
         Bound::Excluded(&self.end)
     }
 }
 
 
 /// Performing multiplication on RangeBounds types.
-pub trait RangeBoundsTimes: Sized + Clone {
-    fn times(&self, n: i32) -> Self;
+///
+/// Each index in the range becomes n indices in the result.
+pub trait RangeBoundsTimes<S: Scalar = i32>: Sized + Clone {
+    fn times(&self, n: S) -> Self;
 }
 
-impl RangeBoundsTimes for Range0To {
-    fn times(&self, n: i32) -> Range0To {
+impl<S: Scalar> RangeBoundsTimes<S> for Range0To<S> {
+    fn times(&self, n: S) -> Range0To<S> {
         Range0To {
             end: self.end * n,
         }
     }
 }
 
-impl RangeBoundsTimes for Range<i32> {
-    fn times(&self, n: i32) -> Self {
+impl<S: Scalar> RangeBoundsTimes<S> for Range<S> {
+    fn times(&self, n: S) -> Self {
         Range {
             start: self.start * n,
             end: self.end * n,
@@ -50,68 +53,139 @@ impl RangeBoundsTimes for Range<i32> {
     }
 }
 
-impl RangeBoundsTimes for RangeFrom<i32> {
-    fn times(&self, n: i32) -> Self {
+impl<S: Scalar> RangeBoundsTimes<S> for RangeFrom<S> {
+    fn times(&self, n: S) -> Self {
         RangeFrom {
             start: self.start * n,
         }
     }
 }
 
-impl RangeBoundsTimes for RangeFull {
-    fn times(&self, _n: i32) -> Self {
+impl<S: Scalar> RangeBoundsTimes<S> for RangeFull {
+    fn times(&self, _n: S) -> Self {
         RangeFull
     }
 }
 
-impl RangeBoundsTimes for RangeInclusive<i32> {
-    fn times(&self, n: i32) -> Self {
+impl<S: Scalar> RangeBoundsTimes<S> for RangeInclusive<S> {
+    fn times(&self, n: S) -> Self {
         RangeInclusive::new(
             *self.start() * n,
-            *self.end() * n,
+            *self.end() * n + n - S::ONE,
         )
     }
 }
 
-impl RangeBoundsTimes for RangeTo<i32> {
-    fn times(&self, n: i32) -> Self {
+impl<S: Scalar> RangeBoundsTimes<S> for RangeTo<S> {
+    fn times(&self, n: S) -> Self {
         RangeTo {
             end: self.end * n,
         }
     }
 }
 
-impl RangeBoundsTimes for RangeToInclusive<i32> {
-    fn times(&self, n: i32) -> Self {
+impl<S: Scalar> RangeBoundsTimes<S> for RangeToInclusive<S> {
+    fn times(&self, n: S) -> Self {
         RangeToInclusive {
-            end: self.end * n,
+            end: self.end * n + n - S::ONE,
+        }
+    }
+}
+
+
+/// Performing block division on RangeBounds types.
+///
+/// The result contains the indices of every size-n block which
+/// overlaps this range, so partial blocks at the edges are kept.
+pub trait RangeBoundsDiv<S: Scalar = i32>: Sized + Clone {
+    fn div(&self, n: S) -> Self;
+}
+
+fn div_ceil<S: Scalar>(i: S, n: S) -> S {
+    let q = i.div_euclid(n);
+    if q * n == i { q } else { q + S::ONE }
+}
+
+impl<S: Scalar> RangeBoundsDiv<S> for Range0To<S> {
+    fn div(&self, n: S) -> Range0To<S> {
+        Range0To {
+            end: div_ceil(self.end, n),
+        }
+    }
+}
+
+impl<S: Scalar> RangeBoundsDiv<S> for Range<S> {
+    fn div(&self, n: S) -> Self {
+        let start = self.start.div_euclid(n);
+        let end = div_ceil(self.end, n);
+        Range {
+            start,
+            end: if self.end > self.start { end } else { start },
         }
     }
 }
 
+impl<S: Scalar> RangeBoundsDiv<S> for RangeFrom<S> {
+    fn div(&self, n: S) -> Self {
+        RangeFrom {
+            start: self.start.div_euclid(n),
+        }
+    }
+}
+
+impl<S: Scalar> RangeBoundsDiv<S> for RangeFull {
+    fn div(&self, _n: S) -> Self {
+        RangeFull
+    }
+}
+
+impl<S: Scalar> RangeBoundsDiv<S> for RangeInclusive<S> {
+    fn div(&self, n: S) -> Self {
+        RangeInclusive::new(
+            self.start().div_euclid(n),
+            self.end().div_euclid(n),
+        )
+    }
+}
+
+impl<S: Scalar> RangeBoundsDiv<S> for RangeTo<S> {
+    fn div(&self, n: S) -> Self {
+        RangeTo {
+            end: div_ceil(self.end, n),
+        }
+    }
+}
+
+impl<S: Scalar> RangeBoundsDiv<S> for RangeToInclusive<S> {
+    fn div(&self, n: S) -> Self {
+        RangeToInclusive {
+            end: self.end.div_euclid(n),
+        }
+    }
+}
 
 /// Performing addition on RangeBounds types.
-pub trait RangeBoundsPlus {
-    type Output: RangeBounds<i32> + Clone;
+pub trait RangeBoundsPlus<S: Scalar = i32> {
+    type Output: RangeBounds<S> + Clone;
     
-    fn plus(&self, n: i32) -> Self::Output;
+    fn plus(&self, n: S) -> Self::Output;
 }
 
-impl RangeBoundsPlus for Range0To {
-    type Output = Range<i32>;
+impl<S: Scalar> RangeBoundsPlus<S> for Range0To<S> {
+    type Output = Range<S>;
     
-    fn plus(&self, n: i32) -> Range<i32> {
+    fn plus(&self, n: S) -> Range<S> {
         Range {
-            start: 1,
+            start: n,
             end: self.end + n,
         }
     }
 }
 
-impl RangeBoundsPlus for Range<i32> {
+impl<S: Scalar> RangeBoundsPlus<S> for Range<S> {
     type Output = Self;
     
-    fn plus(&self, n: i32) -> Self {
+    fn plus(&self, n: S) -> Self {
         Range {
             start: self.start + n,
             end: self.end + n,
@@ -119,28 +193,28 @@ impl RangeBoundsPlus for Range<i32> {
     }
 }
 
-impl RangeBoundsPlus for RangeFrom<i32> {
+impl<S: Scalar> RangeBoundsPlus<S> for RangeFrom<S> {
     type Output = Self;
     
-    fn plus(&self, n: i32) -> Self {
+    fn plus(&self, n: S) -> Self {
         RangeFrom {
             start: self.start + n,
         }
     }
 }
 
-impl RangeBoundsPlus for RangeFull {
+impl<S: Scalar> RangeBoundsPlus<S> for RangeFull {
     type Output = Self;
     
-    fn plus(&self, _n: i32) -> Self {
+    fn plus(&self, _n: S) -> Self {
         RangeFull
     }
 }
 
-impl RangeBoundsPlus for RangeInclusive<i32> {
+impl<S: Scalar> RangeBoundsPlus<S> for RangeInclusive<S> {
     type Output = Self;
     
-    fn plus(&self, n: i32) -> Self {
+    fn plus(&self, n: S) -> Self {
         RangeInclusive::new(
             *self.start() + n,
             *self.end() + n,
@@ -148,20 +222,20 @@ impl RangeBoundsPlus for RangeInclusive<i32> {
     }
 }
 
-impl RangeBoundsPlus for RangeTo<i32> {
+impl<S: Scalar> RangeBoundsPlus<S> for RangeTo<S> {
     type Output = Self;
     
-    fn plus(&self, n: i32) -> Self {
+    fn plus(&self, n: S) -> Self {
         RangeTo {
             end: self.end + n,
         }
     }
 }
 
-impl RangeBoundsPlus for RangeToInclusive<i32> {
+impl<S: Scalar> RangeBoundsPlus<S> for RangeToInclusive<S> {
     type Output = Self;
     
-    fn plus(&self, n: i32) -> Self {
+    fn plus(&self, n: S) -> Self {
         RangeToInclusive {
             end: self.end + n,
         }
@@ -170,22 +244,103 @@ impl RangeBoundsPlus for RangeToInclusive<i32> {
 
 
 /// A range which is not unbounded on either end.
-pub trait BoundRange {
-    fn lower_inclusive(&self) -> i32;
-    fn upper_exclusive(&self) -> i32;
+pub trait BoundRange<S: Scalar = i32> {
+    fn lower_inclusive(&self) -> S;
+    fn upper_exclusive(&self) -> S;
+    
+    /// Wrap a coordinate around into this range.
+    ///
+    /// This does not underflow for unsigned scalars.
+    fn wrap(&self, i: S) -> S {
+        let start = self.lower_inclusive();
+        let end = self.upper_exclusive();
+        let len = end - start;
+        if i >= start {
+            (i - start) % len + start
+        } else {
+            let back = (start - i) % len;
+            if back == S::ZERO { start } else { end - back }
+        }
+    }
 }
 
-impl BoundRange for Range<i32> {
-    fn lower_inclusive(&self) -> i32 { self.start }
-    fn upper_exclusive(&self) -> i32 { self.end }
+impl<S: Scalar> BoundRange<S> for Range<S> {
+    fn lower_inclusive(&self) -> S { self.start }
+    fn upper_exclusive(&self) -> S { self.end }
 }
 
-impl BoundRange for RangeInclusive<i32> {
-    fn lower_inclusive(&self) -> i32 { *self.start() }
-    fn upper_exclusive(&self) -> i32 { *self.end() - 1 }
+impl<S: Scalar> BoundRange<S> for RangeInclusive<S> {
+    fn lower_inclusive(&self) -> S { *self.start() }
+    fn upper_exclusive(&self) -> S { *self.end() + S::ONE }
 }
 
-impl BoundRange for Range0To {
-    fn lower_inclusive(&self) -> i32 { 0 }
-    fn upper_exclusive(&self) -> i32 { self.end }
-}
\ No newline at end of file
+impl<S: Scalar> BoundRange<S> for Range0To<S> {
+    fn lower_inclusive(&self) -> S { S::ZERO }
+    fn upper_exclusive(&self) -> S { self.end }
+}
+
+
+/// Verify that a is more strict than b.
+pub(crate) fn more_strict<S: Scalar>(a: impl RangeBounds<S>, b: impl RangeBounds<S>) -> bool {
+    
+    fn lower_inclusive<S: Scalar>(bound: Bound<&S>) -> Option<S> {
+        match bound {
+            Bound::Included(&i) => Some(i),
+            Bound::Excluded(&i) => Some(i + S::ONE),
+            Bound::Unbounded   => None,
+        }
+    }
+    
+    // exclusive rather than inclusive, so that unsigned scalars
+    // do not underflow on empty ranges
+    fn upper_exclusive<S: Scalar>(bound: Bound<&S>) -> Option<S> {
+        match bound {
+            Bound::Included(&i) => Some(i + S::ONE),
+            Bound::Excluded(&i) => Some(i),
+            Bound::Unbounded   => None,
+        }
+    }
+    
+    let lower_ok = match (
+        lower_inclusive(a.start_bound()), 
+        lower_inclusive(b.start_bound()),
+    ) {
+        (Some(i1), Some(i2)) => i1 >= i2,
+        (Some(_), None)      => true,
+        (None, Some(_))      => false,
+        (None, None)         => true,
+    };
+    if !lower_ok { return false; }
+    
+    let upper_ok = match (
+        upper_exclusive(a.end_bound()), 
+        upper_exclusive(b.end_bound()),
+    ) {
+        (Some(i1), Some(i2)) => i1 <= i2,
+        (Some(_), None)      => true,
+        (None, Some(_))      => false,
+        (None, None)         => true,
+    };
+    if !upper_ok { return false; }
+    
+    true
+}
+
+/// Place a source range to begin at offset, and clip it to a
+/// destination range.
+///
+/// Returns the covered part of the destination range, and the
+/// source coordinate corresponding to its start.
+pub(crate) fn clip<S: Scalar>(src: Range<S>, offset: S, dst: Range<S>) -> Option<(Range<S>, S)> {
+    if src.start >= src.end {
+        return None;
+    }
+    let len = src.end - src.start;
+    let start = offset.max(dst.start);
+    let end = (offset + len).min(dst.end);
+    if start < end {
+        Some((start..end, src.start + (start - offset)))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,363 @@
+//! Sampling grids at fractional coordinates.
+//!
+//! Integer coordinates are the centers of elements, so sampling
+//! at `<1.0, 2.0>` produces exactly the element at `<1, 2>`.
+//!
+//! Linear and cubic filters read neighbouring elements, skipping
+//! those whose weight is zero, so samples at integer coordinates
+//! only read the element there. Other samples near the edge of a
+//! grid tap coordinates outside it. By default
+//! these are read as-is, so grids made unbounded with `wrapping` or
+//! `oob_handler` are sampled with their own out-of-bounds behavior,
+//! and other grids panic. Samplers built with `clamp(true)` instead
+//! clamp taps which fall outside a finite bound to the nearest
+//! element within it.
+
+use crate::{
+    scalar::Scalar,
+    grid2::Grid2Get,
+    grid3::Grid3Get,
+};
+use mint::{Point2, Point3, Vector2, Vector3, Vector4};
+use std::ops::{RangeBounds, Bound};
+
+/// Element type which can be linearly combined.
+pub trait Lerp: Sized {
+    fn scale(self, k: f32) -> Self;
+    
+    fn add(self, other: Self) -> Self;
+    
+    /// Linear interpolation, where t = 0 is self and t = 1 is other.
+    fn lerp(self, other: Self, t: f32) -> Self {
+        self.scale(1.0 - t).add(other.scale(t))
+    }
+}
+
+impl Lerp for f32 {
+    fn scale(self, k: f32) -> Self { self * k }
+    fn add(self, other: Self) -> Self { self + other }
+}
+
+impl Lerp for f64 {
+    fn scale(self, k: f32) -> Self { self * k as f64 }
+    fn add(self, other: Self) -> Self { self + other }
+}
+
+impl Lerp for Vector2<f32> {
+    fn scale(self, k: f32) -> Self {
+        Vector2 { x: self.x * k, y: self.y * k }
+    }
+    
+    fn add(self, other: Self) -> Self {
+        Vector2 { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl Lerp for Vector3<f32> {
+    fn scale(self, k: f32) -> Self {
+        Vector3 { x: self.x * k, y: self.y * k, z: self.z * k }
+    }
+    
+    fn add(self, other: Self) -> Self {
+        Vector3 { x: self.x + other.x, y: self.y + other.y, z: self.z + other.z }
+    }
+}
+
+impl Lerp for Vector4<f32> {
+    fn scale(self, k: f32) -> Self {
+        Vector4 { x: self.x * k, y: self.y * k, z: self.z * k, w: self.w * k }
+    }
+    
+    fn add(self, other: Self) -> Self {
+        Vector4 { x: self.x + other.x, y: self.y + other.y, z: self.z + other.z, w: self.w + other.w }
+    }
+}
+
+/// Reconstruction filter.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Filter {
+    /// Nearest element.
+    Nearest,
+    /// Bilinear in 2D, trilinear in 3D.
+    Linear,
+    /// Catmull-Rom bicubic in 2D, tricubic in 3D.
+    Cubic,
+}
+
+/// Samples a Grid2 at fractional coordinates.
+pub struct Sampler2<G> {
+    grid: G,
+    filter: Filter,
+    clamp: bool,
+}
+
+impl<G> Sampler2<G> {
+    pub fn new(grid: G, filter: Filter) -> Self {
+        Sampler2 {
+            grid,
+            filter,
+            clamp: false,
+        }
+    }
+    
+    /// Builder method to clamp taps into the grid's finite bounds,
+    /// rather than reading them from the grid as-is.
+    ///
+    /// Sampling a grid which is empty along a clamped axis panics.
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+    
+    pub fn filter(&self) -> Filter {
+        self.filter
+    }
+    
+    pub fn into_inner(self) -> G {
+        self.grid
+    }
+    
+    /// Sample with this sampler's filter.
+    pub fn sample<I, S>(&self, pos: I) -> G::Item
+    where
+        I: Into<Point2<f32>>,
+        G: Grid2Get<S>,
+        G::Item: Lerp,
+        S: Scalar,
+    {
+        match self.filter {
+            Filter::Nearest => self.nearest(pos),
+            Filter::Linear => self.bilinear(pos),
+            Filter::Cubic => self.bicubic(pos),
+        }
+    }
+    
+    pub fn nearest<I, S>(&self, pos: I) -> G::Item
+    where
+        I: Into<Point2<f32>>,
+        G: Grid2Get<S>,
+        S: Scalar,
+    {
+        let pos = pos.into();
+        self.tap(pos.x.round() as i64, pos.y.round() as i64)
+    }
+    
+    pub fn bilinear<I, S>(&self, pos: I) -> G::Item
+    where
+        I: Into<Point2<f32>>,
+        G: Grid2Get<S>,
+        G::Item: Lerp,
+        S: Scalar,
+    {
+        let pos = pos.into();
+        let (x, tx) = split(pos.x);
+        let (y, ty) = split(pos.y);
+        
+        let row = |y: i64| linear(|i| self.tap(x + i, y), tx);
+        linear(|i| row(y + i), ty)
+    }
+    
+    pub fn bicubic<I, S>(&self, pos: I) -> G::Item
+    where
+        I: Into<Point2<f32>>,
+        G: Grid2Get<S>,
+        G::Item: Lerp,
+        S: Scalar,
+    {
+        let pos = pos.into();
+        let (x, tx) = split(pos.x);
+        let (y, ty) = split(pos.y);
+        
+        let row = |y: i64| cubic(|i| self.tap(x + i, y), tx);
+        cubic(|i| row(y + i), ty)
+    }
+    
+    fn tap<S>(&self, x: i64, y: i64) -> G::Item
+    where
+        G: Grid2Get<S>,
+        S: Scalar,
+    {
+        let (x, y) = if self.clamp {
+            (clamp(x, &self.grid.x_bound()), clamp(y, &self.grid.y_bound()))
+        } else {
+            (x, y)
+        };
+        self.grid.get(Vector2 {
+            x: S::from_i64(x),
+            y: S::from_i64(y),
+        })
+    }
+}
+
+/// Samples a Grid3 at fractional coordinates.
+pub struct Sampler3<G> {
+    grid: G,
+    filter: Filter,
+    clamp: bool,
+}
+
+impl<G> Sampler3<G> {
+    pub fn new(grid: G, filter: Filter) -> Self {
+        Sampler3 {
+            grid,
+            filter,
+            clamp: false,
+        }
+    }
+    
+    /// Builder method to clamp taps into the grid's finite bounds,
+    /// rather than reading them from the grid as-is.
+    ///
+    /// Sampling a grid which is empty along a clamped axis panics.
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+    
+    pub fn filter(&self) -> Filter {
+        self.filter
+    }
+    
+    pub fn into_inner(self) -> G {
+        self.grid
+    }
+    
+    /// Sample with this sampler's filter.
+    pub fn sample<I, S>(&self, pos: I) -> G::Item
+    where
+        I: Into<Point3<f32>>,
+        G: Grid3Get<S>,
+        G::Item: Lerp,
+        S: Scalar,
+    {
+        match self.filter {
+            Filter::Nearest => self.nearest(pos),
+            Filter::Linear => self.trilinear(pos),
+            Filter::Cubic => self.tricubic(pos),
+        }
+    }
+    
+    pub fn nearest<I, S>(&self, pos: I) -> G::Item
+    where
+        I: Into<Point3<f32>>,
+        G: Grid3Get<S>,
+        S: Scalar,
+    {
+        let pos = pos.into();
+        self.tap(pos.x.round() as i64, pos.y.round() as i64, pos.z.round() as i64)
+    }
+    
+    pub fn trilinear<I, S>(&self, pos: I) -> G::Item
+    where
+        I: Into<Point3<f32>>,
+        G: Grid3Get<S>,
+        G::Item: Lerp,
+        S: Scalar,
+    {
+        let pos = pos.into();
+        let (x, tx) = split(pos.x);
+        let (y, ty) = split(pos.y);
+        let (z, tz) = split(pos.z);
+        
+        let row = |y: i64, z: i64| linear(|i| self.tap(x + i, y, z), tx);
+        let plane = |z: i64| linear(|i| row(y + i, z), ty);
+        linear(|i| plane(z + i), tz)
+    }
+    
+    pub fn tricubic<I, S>(&self, pos: I) -> G::Item
+    where
+        I: Into<Point3<f32>>,
+        G: Grid3Get<S>,
+        G::Item: Lerp,
+        S: Scalar,
+    {
+        let pos = pos.into();
+        let (x, tx) = split(pos.x);
+        let (y, ty) = split(pos.y);
+        let (z, tz) = split(pos.z);
+        
+        let row = |y: i64, z: i64| cubic(|i| self.tap(x + i, y, z), tx);
+        let plane = |z: i64| cubic(|i| row(y + i, z), ty);
+        cubic(|i| plane(z + i), tz)
+    }
+    
+    fn tap<S>(&self, x: i64, y: i64, z: i64) -> G::Item
+    where
+        G: Grid3Get<S>,
+        S: Scalar,
+    {
+        let (x, y, z) = if self.clamp {
+            (
+                clamp(x, &self.grid.x_bound()),
+                clamp(y, &self.grid.y_bound()),
+                clamp(z, &self.grid.z_bound()),
+            )
+        } else {
+            (x, y, z)
+        };
+        self.grid.get(Vector3 {
+            x: S::from_i64(x),
+            y: S::from_i64(y),
+            z: S::from_i64(z),
+        })
+    }
+}
+
+/// Split a coordinate into its integer floor and fraction.
+fn split(f: f32) -> (i64, f32) {
+    let floor = f.floor();
+    (floor as i64, f - floor)
+}
+
+/// Clamp a coordinate into a bound, where it is finite.
+///
+/// Panics if the bound is empty.
+fn clamp<S: Scalar>(i: i64, bound: &impl RangeBounds<S>) -> i64 {
+    let lower = match bound.start_bound() {
+        Bound::Included(&s) => s.to_i64(),
+        Bound::Excluded(&s) => s.to_i64() + 1,
+        Bound::Unbounded => i64::MIN,
+    };
+    let upper = match bound.end_bound() {
+        Bound::Included(&e) => e.to_i64(),
+        Bound::Excluded(&e) => e.to_i64() - 1,
+        Bound::Unbounded => i64::MAX,
+    };
+    assert!(lower <= upper, "cannot sample an empty grid");
+    i.max(lower).min(upper)
+}
+
+/// Linear interpolation between tap(0) and tap(1).
+fn linear<T, F>(tap: F, t: f32) -> T
+where
+    T: Lerp,
+    F: Fn(i64) -> T,
+{
+    if t == 0.0 {
+        return tap(0);
+    }
+    tap(0).lerp(tap(1), t)
+}
+
+/// Catmull-Rom interpolation between tap(0) and tap(1).
+fn cubic<T, F>(tap: F, t: f32) -> T
+where
+    T: Lerp,
+    F: Fn(i64) -> T,
+{
+    if t == 0.0 {
+        return tap(0);
+    }
+    
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let w0 = (-t3 + 2.0 * t2 - t) / 2.0;
+    let w1 = (3.0 * t3 - 5.0 * t2 + 2.0) / 2.0;
+    let w2 = (-3.0 * t3 + 4.0 * t2 + t) / 2.0;
+    let w3 = (t3 - t2) / 2.0;
+    
+    tap(-1).scale(w0)
+        .add(tap(0).scale(w1))
+        .add(tap(1).scale(w2))
+        .add(tap(2).scale(w3))
+}
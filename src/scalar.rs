@@ -0,0 +1,73 @@
+//! Integer types usable as grid coordinates.
+
+use std::{
+    ops::{Add, Sub, Mul, Div, Rem},
+    fmt::Debug,
+    hash::Hash,
+};
+
+/// Integer type usable as a grid coordinate.
+///
+/// Grid traits are generic over their coordinate scalar,
+/// defaulting to `i32`.
+pub trait Scalar
+    : 'static
+    + Copy
+    + Ord
+    + Debug
+    + Hash
+    + Add<Output=Self>
+    + Sub<Output=Self>
+    + Mul<Output=Self>
+    + Div<Output=Self>
+    + Rem<Output=Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Static reference to zero.
+    ///
+    /// This exists so that `RangeBounds` can be implemented
+    /// for ranges which implicitly start at zero.
+    fn zero_ref() -> &'static Self;
+
+    /// Euclidean remainder, which is never negative.
+    fn rem_euclid(self, rhs: Self) -> Self;
+
+    /// Euclidean division, consistent with `rem_euclid`.
+    fn div_euclid(self, rhs: Self) -> Self;
+
+    /// Convert a non-negative coordinate into an array index.
+    fn to_usize(self) -> usize;
+
+    /// Convert an array index into a coordinate.
+    fn from_usize(n: usize) -> Self;
+
+    /// Convert a coordinate into a signed integer.
+    fn to_i64(self) -> i64;
+
+    /// Convert a signed integer into a coordinate.
+    fn from_i64(n: i64) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ($($t:ty),*)=>{$(
+        impl Scalar for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn zero_ref() -> &'static Self { &0 }
+
+            fn rem_euclid(self, rhs: Self) -> Self { <$t>::rem_euclid(self, rhs) }
+            fn div_euclid(self, rhs: Self) -> Self { <$t>::div_euclid(self, rhs) }
+
+            fn to_usize(self) -> usize { self as usize }
+            fn from_usize(n: usize) -> Self { n as $t }
+
+            fn to_i64(self) -> i64 { self as i64 }
+            fn from_i64(n: i64) -> Self { n as $t }
+        }
+    )*};
+}
+
+impl_scalar!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
@@ -0,0 +1,295 @@
+//! Borrowed grids.
+//!
+//! Combinators take their grid by value. Wrapping a borrow of a grid
+//! in a view lets combinators be applied without consuming it, after
+//! which the grid can be used again.
+
+use crate::{
+    scalar::Scalar,
+    grid2::{Grid2, Grid2Len, Grid2Get, Grid2Set, Grid2Ref, Grid2Mut},
+    grid3::{Grid3, Grid3Len, Grid3Get, Grid3Set, Grid3Ref, Grid3Mut},
+    gridn::{GridN, GridNLen, GridNGet, GridNSet, GridNRef, GridNMut},
+};
+use mint::{Vector2, Vector3};
+
+/// Immutably borrowed grid.
+pub struct GridRef<'a, G: ?Sized> {
+    inner: &'a G,
+}
+
+impl<'a, G: ?Sized> GridRef<'a, G> {
+    pub fn new(inner: &'a G) -> Self {
+        GridRef { inner }
+    }
+    
+    pub fn inner(&self) -> &'a G {
+        self.inner
+    }
+}
+
+impl<G: ?Sized> Clone for GridRef<'_, G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G: ?Sized> Copy for GridRef<'_, G> {}
+
+/// Mutably borrowed grid.
+pub struct GridMut<'a, G: ?Sized> {
+    inner: &'a mut G,
+}
+
+impl<'a, G: ?Sized> GridMut<'a, G> {
+    pub fn new(inner: &'a mut G) -> Self {
+        GridMut { inner }
+    }
+    
+    pub fn inner(&self) -> &G {
+        self.inner
+    }
+    
+    pub fn inner_mut(&mut self) -> &mut G {
+        self.inner
+    }
+}
+
+// ==== Grid2 ====
+
+macro_rules! impl_grid2_read {
+    ($view:ident)=>{
+        impl<G, S> Grid2<S> for $view<'_, G>
+        where
+            G: Grid2<S> + ?Sized,
+            S: Scalar,
+        {
+            type Item = G::Item;
+            type XBound = G::XBound;
+            type YBound = G::YBound;
+            
+            fn x_bound(&self) -> Self::XBound { self.inner.x_bound() }
+            fn y_bound(&self) -> Self::YBound { self.inner.y_bound() }
+        }
+        
+        impl<G, S> Grid2Len<S> for $view<'_, G>
+        where
+            G: Grid2Len<S> + ?Sized,
+            S: Scalar,
+        {}
+        
+        impl<G, S> Grid2Get<S> for $view<'_, G>
+        where
+            G: Grid2Get<S> + ?Sized,
+            S: Scalar,
+        {
+            fn get<I>(&self, coord: I) -> Self::Item
+            where
+                I: Into<Vector2<S>>
+            {
+                self.inner.get(coord)
+            }
+        }
+        
+        impl<G, S> Grid2Ref<S> for $view<'_, G>
+        where
+            G: Grid2Ref<S> + ?Sized,
+            S: Scalar,
+        {
+            fn idx<I>(&self, coord: I) -> &Self::Item
+            where
+                I: Into<Vector2<S>>
+            {
+                self.inner.idx(coord)
+            }
+        }
+    };
+}
+
+impl_grid2_read!(GridRef);
+impl_grid2_read!(GridMut);
+
+impl<G, S> Grid2Set<S> for GridMut<'_, G>
+where
+    G: Grid2Set<S> + ?Sized,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.set(coord, elem)
+    }
+}
+
+impl<G, S> Grid2Mut<S> for GridMut<'_, G>
+where
+    G: Grid2Mut<S> + ?Sized,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector2<S>>
+    {
+        self.inner.midx(coord)
+    }
+}
+
+// ==== Grid3 ====
+
+macro_rules! impl_grid3_read {
+    ($view:ident)=>{
+        impl<G, S> Grid3<S> for $view<'_, G>
+        where
+            G: Grid3<S> + ?Sized,
+            S: Scalar,
+        {
+            type Item = G::Item;
+            type XBound = G::XBound;
+            type YBound = G::YBound;
+            type ZBound = G::ZBound;
+            
+            fn x_bound(&self) -> Self::XBound { self.inner.x_bound() }
+            fn y_bound(&self) -> Self::YBound { self.inner.y_bound() }
+            fn z_bound(&self) -> Self::ZBound { self.inner.z_bound() }
+        }
+        
+        impl<G, S> Grid3Len<S> for $view<'_, G>
+        where
+            G: Grid3Len<S> + ?Sized,
+            S: Scalar,
+        {}
+        
+        impl<G, S> Grid3Get<S> for $view<'_, G>
+        where
+            G: Grid3Get<S> + ?Sized,
+            S: Scalar,
+        {
+            fn get<I>(&self, coord: I) -> Self::Item
+            where
+                I: Into<Vector3<S>>
+            {
+                self.inner.get(coord)
+            }
+        }
+        
+        impl<G, S> Grid3Ref<S> for $view<'_, G>
+        where
+            G: Grid3Ref<S> + ?Sized,
+            S: Scalar,
+        {
+            fn idx<I>(&self, coord: I) -> &Self::Item
+            where
+                I: Into<Vector3<S>>
+            {
+                self.inner.idx(coord)
+            }
+        }
+    };
+}
+
+impl_grid3_read!(GridRef);
+impl_grid3_read!(GridMut);
+
+impl<G, S> Grid3Set<S> for GridMut<'_, G>
+where
+    G: Grid3Set<S> + ?Sized,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<Vector3<S>>
+    {
+        self.inner.set(coord, elem)
+    }
+}
+
+impl<G, S> Grid3Mut<S> for GridMut<'_, G>
+where
+    G: Grid3Mut<S> + ?Sized,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<Vector3<S>>
+    {
+        self.inner.midx(coord)
+    }
+}
+
+// ==== GridN ====
+
+macro_rules! impl_gridn_read {
+    ($view:ident)=>{
+        impl<G, S, const D: usize> GridN<D, S> for $view<'_, G>
+        where
+            G: GridN<D, S> + ?Sized,
+            S: Scalar,
+        {
+            type Item = G::Item;
+            type Bound = G::Bound;
+            
+            fn bounds(&self) -> [Self::Bound; D] { self.inner.bounds() }
+        }
+        
+        impl<G, S, const D: usize> GridNLen<D, S> for $view<'_, G>
+        where
+            G: GridNLen<D, S> + ?Sized,
+            S: Scalar,
+        {}
+        
+        impl<G, S, const D: usize> GridNGet<D, S> for $view<'_, G>
+        where
+            G: GridNGet<D, S> + ?Sized,
+            S: Scalar,
+        {
+            fn get<I>(&self, coord: I) -> Self::Item
+            where
+                I: Into<[S; D]>
+            {
+                self.inner.get(coord)
+            }
+        }
+        
+        impl<G, S, const D: usize> GridNRef<D, S> for $view<'_, G>
+        where
+            G: GridNRef<D, S> + ?Sized,
+            S: Scalar,
+        {
+            fn idx<I>(&self, coord: I) -> &Self::Item
+            where
+                I: Into<[S; D]>
+            {
+                self.inner.idx(coord)
+            }
+        }
+    };
+}
+
+impl_gridn_read!(GridRef);
+impl_gridn_read!(GridMut);
+
+impl<G, S, const D: usize> GridNSet<D, S> for GridMut<'_, G>
+where
+    G: GridNSet<D, S> + ?Sized,
+    S: Scalar,
+{
+    fn set<I>(&mut self, coord: I, elem: Self::Item)
+    where
+        I: Into<[S; D]>
+    {
+        self.inner.set(coord, elem)
+    }
+}
+
+impl<G, S, const D: usize> GridNMut<D, S> for GridMut<'_, G>
+where
+    G: GridNMut<D, S> + ?Sized,
+    S: Scalar,
+{
+    fn midx<I>(&mut self, coord: I) -> &mut Self::Item
+    where
+        I: Into<[S; D]>
+    {
+        self.inner.midx(coord)
+    }
+}
@@ -0,0 +1,71 @@
+extern crate grid_trait;
+extern crate mint;
+
+use grid_trait::{
+    grid3::{self, Grid3Get, Grid3Len},
+    io::binary::*,
+};
+use mint::Vector3;
+
+fn header(compression: Compression, lens: [i64; 3]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_header(&mut out, &Header {
+        version: VERSION,
+        compression,
+        element_size: 1,
+        bounds: lens.iter().map(|&len| 0..len).collect(),
+    }).unwrap();
+    out
+}
+
+#[test]
+fn round_trips_with_every_compression() {
+    let grid = grid3::alloc_gen(5, 4, 3, |c: Vector3<i32>| (c.x / 2 + c.y * c.z) as u16);
+    for &compression in &[Compression::None, Compression::Rle, Compression::Lz] {
+        let mut bytes = Vec::new();
+        write_grid3(&grid, &mut bytes, compression).unwrap();
+        let read: grid3::backends::heap::ArrayGrid3<u16> = read_grid3(&bytes[..]).unwrap();
+        assert_eq!((read.x_len(), read.y_len(), read.z_len()), (5, 4, 3));
+        for z in 0..3 {
+            for y in 0..4 {
+                for x in 0..5 {
+                    assert_eq!(read.get([x, y, z]), grid.get([x, y, z]));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn rejects_runs_past_the_declared_length() {
+    let mut bytes = header(Compression::Rle, [4, 4, 4]);
+    bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+    bytes.push(7);
+    match read_grid3::<u8, i32, _>(&bytes[..]) {
+        Err(Error::Corrupt(_)) => (),
+        other => panic!("expected a corrupt run, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn rejects_huge_headers_without_allocating() {
+    let mut bytes = header(Compression::Rle, [1 << 20, 1 << 20, 1 << 20]);
+    bytes.extend_from_slice(&8u32.to_le_bytes());
+    bytes.push(7);
+    match read_grid3::<u8, i64, _>(&bytes[..]) {
+        Err(Error::Truncated) | Err(Error::TooLarge) => (),
+        other => panic!("expected an error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn reports_truncation() {
+    let grid = grid3::alloc(3, 3, 3, 1u8);
+    let mut bytes = Vec::new();
+    write_grid3(&grid, &mut bytes, Compression::None).unwrap();
+    bytes.truncate(bytes.len() - 1);
+    match read_grid3::<u8, i32, _>(&bytes[..]) {
+        Err(Error::Truncated) => (),
+        other => panic!("expected truncation, got {:?}", other.map(|_| ())),
+    }
+}
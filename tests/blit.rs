@@ -0,0 +1,103 @@
+extern crate grid_trait;
+extern crate mint;
+
+use grid_trait::{
+    grid2::{self, Grid2, Grid2Get, backends::heap::ArrayGrid2},
+    grid3::{self, Grid3Get},
+    algo::blit::*,
+};
+use mint::{Vector2, Vector3};
+
+fn src2() -> ArrayGrid2<i32> {
+    grid2::alloc_gen(3, 4, |c: Vector2<i32>| 100 + c.x + 10 * c.y)
+}
+
+/// Expected destination element after blitting src2 at an offset.
+fn expected(x: i32, y: i32, offset: [i32; 2]) -> i32 {
+    let (sx, sy) = (x - offset[0], y - offset[1]);
+    if (0..3).contains(&sx) && (0..4).contains(&sy) {
+        100 + sx + 10 * sy
+    } else {
+        0
+    }
+}
+
+const OFFSETS: [[i32; 2]; 6] = [[0, 0], [1, 2], [-2, -1], [4, 3], [-3, 0], [5, 6]];
+
+#[test]
+fn blit_clips_to_the_destination() {
+    for &offset in &OFFSETS {
+        let mut generic = grid2::alloc(5, 5, 0);
+        blit(&src2(), &mut generic, offset);
+        let mut fast = grid2::alloc(5, 5, 0);
+        fast.blit_from(&src2(), offset);
+        for y in 0..5 {
+            for x in 0..5 {
+                assert_eq!(generic.get([x, y]), expected(x, y, offset), "{:?} at {:?}", offset, (x, y));
+                assert_eq!(fast.get([x, y]), expected(x, y, offset), "{:?} at {:?}", offset, (x, y));
+            }
+        }
+    }
+}
+
+#[test]
+fn blit_reads_from_the_source_bounds() {
+    let src = grid2::alloc_gen(6, 6, |c: Vector2<i32>| c.x + 10 * c.y);
+    let mut dst = grid2::alloc(3, 3, 0);
+    blit(&(&src).subview(2..4, 3..5), &mut dst, [1, 1]);
+    assert_eq!(dst.get([1, 1]), 32);
+    assert_eq!(dst.get([2, 2]), 43);
+    assert_eq!(dst.get([0, 0]), 0);
+}
+
+#[test]
+fn masked_and_blended_blits() {
+    let mut dst = grid2::alloc(5, 5, 1);
+    blit_masked(&src2(), &mut dst, [1, 1], |&e| e % 2 == 0);
+    assert_eq!(dst.get([1, 1]), 100);
+    assert_eq!(dst.get([2, 1]), 1);
+    
+    let mut dst = grid2::alloc(5, 5, 1);
+    blit_with(&src2(), &mut dst, [-1, 0], |d, s| d + s);
+    assert_eq!(dst.get([0, 0]), 102);
+    assert_eq!(dst.get([2, 0]), 1);
+}
+
+#[test]
+fn fill_rect_clips_to_the_grid() {
+    let mut generic = grid2::alloc(4, 4, 0);
+    fill_rect(&mut generic, -2..2, 3..9, 7);
+    let mut fast = grid2::alloc(4, 4, 0);
+    fast.fill_rect(-2..2, 3..9, 7);
+    for y in 0..4 {
+        for x in 0..4 {
+            let expected = if x < 2 && y == 3 { 7 } else { 0 };
+            assert_eq!(generic.get([x, y]), expected);
+            assert_eq!(fast.get([x, y]), expected);
+        }
+    }
+}
+
+#[test]
+fn blit3_clips_to_the_destination() {
+    let src = grid3::alloc_gen(2, 3, 2, |c: Vector3<i32>| 1 + c.x + 10 * c.y + 100 * c.z);
+    let offset = [3, -1, 1];
+    let mut generic = grid3::alloc(4, 4, 4, 0);
+    blit3(&src, &mut generic, offset);
+    let mut fast = grid3::alloc(4, 4, 4, 0);
+    fast.blit_from(&src, offset);
+    let mut filled = grid3::alloc(4, 4, 4, 0);
+    fill_box(&mut filled, 3..5, -1..2, 1..3, 1);
+    for z in 0..4 {
+        for y in 0..4 {
+            for x in 0..4 {
+                let (sx, sy, sz) = (x - offset[0], y - offset[1], z - offset[2]);
+                let inside = (0..2).contains(&sx) && (0..3).contains(&sy) && (0..2).contains(&sz);
+                let expected = if inside { 1 + sx + 10 * sy + 100 * sz } else { 0 };
+                assert_eq!(generic.get([x, y, z]), expected);
+                assert_eq!(fast.get([x, y, z]), expected);
+                assert_eq!(filled.get([x, y, z]), inside as i32);
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+extern crate grid_trait;
+extern crate mint;
+
+use grid_trait::{
+    grid2::{self, Grid2, Grid2Get},
+    grid3::{self, Grid3, Grid3Get},
+    algo::convolve::*,
+};
+use mint::{Vector2, Vector3};
+
+#[test]
+fn impulse_reproduces_the_kernel() {
+    let grid = grid2::alloc_gen(5, 5, |c: Vector2<i32>| ([c.x, c.y] == [2, 2]) as u8 as f32);
+    let kernel = grid2::alloc_gen(3, 3, |c: Vector2<i32>| (c.x + 3 * c.y) as f32);
+    let out = convolve(&grid, &kernel, 1..4, 1..4);
+    for y in 0..3 {
+        for x in 0..3 {
+            assert_eq!(out.get([x + 1, y + 1]), (x + 3 * y) as f32);
+        }
+    }
+}
+
+#[test]
+fn separable_matches_full() {
+    let grid = grid2::alloc_gen(8, 6, |c: Vector2<i32>| ((c.x * 7 + c.y * 3) % 5) as f32);
+    let (kx, ky) = ([1.0, 2.0, 1.0], [0.5, 0.5]);
+    let kernel = grid2::alloc_gen(3, 2, |c: Vector2<i32>| kx[c.x as usize] * ky[c.y as usize]);
+    let full = convolve(&grid, &kernel, 1..7, 1..5);
+    let sep = convolve_separable(&grid, &kx, &ky, 1..7, 1..5);
+    for y in 1..5 {
+        for x in 1..7 {
+            assert_eq!(full.get([x, y]), sep.get([x, y]));
+        }
+    }
+}
+
+#[test]
+fn output_has_the_given_bounds() {
+    let grid = grid2::alloc(6, 6, 1.0f32);
+    let out = convolve_separable(&grid, &[1.0, 1.0, 1.0], &[1.0], 2..5, 1..3);
+    assert_eq!((out.x_bound(), out.y_bound()), (2..5, 1..3));
+    assert_eq!(out.get([4, 2]), 3.0);
+    
+    let grid = grid3::alloc_gen(4, 4, 4, |c: Vector3<i32>| c.z as f32);
+    let kernel = grid3::alloc(1, 1, 3, 1.0f32);
+    let out = convolve3(&grid, &kernel, 1..3, 0..2, 1..3);
+    assert_eq!((out.x_bound(), out.y_bound(), out.z_bound()), (1..3, 0..2, 1..3));
+    assert_eq!(out.get([2, 1, 2]), 6.0);
+}
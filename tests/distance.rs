@@ -0,0 +1,100 @@
+extern crate grid_trait;
+extern crate mint;
+
+use grid_trait::{
+    grid2::{self, Grid2, Grid2Get},
+    grid3::{self, Grid3, Grid3Get},
+    algo::distance::*,
+};
+use mint::{Vector2, Vector3};
+
+/// Deterministic scattered feature pattern.
+fn is_feature(x: i32, y: i32, z: i32) -> bool {
+    (x * 7 + y * 13 + z * 5) % 11 == 0
+}
+
+fn brute2(x: i32, y: i32, metric: fn(i32, i32) -> f32) -> f32 {
+    let mut best = f32::INFINITY;
+    for fy in 0..6 {
+        for fx in 0..9 {
+            if is_feature(fx, fy, 0) {
+                best = best.min(metric(fx - x, fy - y));
+            }
+        }
+    }
+    best
+}
+
+#[test]
+fn transforms_match_brute_force_2d() {
+    let grid = grid2::alloc_gen(9, 6, |c: Vector2<i32>| is_feature(c.x, c.y, 0));
+    
+    let euclid = edt(&grid, |b| b);
+    let taxi = manhattan(&grid, |b| b);
+    let cheb = chebyshev(&grid, |b| b);
+    for y in 0..6 {
+        for x in 0..9 {
+            let e = brute2(x, y, |dx, dy| ((dx * dx + dy * dy) as f32).sqrt());
+            assert!((euclid.get([x, y]) - e).abs() < 1e-5, "edt at {:?}", (x, y));
+            assert_eq!(taxi.get([x, y]), brute2(x, y, |dx, dy| (dx.abs() + dy.abs()) as f32));
+            assert_eq!(cheb.get([x, y]), brute2(x, y, |dx, dy| dx.abs().max(dy.abs()) as f32));
+        }
+    }
+}
+
+#[test]
+fn edt3_matches_brute_force() {
+    let grid = grid3::alloc_gen(6, 5, 4, |c: Vector3<i32>| is_feature(c.x, c.y, c.z));
+    let euclid = edt3(&grid, |b| b);
+    for z in 0..4 {
+        for y in 0..5 {
+            for x in 0..6 {
+                let mut best = f32::INFINITY;
+                for fz in 0..4 {
+                    for fy in 0..5 {
+                        for fx in 0..6 {
+                            if is_feature(fx, fy, fz) {
+                                let d = (fx - x).pow(2) + (fy - y).pow(2) + (fz - z).pow(2);
+                                best = best.min((d as f32).sqrt());
+                            }
+                        }
+                    }
+                }
+                assert!((euclid.get([x, y, z]) - best).abs() < 1e-5);
+            }
+        }
+    }
+}
+
+#[test]
+fn signed_edt_is_negative_inside_features() {
+    // a 3-wide square of features in a 7x7 grid
+    let grid = grid2::alloc_gen(7, 7, |c: Vector2<i32>| {
+        (2..5).contains(&c.x) && (2..5).contains(&c.y)
+    });
+    let signed = signed_edt(&grid, |b| b);
+    assert_eq!(signed.get([3, 3]), -2.0);
+    assert_eq!(signed.get([2, 3]), -1.0);
+    assert_eq!(signed.get([1, 3]), 1.0);
+    assert_eq!(signed.get([0, 0]), 8.0f32.sqrt());
+}
+
+#[test]
+fn no_features_is_infinite() {
+    let grid = grid2::alloc(3, 3, false);
+    assert_eq!(edt(&grid, |b| b).get([1, 1]), f32::INFINITY);
+}
+
+#[test]
+fn output_keeps_input_bounds() {
+    let grid = grid2::alloc_gen(9, 6, |c: Vector2<i32>| is_feature(c.x, c.y, 0));
+    let sub = (&grid).subview(3..8, 1..5);
+    let euclid = edt(&sub, |b| b);
+    assert_eq!(euclid.x_bound(), 3..8);
+    assert_eq!(euclid.y_bound(), 1..5);
+    
+    let grid = grid3::alloc(4, 4, 4, true);
+    let euclid = edt3(&(&grid).subview(1..3, 2..4, 0..1), |b| b);
+    assert_eq!((euclid.x_bound(), euclid.y_bound(), euclid.z_bound()), (1..3, 2..4, 0..1));
+    assert_eq!(euclid.get([2, 3, 0]), 0.0);
+}
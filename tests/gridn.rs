@@ -0,0 +1,31 @@
+extern crate grid_trait;
+
+use grid_trait::{
+    grid2::{Grid2, Grid2Get},
+    gridn::{self, *, backends::heap::ArrayGrid1},
+};
+
+#[test]
+fn array_grid4_indexes_every_axis() {
+    let mut grid = gridn::alloc_gen([2, 3, 4, 5], |[x, y, z, w]: [i32; 4]| {
+        x + 10 * y + 100 * z + 1000 * w
+    });
+    
+    assert_eq!(grid.lens(), [2, 3, 4, 5]);
+    assert_eq!(grid.get([1, 2, 3, 4]), 4321);
+    assert_eq!(grid.try_get([2, 0, 0, 0]), None);
+    
+    grid.set([0, 1, 2, 3], -1);
+    assert_eq!(grid.get([0, 1, 2, 3]), -1);
+    assert_eq!(grid.get([1, 1, 2, 3]), 3211);
+}
+
+#[test]
+fn array_grid1_round_trips_through_grid2() {
+    let series: ArrayGrid1<i32> = gridn::alloc_gen([4], |[t]: [i32; 1]| t * t);
+    assert_eq!(series.get([3]), 9);
+    
+    let grid = gridn::alloc_gen([3, 2], |[x, y]: [i32; 2]| x * 10 + y).into_grid2();
+    assert_eq!(grid.get([2, 1]), 21);
+    assert_eq!(grid.into_gridn().get([1, 1]), 11);
+}
@@ -0,0 +1,86 @@
+extern crate grid_trait;
+extern crate mint;
+
+use grid_trait::{
+    grid3::{self, Grid3},
+    algo::mesh::*,
+};
+use mint::Vector3;
+use std::collections::HashMap;
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[test]
+fn marching_cubes_single_element_is_an_octahedron() {
+    let grid = grid3::alloc_gen(5, 5, 5, |c: Vector3<i32>| {
+        if [c.x, c.y, c.z] == [2, 2, 2] { 1.0f32 } else { 0.0 }
+    });
+    let mesh = marching_cubes(&grid, 0.5);
+    
+    assert_eq!(mesh.positions.len(), 6);
+    assert_eq!(mesh.indices.len(), 8 * 3);
+    
+    let center = [2.0, 2.0, 2.0];
+    for (p, n) in mesh.positions.iter().zip(mesh.normals.iter()) {
+        let d = sub([p.x, p.y, p.z], center);
+        assert!((dot(d, d) - 0.25).abs() < 1e-6);
+        assert!(dot(d, [n.x, n.y, n.z]) > 0.0);
+    }
+    
+    // every triangle faces away from the center, and every edge is
+    // shared by exactly two triangles, in opposite directions
+    let mut edges = HashMap::new();
+    for tri in mesh.indices.chunks(3) {
+        let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| {
+            let p = mesh.positions[i as usize];
+            [p.x, p.y, p.z]
+        });
+        let face = cross(sub(b, a), sub(c, a));
+        assert!(dot(face, sub(a, center)) > 0.0);
+        for k in 0..3 {
+            *edges.entry((tri[k], tri[(k + 1) % 3])).or_insert(0) += 1;
+        }
+    }
+    for (&(a, b), &count) in &edges {
+        assert_eq!(count, 1);
+        assert_eq!(edges.get(&(b, a)), Some(&1));
+    }
+}
+
+#[test]
+fn marching_cubes_chunks_match_along_seams() {
+    let grid = grid3::alloc_gen(16, 8, 8, |c: Vector3<i32>| {
+        let d = [c.x as f32 - 7.3, c.y as f32 - 3.6, c.z as f32 - 4.1];
+        d[0] * d[0] + d[1] * d[1] * 1.5 + d[2] * d[2] * 0.7
+    });
+    
+    // chunks spanning 1..7 and 7..13 along x
+    let left = marching_cubes(&(&grid).subview(0..9, 0..8, 0..8), 9.0);
+    let right = marching_cubes(&(&grid).subview(6..15, 0..8, 0..8), 9.0);
+    
+    let key = |p: &mint::Point3<f32>| [p.x, p.y, p.z].map(|f| (f * 1024.0).round() as i64);
+    let normals: HashMap<_, _> = left.positions.iter().map(key)
+        .zip(left.normals.iter())
+        .collect();
+    let mut shared = 0;
+    for (p, n) in right.positions.iter().zip(right.normals.iter()) {
+        if p.x == 7.0 {
+            let m = normals[&key(p)];
+            assert!((n.x - m.x).abs() < 1e-6 && (n.y - m.y).abs() < 1e-6 && (n.z - m.z).abs() < 1e-6);
+            shared += 1;
+        }
+    }
+    assert!(shared > 0);
+    assert!(left.positions.iter().all(|p| p.x <= 7.0));
+    assert!(right.positions.iter().all(|p| p.x >= 7.0));
+}
@@ -0,0 +1,44 @@
+extern crate grid_trait;
+extern crate mint;
+
+use grid_trait::{
+    grid2::{self, Grid2, Grid2Get},
+    algo::{Edge, morph::*},
+};
+use mint::Vector2;
+
+fn cross() -> grid2::backends::heap::ArrayGrid2<bool> {
+    grid2::alloc_gen(3, 3, |c: Vector2<i32>| c.x == 1 || c.y == 1)
+}
+
+#[test]
+fn dilate_grows_by_the_element() {
+    let grid = grid2::alloc_gen(5, 5, |c: Vector2<i32>| [c.x, c.y] == [2, 2]);
+    let dilated = dilate(&grid, &cross(), Edge::Constant(false));
+    for y in 0..5 {
+        for x in 0..5 {
+            let expected = (x - 2i32).abs() + (y - 2i32).abs() <= 1;
+            assert_eq!(dilated.get([x, y]), expected, "at {:?}", (x, y));
+        }
+    }
+}
+
+#[test]
+fn open_removes_features_smaller_than_the_element() {
+    let grid = grid2::alloc_gen(7, 7, |c: Vector2<i32>| {
+        [c.x, c.y] == [0, 6] || ((1..6).contains(&c.x) && (1..4).contains(&c.y))
+    });
+    let opened = open(&grid, &grid2::alloc(3, 3, true), Edge::Constant(false));
+    assert!(!opened.get([0, 6]));
+    assert!(opened.get([1, 1]) && opened.get([5, 3]));
+}
+
+#[test]
+fn output_keeps_input_bounds() {
+    let grid = grid2::alloc_gen(8, 8, |c: Vector2<i32>| [c.x, c.y] == [5, 4]);
+    let sub = (&grid).subview(3..7, 2..6);
+    let dilated = dilate(&sub, &cross(), Edge::Constant(false));
+    assert_eq!((dilated.x_bound(), dilated.y_bound()), (3..7, 2..6));
+    assert!(dilated.get([5, 4]) && dilated.get([6, 4]) && dilated.get([5, 5]));
+    assert!(!dilated.get([6, 5]));
+}
@@ -0,0 +1,59 @@
+extern crate grid_trait;
+
+use grid_trait::{
+    grid2::{Grid2, Grid2Get},
+    grid3::Grid3Get,
+    noise::*,
+};
+
+#[test]
+fn noise_is_deterministic_per_seed() {
+    let a = Perlin::new(7).grid2::<i32>();
+    let b = Perlin::new(7).grid2::<i32>();
+    let c = Perlin::new(8).grid2::<i32>();
+    let coords = [[3, 5], [-20, 41], [100, -7]];
+    assert!(coords.iter().all(|&p| a.get(p) == b.get(p)));
+    assert!(coords.iter().any(|&p| a.get(p) != c.get(p)));
+}
+
+#[test]
+fn perlin_is_zero_at_lattice_points() {
+    let grid = Perlin::new(3).frequency(0.25).grid3::<i32>();
+    assert_eq!(grid.get([8, -4, 12]), 0.0);
+    assert_ne!(grid.get([9, -4, 12]), 0.0);
+}
+
+#[test]
+fn neighbouring_cells_differ_far_from_the_origin() {
+    let far = 1i64 << 40;
+    let value = Value::new(1).frequency(1.0).grid2::<i64>();
+    let worley = Worley::new(1).frequency(1.0).grid2::<i64>();
+    let simplex = Simplex::new(1).frequency(0.5).grid2::<i64>();
+    for x in far..far + 4 {
+        assert_ne!(value.get([x, far]), value.get([x + 1, far]));
+        assert_ne!(worley.get([x, far]), worley.get([x + 1, far]));
+        assert_ne!(simplex.get([x, far]), simplex.get([x + 1, far]));
+    }
+}
+
+#[test]
+fn outputs_are_in_range() {
+    let value = Value::new(5).grid2::<i32>();
+    let ridged = Ridged::new(Perlin::new(5)).grid2::<i32>();
+    let worley = Worley::new(5).grid2::<i32>();
+    for y in -40..40 {
+        for x in -40..40 {
+            assert!((-1.0..=1.0).contains(&value.get([x, y])));
+            assert!((0.0..=1.0).contains(&ridged.get([x, y])));
+            assert!((0.0..=1.5).contains(&worley.get([x, y])));
+        }
+    }
+}
+
+#[test]
+fn noise_grids_compose_with_combinators() {
+    let grid = Fbm::new(Simplex::new(2)).grid2::<i32>();
+    let expected = grid.get([5, 6]);
+    let collected = grid.view().subview_0to(8, 8).collect();
+    assert_eq!(collected.get([5, 6]), expected);
+}
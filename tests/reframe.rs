@@ -0,0 +1,109 @@
+extern crate grid_trait;
+extern crate mint;
+
+use grid_trait::{
+    grid2::{self, Grid2Get, Grid2Len, backends::heap::ArrayGrid2},
+    grid3::{self, Grid3Get, Grid3Len, backends::heap::ArrayGrid3},
+};
+use mint::{Vector2, Vector3};
+
+const X: i32 = 4;
+const Y: i32 = 3;
+
+fn original(x: i32, y: i32) -> i32 {
+    if (0..X).contains(&x) && (0..Y).contains(&y) { 1 + x + 10 * y } else { 0 }
+}
+
+fn grid() -> ArrayGrid2<i32> {
+    grid2::alloc_gen(X, Y, |c: Vector2<i32>| original(c.x, c.y))
+}
+
+/// Assert that a grid has the given lengths, and that each element
+/// is the original element at that coordinate plus origin.
+fn check(grid: &ArrayGrid2<i32>, lens: [i32; 2], origin: [i32; 2]) {
+    assert_eq!([grid.x_len(), grid.y_len()], lens);
+    for y in 0..lens[1] {
+        for x in 0..lens[0] {
+            assert_eq!(grid.get([x, y]), original(x + origin[0], y + origin[1]),
+                "lens {:?}, origin {:?}, at {:?}", lens, origin, (x, y));
+        }
+    }
+}
+
+#[test]
+fn resize_keeps_elements_at_their_coordinates() {
+    for &lens in &[[6, 5], [2, 2], [6, 1], [1, 6], [0, 3], [4, 3]] {
+        let mut g = grid();
+        g.resize(lens[0], lens[1], 0);
+        check(&g, lens, [0, 0]);
+    }
+}
+
+#[test]
+fn pad_moves_elements_by_the_low_padding() {
+    for &(l, r, t, b) in &[(1, 2, 3, 0), (0, 0, 0, 0), (2, 0, 0, 2)] {
+        let mut g = grid();
+        g.pad(l, r, t, b, 0);
+        check(&g, [X + l + r, Y + t + b], [-l, -t]);
+    }
+}
+
+#[test]
+fn crop_moves_the_range_to_the_origin() {
+    let mut g = grid();
+    g.crop(1..3, 1..3);
+    check(&g, [2, 2], [1, 1]);
+    
+    let mut g = grid();
+    g.crop(0..4, 2..2);
+    check(&g, [4, 0], [0, 2]);
+}
+
+#[test]
+fn shift_origin_keeps_the_size() {
+    for &origin in &[[1, 0], [0, 2], [-1, -1], [2, -1], [-3, 1], [4, 0]] {
+        let mut g = grid();
+        g.shift_origin(origin, 0);
+        check(&g, [X, Y], origin);
+    }
+}
+
+#[test]
+fn reframe_3d() {
+    let f = |x: i32, y: i32, z: i32| {
+        if (0..3).contains(&x) && (0..2).contains(&y) && (0..2).contains(&z) {
+            1 + x + 10 * y + 100 * z
+        } else {
+            0
+        }
+    };
+    let make = || -> ArrayGrid3<i32> {
+        grid3::alloc_gen(3, 2, 2, |c: Vector3<i32>| f(c.x, c.y, c.z))
+    };
+    let check3 = |g: &ArrayGrid3<i32>, lens: [i32; 3], origin: [i32; 3]| {
+        assert_eq!([g.x_len(), g.y_len(), g.z_len()], lens);
+        for z in 0..lens[2] {
+            for y in 0..lens[1] {
+                for x in 0..lens[0] {
+                    assert_eq!(g.get([x, y, z]), f(x + origin[0], y + origin[1], z + origin[2]));
+                }
+            }
+        }
+    };
+    
+    let mut g = make();
+    g.resize(4, 1, 3, 0);
+    check3(&g, [4, 1, 3], [0, 0, 0]);
+    
+    let mut g = make();
+    g.pad([1, 0, 2], [0, 1, 1], 0);
+    check3(&g, [4, 3, 5], [-1, 0, -2]);
+    
+    let mut g = make();
+    g.crop(1..3, 0..1, 1..2);
+    check3(&g, [2, 1, 1], [1, 0, 1]);
+    
+    let mut g = make();
+    g.shift_origin([-1, 1, 1], 0);
+    check3(&g, [3, 2, 2], [-1, 1, 1]);
+}
@@ -0,0 +1,64 @@
+extern crate grid_trait;
+extern crate mint;
+
+use grid_trait::{
+    grid2,
+    grid3,
+    sample::*,
+};
+use mint::{Vector2, Vector3};
+
+fn ramp2() -> grid2::backends::heap::ArrayGrid2<f32> {
+    grid2::alloc_gen(3, 3, |c: Vector2<i32>| (c.x + 10 * c.y) as f32)
+}
+
+#[test]
+fn integer_positions_read_the_element_at_the_edge() {
+    for &filter in &[Filter::Nearest, Filter::Linear, Filter::Cubic] {
+        let sampler = Sampler2::new(ramp2(), filter);
+        assert_eq!(sampler.sample([2.0, 2.0]), 22.0);
+        assert_eq!(sampler.sample([0.0, 2.0]), 20.0);
+        assert_eq!(sampler.sample([2.0, 0.0]), 2.0);
+        
+        let sampler = Sampler3::new(
+            grid3::alloc_gen(2, 2, 2, |c: Vector3<i32>| (c.x + 10 * c.y + 100 * c.z) as f32),
+            filter,
+        );
+        assert_eq!(sampler.sample([1.0, 1.0, 1.0]), 111.0);
+    }
+}
+
+#[test]
+fn linear_interpolates_between_elements() {
+    let sampler = Sampler2::new(ramp2(), Filter::Linear);
+    assert_eq!(sampler.sample([1.5, 0.0]), 1.5);
+    assert_eq!(sampler.sample([0.5, 1.5]), 15.5);
+}
+
+#[test]
+fn cubic_reproduces_a_linear_ramp() {
+    let grid = grid2::alloc_gen(5, 3, |c: Vector2<i32>| (c.x + 10 * c.y) as f32);
+    let sampler = Sampler2::new(grid, Filter::Cubic);
+    assert!((sampler.sample([1.25, 1.0]) - 11.25).abs() < 1e-5);
+}
+
+#[test]
+fn clamped_taps_stay_in_bounds() {
+    let sampler = Sampler2::new(ramp2(), Filter::Linear).clamp(true);
+    assert_eq!(sampler.sample([2.5, 2.0]), 22.0);
+    assert_eq!(sampler.sample([-1.0, 0.0]), 0.0);
+}
+
+#[test]
+#[should_panic(expected = "invalid index")]
+fn unclamped_taps_outside_bounds_panic() {
+    let sampler = Sampler2::new(ramp2(), Filter::Linear);
+    sampler.sample([2.5, 2.0]);
+}
+
+#[test]
+#[should_panic(expected = "cannot sample an empty grid")]
+fn clamped_empty_grid_panics() {
+    let sampler = Sampler2::new(grid2::alloc(0, 3, 0.0f32), Filter::Nearest).clamp(true);
+    sampler.sample([0.0, 0.0]);
+}
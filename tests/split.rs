@@ -0,0 +1,85 @@
+extern crate grid_trait;
+extern crate mint;
+
+use grid_trait::{
+    grid2::{self, Grid2, Grid2Get, Grid2Set},
+    grid3::{self, Grid3, Grid3Get, Grid3Set},
+};
+use mint::Vector3;
+use std::thread;
+
+#[test]
+fn split_halves_keep_parent_coordinates() {
+    let mut grid = grid2::alloc(6, 4, 0);
+    {
+        let (mut left, mut right) = grid.split_at_x(2);
+        assert_eq!((left.x_bound(), right.x_bound()), (0..2, 2..6));
+        left.set([1, 3], 1);
+        right.set([2, 3], 2);
+        assert!(left.try_get([2, 3]).is_none());
+        
+        let (mut bottom, mut top) = right.split_at_y(1);
+        assert_eq!((bottom.y_bound(), top.y_bound()), (0..1, 1..4));
+        bottom.set([5, 0], 3);
+        top.set([5, 1], 4);
+    }
+    assert_eq!(grid.get([1, 3]), 1);
+    assert_eq!(grid.get([2, 3]), 2);
+    assert_eq!(grid.get([5, 0]), 3);
+    assert_eq!(grid.get([5, 1]), 4);
+}
+
+#[test]
+fn chunks_cover_the_grid_once() {
+    let mut grid = grid2::alloc(7, 5, 0);
+    thread::scope(|scope| {
+        for mut chunk in grid.chunks_mut(3, 2) {
+            scope.spawn(move || {
+                let (x, y) = (chunk.x_bound(), chunk.y_bound());
+                for y in y.clone() {
+                    for x in x.clone() {
+                        let n = chunk.get([x, y]);
+                        chunk.set([x, y], n + x + 10 * y + 1);
+                    }
+                }
+            });
+        }
+    });
+    for y in 0..5 {
+        for x in 0..7 {
+            assert_eq!(grid.get([x, y]), x + 10 * y + 1);
+        }
+    }
+}
+
+#[test]
+fn chunks_cover_the_grid3_once() {
+    let mut grid = grid3::alloc(5, 4, 3, 0);
+    for mut chunk in grid.chunks_mut(2, 3, 2) {
+        let (xs, ys, zs) = (chunk.x_bound(), chunk.y_bound(), chunk.z_bound());
+        for z in zs {
+            for y in ys.clone() {
+                for x in xs.clone() {
+                    let n = chunk.get([x, y, z]);
+                    chunk.set([x, y, z], n + 1);
+                }
+            }
+        }
+    }
+    let (mut low, mut high) = grid.split_at_z(1);
+    low.set([4, 3, 0], 5);
+    high.set([4, 3, 1], 6);
+    
+    let check = grid3::alloc_gen(5, 4, 3, |c: Vector3<i32>| match [c.x, c.y, c.z] {
+        [4, 3, 0] => 5,
+        [4, 3, 1] => 6,
+        _ => 1,
+    });
+    for z in 0..3 {
+        for y in 0..4 {
+            for x in 0..5 {
+                assert_eq!(grid.get([x, y, z]), check.get([x, y, z]));
+            }
+        }
+    }
+}